@@ -259,6 +259,7 @@ fn output_format_conversion() -> Result<(), Box<dyn std::error::Error>> {
             let md_format = OutputFormat::Markdown {
                 preserve_structure: true,
                 include_metadata: true,
+                toc: false,
             };
             
             let mut md_output = Vec::new();
@@ -44,12 +44,15 @@ fn directory_processing_example() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create Args for batch processing
     let args = Args {
-        input: PathBuf::new(), // Not used in batch mode
+        command: None,
+        input: None, // Not used in batch mode
         output: None,
         format: "text".to_string(),
         metadata: true,
         preserve_formatting: false,
         pretty: false,
+        css: false,
+        toc: false,
         line_numbers: false,
         text_only: false,
         verbose: true,
@@ -57,6 +60,10 @@ fn directory_processing_example() -> Result<(), Box<dyn std::error::Error>> {
         output_dir: Some(PathBuf::from("./output")),
         overwrite: false,
         max_files: Some(5), // Limit for demo
+        heading_heuristic: false,
+        revisions: "accept".to_string(),
+        extract_images: None,
+        shapes: "include".to_string(),
     };
 
     // Validate arguments
@@ -102,12 +109,15 @@ fn glob_pattern_example() -> Result<(), Box<dyn std::error::Error>> {
         println!("  🔍 Pattern: {}", pattern);
         
         let args = Args {
-            input: PathBuf::new(),
+            command: None,
+            input: None,
             output: None,
             format: "json".to_string(),
             metadata: true,
             preserve_formatting: false,
             pretty: true,
+            css: false,
+            toc: false,
             line_numbers: false,
             text_only: false,
             verbose: false,
@@ -115,6 +125,10 @@ fn glob_pattern_example() -> Result<(), Box<dyn std::error::Error>> {
             output_dir: Some(PathBuf::from("./json_output")),
             overwrite: true,
             max_files: Some(3),
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         match args.get_batch_files() {
@@ -148,12 +162,15 @@ fn batch_format_conversion() -> Result<(), Box<dyn std::error::Error>> {
     ];
 
     let base_args = Args {
-        input: PathBuf::new(),
+        command: None,
+        input: None,
         output: None,
         format: "text".to_string(), // Will be overridden
         metadata: true,
         preserve_formatting: false,
         pretty: true,
+        css: false,
+        toc: false,
         line_numbers: false,
         text_only: false,
         verbose: false,
@@ -161,6 +178,10 @@ fn batch_format_conversion() -> Result<(), Box<dyn std::error::Error>> {
         output_dir: None, // Will be set per format
         overwrite: true,
         max_files: Some(2),
+        heading_heuristic: false,
+        revisions: "accept".to_string(),
+        extract_images: None,
+        shapes: "include".to_string(),
     };
 
     // Get files once
@@ -180,7 +201,7 @@ fn batch_format_conversion() -> Result<(), Box<dyn std::error::Error>> {
     println!("  📄 Found {} files to convert", files.len());
 
     // Convert to each format
-    for (format, extension) in formats {
+    for (format, _extension) in formats {
         println!("  🔄 Converting to {} format...", format);
 
         let mut format_args = clone_args(&base_args);
@@ -224,12 +245,15 @@ fn performance_monitoring_example() -> Result<(), Box<dyn std::error::Error>> {
     println!("  Monitoring batch processing performance...");
 
     let args = Args {
-        input: PathBuf::new(),
+        command: None,
+        input: None,
         output: None,
         format: "text".to_string(),
         metadata: false,
         preserve_formatting: false,
         pretty: false,
+        css: false,
+        toc: false,
         line_numbers: false,
         text_only: true, // Fastest mode
         verbose: false,
@@ -237,6 +261,10 @@ fn performance_monitoring_example() -> Result<(), Box<dyn std::error::Error>> {
         output_dir: Some(PathBuf::from("./perf_output")),
         overwrite: true,
         max_files: Some(10),
+        heading_heuristic: false,
+        revisions: "accept".to_string(),
+        extract_images: None,
+        shapes: "include".to_string(),
     };
 
     let files = match args.get_batch_files() {
@@ -324,12 +352,15 @@ fn error_handling_example() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let args = Args {
-        input: PathBuf::new(),
+        command: None,
+        input: None,
         output: None,
         format: "text".to_string(),
         metadata: true,
         preserve_formatting: false,
         pretty: false,
+        css: false,
+        toc: false,
         line_numbers: false,
         text_only: false,
         verbose: true,
@@ -337,6 +368,10 @@ fn error_handling_example() -> Result<(), Box<dyn std::error::Error>> {
         output_dir: Some(PathBuf::from("./error_test_output")),
         overwrite: true,
         max_files: None,
+        heading_heuristic: false,
+        revisions: "accept".to_string(),
+        extract_images: None,
+        shapes: "include".to_string(),
     };
 
     // Create output directory
@@ -451,6 +486,7 @@ fn convert_single_file(file: &PathBuf, args: &Args) -> Result<(), Box<dyn std::e
         let extension = match args.format.as_str() {
             "json" => "json",
             "markdown" | "md" => "md",
+            "html" => "html",
             _ => "txt",
         };
         format!("{}.{}", stem, extension)
@@ -476,6 +512,12 @@ fn convert_single_file(file: &PathBuf, args: &Args) -> Result<(), Box<dyn std::e
         "markdown" | "md" => OutputFormat::Markdown {
             preserve_structure: !args.text_only,
             include_metadata: args.metadata,
+            toc: args.toc,
+        },
+        "html" => OutputFormat::Html {
+            include_metadata: args.metadata,
+            include_css: args.css,
+            toc: args.toc,
         },
         _ => OutputFormat::Text {
             preserve_formatting: args.preserve_formatting,
@@ -497,12 +539,15 @@ fn convert_single_file(file: &PathBuf, args: &Args) -> Result<(), Box<dyn std::e
 // Helper function to clone Args
 fn clone_args(args: &Args) -> Args {
     Args {
+        command: None,
         input: args.input.clone(),
         output: args.output.clone(),
         format: args.format.clone(),
         metadata: args.metadata,
         preserve_formatting: args.preserve_formatting,
         pretty: args.pretty,
+        css: args.css,
+        toc: args.toc,
         line_numbers: args.line_numbers,
         text_only: args.text_only,
         verbose: args.verbose,
@@ -510,6 +555,10 @@ fn clone_args(args: &Args) -> Args {
         output_dir: args.output_dir.clone(),
         overwrite: args.overwrite,
         max_files: args.max_files,
+        heading_heuristic: args.heading_heuristic,
+        revisions: args.revisions.clone(),
+        extract_images: args.extract_images.clone(),
+        shapes: args.shapes.clone(),
     }
 }
 
@@ -531,12 +580,15 @@ mod tests {
         
         // Test invalid batch pattern
         let args = Args {
-            input: PathBuf::new(),
+            command: None,
+            input: None,
             output: None,
             format: "text".to_string(),
             metadata: false,
             preserve_formatting: false,
             pretty: false,
+            css: false,
+            toc: false,
             line_numbers: false,
             text_only: false,
             verbose: false,
@@ -544,6 +596,10 @@ mod tests {
             output_dir: Some(temp_dir.path().to_path_buf()),
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         // Should fail with invalid glob pattern
@@ -1,5 +1,5 @@
-use clap::Parser;
-use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
 use crate::error::{DocParserError, Result};
 use glob;
 
@@ -10,10 +10,14 @@ use glob;
     version = "0.1.0",
     about = "A cross-platform DOC/DOCX parser that extracts text content"
 )]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Input DOC/DOCX file
-    #[arg(help = "Input DOC/DOCX file path")]
-    pub input: PathBuf,
+    #[arg(required = true, help = "Input DOC/DOCX file path")]
+    pub input: Option<PathBuf>,
 
     /// Output file (default: stdout)
     #[arg(short, long, help = "Output file path (default: stdout)")]
@@ -21,7 +25,7 @@ pub struct Args {
 
     /// Output format
     #[arg(short = 'f', long, default_value = "text", 
-          help = "Output format: text, markdown, json")]
+          help = "Output format: text, markdown, json, html")]
     pub format: String,
 
     /// Include metadata in output
@@ -36,6 +40,14 @@ pub struct Args {
     #[arg(long, help = "Pretty print JSON output")]
     pub pretty: bool,
 
+    /// Embed a stylesheet (for HTML output)
+    #[arg(long, help = "Embed CSS styles in HTML output")]
+    pub css: bool,
+
+    /// Insert a table of contents (for Markdown and HTML output)
+    #[arg(long, help = "Insert a linked table of contents in Markdown or HTML output")]
+    pub toc: bool,
+
     /// Add line numbers (for text output)
     #[arg(long, help = "Add line numbers to text output")]
     pub line_numbers: bool,
@@ -63,6 +75,25 @@ pub struct Args {
     /// Maximum number of files to process
     #[arg(long, help = "Maximum number of files to process (default: unlimited)")]
     pub max_files: Option<usize>,
+
+    /// Guess headings from paragraph length when the document has no heading styles
+    #[arg(long, help = "Fall back to length-based heading detection for documents without heading styles")]
+    pub heading_heuristic: bool,
+
+    /// How tracked changes are applied
+    #[arg(long, default_value = "accept",
+          help = "Tracked changes: accept (final text), reject (original text), show (mark insertions and deletions)")]
+    pub revisions: String,
+
+    /// Directory for extracted images
+    #[arg(long, value_name = "DIR",
+          help = "Write embedded images into DIR and reference them by relative path in Markdown, JSON and HTML output")]
+    pub extract_images: Option<PathBuf>,
+
+    /// Text in text boxes, drawing canvases and SmartArt
+    #[arg(long, default_value = "include",
+          help = "Text boxes, drawing canvases and SmartArt: include (as sections after their paragraph) or skip")]
+    pub shapes: String,
 }
 
 impl Args {
@@ -83,6 +114,7 @@ impl Args {
                 Ok(crate::output::OutputFormat::Markdown {
                     preserve_structure: !self.text_only,
                     include_metadata: self.metadata,
+                    toc: self.toc,
                 })
             }
             "json" => {
@@ -91,8 +123,29 @@ impl Args {
                     include_formatting: self.preserve_formatting,
                 })
             }
+            "html" => {
+                Ok(crate::output::OutputFormat::Html {
+                    include_metadata: self.metadata,
+                    include_css: self.css,
+                    toc: self.toc,
+                })
+            }
+            _ => Err(DocParserError::InvalidConfiguration {
+                details: format!("Unsupported output format: '{}'. Supported formats: text, markdown, json, html", self.format)
+            }),
+        }
+    }
+
+    pub fn get_revision_mode(&self) -> Result<crate::parser::RevisionMode> {
+        parse_revision_mode(&self.revisions)
+    }
+
+    pub fn get_shape_mode(&self) -> Result<crate::parser::ShapeMode> {
+        match self.shapes.to_lowercase().as_str() {
+            "include" => Ok(crate::parser::ShapeMode::Include),
+            "skip" => Ok(crate::parser::ShapeMode::Skip),
             _ => Err(DocParserError::InvalidConfiguration {
-                details: format!("Unsupported output format: '{}'. Supported formats: text, markdown, json", self.format)
+                details: format!("Unsupported shapes mode: '{}'. Supported modes: include, skip", self.shapes)
             }),
         }
     }
@@ -107,16 +160,25 @@ impl Args {
         self.validate_single_file_mode()
     }
 
+    /// 转换的输入文件（仅在使用子命令时缺省）
+    pub fn input_path(&self) -> Result<&Path> {
+        self.input.as_deref().ok_or_else(|| DocParserError::InvalidConfiguration {
+            details: "Missing input file".to_string(),
+        })
+    }
+
     fn validate_single_file_mode(&self) -> Result<()> {
+        let input = self.input_path()?;
+
         // 检查输入文件是否存在
-        if !self.input.exists() {
+        if !input.exists() {
             return Err(DocParserError::FileNotFound {
-                file: self.input.display().to_string(),
+                file: input.display().to_string(),
             });
         }
 
         // 检查文件扩展名
-        let extension = self.input
+        let extension = input
             .extension()
             .and_then(|ext| ext.to_str())
             .unwrap_or("")
@@ -126,7 +188,7 @@ impl Args {
             "docx" | "doc" => {}
             _ => return Err(DocParserError::UnsupportedFormat {
                 format: extension,
-                file: self.input.display().to_string(),
+                file: input.display().to_string(),
             }),
         }
 
@@ -165,12 +227,15 @@ impl Args {
     fn validate_common_options(&self) -> Result<()> {
         // 验证输出格式
         match self.format.to_lowercase().as_str() {
-            "text" | "markdown" | "md" | "json" => {}
+            "text" | "markdown" | "md" | "json" | "html" => {}
             _ => return Err(DocParserError::InvalidConfiguration {
-                details: format!("Unsupported output format: '{}'. Supported formats: text, markdown, json", self.format)
+                details: format!("Unsupported output format: '{}'. Supported formats: text, markdown, json, html", self.format)
             }),
         }
 
+        self.get_revision_mode()?;
+        self.get_shape_mode()?;
+
         // 检查输出文件路径是否有效（单文件模式）
         if let Some(output_path) = &self.output {
             if let Some(parent) = output_path.parent() {
@@ -195,6 +260,18 @@ impl Args {
             });
         }
 
+        if self.css && self.format.to_lowercase() != "html" {
+            return Err(DocParserError::InvalidConfiguration {
+                details: "CSS option is only supported with HTML output format".to_string(),
+            });
+        }
+
+        if self.toc && !matches!(self.format.to_lowercase().as_str(), "markdown" | "md" | "html") {
+            return Err(DocParserError::InvalidConfiguration {
+                details: "Table of contents is only supported with Markdown and HTML output formats".to_string(),
+            });
+        }
+
         Ok(())
     }
 
@@ -286,6 +363,199 @@ impl Args {
     }
 }
 
+fn parse_revision_mode(mode: &str) -> Result<crate::parser::RevisionMode> {
+    match mode.to_lowercase().as_str() {
+        "accept" => Ok(crate::parser::RevisionMode::Accept),
+        "reject" => Ok(crate::parser::RevisionMode::Reject),
+        "show" => Ok(crate::parser::RevisionMode::Show),
+        _ => Err(DocParserError::InvalidConfiguration {
+            details: format!("Unsupported revisions mode: '{}'. Supported modes: accept, reject, show", mode)
+        }),
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Export every table as a standalone file
+    Tables(TablesArgs),
+    /// Print the heading outline
+    Outline(OutlineArgs),
+    /// Split the document into JSONL chunks for retrieval
+    Chunk(ChunkArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct TablesArgs {
+    /// Input DOCX file
+    #[arg(help = "Input DOCX file path")]
+    pub input: PathBuf,
+
+    /// Table file format
+    #[arg(short = 'f', long, default_value = "csv",
+          help = "Table format: csv, tsv, markdown, json, html, text")]
+    pub format: String,
+
+    /// Directory for the table files
+    #[arg(long, help = "Directory for the table files (default: current directory)")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Overwrite existing table files
+    #[arg(long, help = "Overwrite existing table files")]
+    pub overwrite: bool,
+
+    /// How tracked changes are applied
+    #[arg(long, default_value = "accept",
+          help = "Tracked changes: accept (final text), reject (original text), show (keep both)")]
+    pub revisions: String,
+
+    /// Enable verbose output
+    #[arg(short, long, help = "Enable verbose output")]
+    pub verbose: bool,
+}
+
+impl TablesArgs {
+    pub fn get_table_format(&self) -> Result<crate::parser::TableOutputFormat> {
+        crate::parser::TableOutputFormat::from_name(&self.format).ok_or_else(|| DocParserError::InvalidConfiguration {
+            details: format!("Unsupported table format: '{}'. Supported formats: csv, tsv, markdown, json, html, text", self.format)
+        })
+    }
+
+    pub fn get_revision_mode(&self) -> Result<crate::parser::RevisionMode> {
+        parse_revision_mode(&self.revisions)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !self.input.exists() {
+            return Err(DocParserError::FileNotFound {
+                file: self.input.display().to_string(),
+            });
+        }
+        self.get_table_format()?;
+        self.get_revision_mode()?;
+        Ok(())
+    }
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct OutlineArgs {
+    /// Input DOC/DOCX file
+    #[arg(help = "Input DOC/DOCX file path")]
+    pub input: PathBuf,
+
+    /// Output format
+    #[arg(short = 'f', long, default_value = "text",
+          help = "Outline format: text, json")]
+    pub format: String,
+
+    /// Output file path
+    #[arg(short, long, help = "Output file path (default: stdout)")]
+    pub output: Option<PathBuf>,
+
+    /// Pretty print JSON output
+    #[arg(long, help = "Pretty print JSON output")]
+    pub pretty: bool,
+
+    /// Guess headings from paragraph length when the document has no heading styles
+    #[arg(long, help = "Fall back to length-based heading detection for documents without heading styles")]
+    pub heading_heuristic: bool,
+}
+
+impl OutlineArgs {
+    pub fn validate(&self) -> Result<()> {
+        if !self.input.exists() {
+            return Err(DocParserError::FileNotFound {
+                file: self.input.display().to_string(),
+            });
+        }
+        match self.format.to_lowercase().as_str() {
+            "text" | "json" => {}
+            _ => return Err(DocParserError::InvalidConfiguration {
+                details: format!("Unsupported outline format: '{}'. Supported formats: text, json", self.format)
+            }),
+        }
+        if self.pretty && self.format.to_lowercase() != "json" {
+            return Err(DocParserError::InvalidConfiguration {
+                details: "Pretty print option is only supported with JSON output format".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ChunkArgs {
+    /// Input DOC/DOCX file
+    #[arg(help = "Input DOC/DOCX file path")]
+    pub input: PathBuf,
+
+    /// Output file path
+    #[arg(short, long, help = "Output JSONL file path (default: stdout)")]
+    pub output: Option<PathBuf>,
+
+    /// Character budget per chunk
+    #[arg(long, value_name = "N", conflicts_with = "max_tokens",
+          help = "Maximum characters per chunk (default: 2000)")]
+    pub max_chars: Option<usize>,
+
+    /// Estimated token budget per chunk
+    #[arg(long, value_name = "N",
+          help = "Maximum estimated tokens per chunk, instead of characters")]
+    pub max_tokens: Option<usize>,
+
+    /// Overlap between consecutive chunks
+    #[arg(long, value_name = "N",
+          help = "Overlap between consecutive chunks, in the budget unit (default: 10% of the budget)")]
+    pub overlap: Option<usize>,
+
+    /// Guess headings from paragraph length when the document has no heading styles
+    #[arg(long, help = "Fall back to length-based heading detection for documents without heading styles")]
+    pub heading_heuristic: bool,
+
+    /// How tracked changes are applied
+    #[arg(long, default_value = "accept",
+          help = "Tracked changes: accept (final text), reject (original text), show (keep both)")]
+    pub revisions: String,
+}
+
+impl ChunkArgs {
+    pub fn get_chunk_config(&self) -> crate::output::ChunkConfig {
+        let config = match (self.max_tokens, self.max_chars) {
+            (Some(max_tokens), _) => crate::output::ChunkConfig::tokens(max_tokens),
+            (None, Some(max_chars)) => crate::output::ChunkConfig::chars(max_chars),
+            (None, None) => crate::output::ChunkConfig::default(),
+        };
+        match self.overlap {
+            Some(overlap) => config.with_overlap(overlap),
+            None => config,
+        }
+    }
+
+    pub fn get_revision_mode(&self) -> Result<crate::parser::RevisionMode> {
+        parse_revision_mode(&self.revisions)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !self.input.exists() {
+            return Err(DocParserError::FileNotFound {
+                file: self.input.display().to_string(),
+            });
+        }
+        let config = self.get_chunk_config();
+        if config.max_size == 0 {
+            return Err(DocParserError::InvalidConfiguration {
+                details: "Chunk size must be greater than 0".to_string(),
+            });
+        }
+        if config.overlap >= config.max_size {
+            return Err(DocParserError::InvalidConfiguration {
+                details: format!("Overlap ({}) must be smaller than the chunk size ({})", config.overlap, config.max_size),
+            });
+        }
+        self.get_revision_mode()?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProcessingMode {
     TextOnly,           // 只提取纯文本，最快
@@ -306,12 +576,15 @@ mod tests {
         File::create(&file_path).unwrap();
 
         let args = Args {
-            input: file_path,
+            command: None,
+            input: Some(file_path),
             output: None,
             format: "text".to_string(),
             metadata: false,
             preserve_formatting: false,
             pretty: false,
+            css: false,
+            toc: false,
             line_numbers: false,
             text_only: false,
             verbose: false,
@@ -319,6 +592,10 @@ mod tests {
             output_dir: None,
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         let result = args.validate();
@@ -334,12 +611,15 @@ mod tests {
         File::create(&file_path).unwrap();
 
         let args = Args {
-            input: file_path,
+            command: None,
+            input: Some(file_path),
             output: None,
             format: "xml".to_string(),
             metadata: false,
             preserve_formatting: false,
             pretty: false,
+            css: false,
+            toc: false,
             line_numbers: false,
             text_only: false,
             verbose: false,
@@ -347,6 +627,10 @@ mod tests {
             output_dir: None,
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         let result = args.validate();
@@ -363,12 +647,15 @@ mod tests {
 
         // Line numbers with non-text format
         let args = Args {
-            input: file_path.clone(),
+            command: None,
+            input: Some(file_path.clone()),
             output: None,
             format: "json".to_string(),
             metadata: false,
             preserve_formatting: false,
             pretty: false,
+            css: false,
+            toc: false,
             line_numbers: true,
             text_only: false,
             verbose: false,
@@ -376,6 +663,10 @@ mod tests {
             output_dir: None,
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         let result = args.validate();
@@ -385,12 +676,15 @@ mod tests {
 
         // Pretty print with non-JSON format
         let args = Args {
-            input: file_path,
+            command: None,
+            input: Some(file_path),
             output: None,
             format: "text".to_string(),
             metadata: false,
             preserve_formatting: false,
             pretty: true,
+            css: false,
+            toc: false,
             line_numbers: false,
             text_only: false,
             verbose: false,
@@ -398,6 +692,10 @@ mod tests {
             output_dir: None,
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         let result = args.validate();
@@ -409,12 +707,15 @@ mod tests {
     #[test]
     fn test_get_processing_mode() {
         let args = Args {
-            input: PathBuf::from("test.docx"),
+            command: None,
+            input: Some(PathBuf::from("test.docx")),
             output: None,
             format: "text".to_string(),
             metadata: false,
             preserve_formatting: false,
             pretty: false,
+            css: false,
+            toc: false,
             line_numbers: false,
             text_only: true,
             verbose: false,
@@ -422,17 +723,24 @@ mod tests {
             output_dir: None,
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         assert_eq!(args.get_processing_mode(), ProcessingMode::TextOnly);
 
         let args = Args {
-            input: PathBuf::from("test.docx"),
+            command: None,
+            input: Some(PathBuf::from("test.docx")),
             output: None,
             format: "text".to_string(),
             metadata: true,
             preserve_formatting: false,
             pretty: false,
+            css: false,
+            toc: false,
             line_numbers: false,
             text_only: false,
             verbose: false,
@@ -440,6 +748,10 @@ mod tests {
             output_dir: None,
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         assert_eq!(args.get_processing_mode(), ProcessingMode::FullWithMetadata);
@@ -448,12 +760,15 @@ mod tests {
     #[test]
     fn test_get_output_format() {
         let args = Args {
-            input: PathBuf::from("test.docx"),
+            command: None,
+            input: Some(PathBuf::from("test.docx")),
             output: None,
             format: "text".to_string(),
             metadata: true,
             preserve_formatting: false,
             pretty: false,
+            css: false,
+            toc: false,
             line_numbers: true,
             text_only: false,
             verbose: false,
@@ -461,6 +776,10 @@ mod tests {
             output_dir: None,
             overwrite: false,
             max_files: None,
+            heading_heuristic: false,
+            revisions: "accept".to_string(),
+            extract_images: None,
+            shapes: "include".to_string(),
         };
 
         let format = args.get_output_format().unwrap();
@@ -473,4 +792,104 @@ mod tests {
             _ => panic!("Expected Text format"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_html_format_and_css() {
+        let args = Args::parse_from(["doc-parser", "test.docx", "-f", "html", "--css", "--metadata"]);
+        match args.get_output_format().unwrap() {
+            crate::output::OutputFormat::Html { include_metadata, include_css, .. } => {
+                assert!(include_metadata);
+                assert!(include_css);
+            }
+            _ => panic!("Expected Html format"),
+        }
+
+        let args = Args::parse_from(["doc-parser", "test.docx", "-f", "markdown", "--css"]);
+        assert!(matches!(args.validate_common_options(), Err(DocParserError::InvalidConfiguration { .. })));
+    }
+
+    #[test]
+    fn test_toc_option() {
+        let args = Args::parse_from(["doc-parser", "test.docx", "-f", "md", "--toc"]);
+        assert!(args.validate_common_options().is_ok());
+        assert!(matches!(args.get_output_format().unwrap(), crate::output::OutputFormat::Markdown { toc: true, .. }));
+
+        let args = Args::parse_from(["doc-parser", "test.docx", "-f", "json", "--toc"]);
+        assert!(matches!(args.validate_common_options(), Err(DocParserError::InvalidConfiguration { .. })));
+    }
+
+    #[test]
+    fn test_get_revision_mode() {
+        let mut args = Args::parse_from(["doc-parser", "test.docx"]);
+        assert_eq!(args.get_revision_mode().unwrap(), crate::parser::RevisionMode::Accept);
+
+        args.revisions = "Show".to_string();
+        assert_eq!(args.get_revision_mode().unwrap(), crate::parser::RevisionMode::Show);
+
+        args.revisions = "merge".to_string();
+        assert!(matches!(args.get_revision_mode(), Err(DocParserError::InvalidConfiguration { .. })));
+    }
+
+    #[test]
+    fn test_get_shape_mode() {
+        let mut args = Args::parse_from(["doc-parser", "test.docx", "--shapes", "Skip"]);
+        assert_eq!(args.get_shape_mode().unwrap(), crate::parser::ShapeMode::Skip);
+
+        args.shapes = "include".to_string();
+        assert_eq!(args.get_shape_mode().unwrap(), crate::parser::ShapeMode::Include);
+
+        args.shapes = "flatten".to_string();
+        assert!(matches!(args.get_shape_mode(), Err(DocParserError::InvalidConfiguration { .. })));
+    }
+
+    #[test]
+    fn test_subcommands_are_listed_and_optional() {
+        let args = Args::try_parse_from(["doc-parser", "report.docx", "-f", "html"]).unwrap();
+        assert!(args.command.is_none());
+        assert_eq!(args.input_path().unwrap(), Path::new("report.docx"));
+
+        let help = <Args as clap::CommandFactory>::command().render_help().to_string();
+        for command in ["tables", "outline", "chunk"] {
+            assert!(help.contains(command), "{} missing from help", command);
+        }
+        assert!(Args::try_parse_from(["doc-parser", "outline", "spec.docx", "--css"]).is_err());
+    }
+
+    #[test]
+    fn test_tables_command() {
+        let line = Args::try_parse_from(["doc-parser", "tables", "report.docx", "--format", "TSV", "--output-dir", "out"]).unwrap();
+        let Some(Command::Tables(args)) = line.command else { panic!("Expected tables command") };
+        assert_eq!(args.input, PathBuf::from("report.docx"));
+        assert_eq!(args.output_dir, Some(PathBuf::from("out")));
+        assert!(matches!(args.get_table_format().unwrap(), crate::parser::TableOutputFormat::TSV));
+
+        let line = Args::try_parse_from(["doc-parser", "tables", "report.docx", "-f", "xlsx"]).unwrap();
+        let Some(Command::Tables(args)) = line.command else { panic!("Expected tables command") };
+        assert!(matches!(args.get_table_format(), Err(DocParserError::InvalidConfiguration { .. })));
+    }
+
+    #[test]
+    fn test_chunk_command() {
+        let line = Args::try_parse_from(["doc-parser", "chunk", "spec.docx", "--max-tokens", "256"]).unwrap();
+        let Some(Command::Chunk(args)) = line.command else { panic!("Expected chunk command") };
+        let config = args.get_chunk_config();
+        assert_eq!(config.unit, crate::output::ChunkUnit::Tokens);
+        assert_eq!((config.max_size, config.overlap), (256, 25));
+
+        let line = Args::try_parse_from(["doc-parser", "chunk", "spec.docx", "--overlap", "0"]).unwrap();
+        let Some(Command::Chunk(args)) = line.command else { panic!("Expected chunk command") };
+        let config = args.get_chunk_config();
+        assert_eq!((config.unit, config.max_size, config.overlap), (crate::output::ChunkUnit::Chars, 2000, 0));
+
+        assert!(Args::try_parse_from(["doc-parser", "chunk", "spec.docx", "--max-chars", "10", "--max-tokens", "5"]).is_err());
+    }
+
+    #[test]
+    fn test_outline_command() {
+        let line = Args::try_parse_from(["doc-parser", "outline", "spec.docx", "-f", "json", "--pretty"]).unwrap();
+        let Some(Command::Outline(args)) = line.command else { panic!("Expected outline command") };
+        assert_eq!(args.input, PathBuf::from("spec.docx"));
+        assert_eq!(args.format, "json");
+        assert!(args.pretty);
+    }
+}
@@ -86,6 +86,9 @@ impl DocParserError {
             DocParserError::FileNotFound { file } => {
                 format!("❌ File not found: '{file}'\n💡 Make sure the file path is correct and the file exists.")
             }
+            DocParserError::UnsupportedFormat { format, .. } if format == "doc" => {
+                "❌ Word 97-2003 (.doc) support is not enabled in this build\n💡 Rebuild with `--features legacy-doc`, or save the file as .docx.".to_string()
+            }
             DocParserError::UnsupportedFormat { format, .. } => {
                 format!("❌ Unsupported file format: '.{format}'\n💡 Supported formats: .doc, .docx\n💡 Try converting your file to Word format first.")
             }
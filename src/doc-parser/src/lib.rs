@@ -66,12 +66,15 @@
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // Create Args structure for batch processing
 //! let mut args = Args {
-//!     input: PathBuf::new(), // Not used in batch mode
+//!     command: None,
+//!     input: None, // Not used in batch mode
 //!     output: None,
 //!     format: "json".to_string(),
 //!     metadata: true,
 //!     preserve_formatting: false,
 //!     pretty: true,
+//!     css: false,
+//!     toc: false,
 //!     line_numbers: false,
 //!     text_only: false,
 //!     verbose: true,
@@ -79,6 +82,10 @@
 //!     output_dir: Some(PathBuf::from("./output")),
 //!     overwrite: false,
 //!     max_files: Some(10),
+//!     heading_heuristic: false,
+//!     revisions: "accept".to_string(),
+//!     extract_images: None,
+//!     shapes: "include".to_string(),
 //! };
 //!
 //! if args.is_batch_mode() {
@@ -107,7 +114,7 @@
 //! ## Supported File Formats
 //!
 //! - **DOCX**: Full support (Office 2007+)
-//! - **DOC**: Basic support (requires `legacy-doc` feature)
+//! - **DOC**: Word 97-2003 binary files (`legacy-doc` feature, enabled by default)
 //!
 //! ## Error Handling
 //!
@@ -143,7 +150,7 @@ pub mod error;
 
 // Re-export commonly used types for convenience
 pub use error::{DocParserError, Result};
-pub use parser::{DocParser, DocData, DocSection, DocMetadata, SectionType, FormatInfo};
+pub use parser::{DocParser, DocData, DocSection, DocMetadata, ParseOptions, SectionType, FormatInfo, TextSpan, Equation, VerticalAlign, ListInfo, Link, Note, NoteKind, NoteReference, Comment, Revision, RevisionKind, RevisionMode, ImageInfo, ShapeInfo, ShapeKind, ShapeMode, Outline, OutlineEntry};
 pub use output::{OutputFormat, OutputProcessor};
 pub use cli::{Args, ProcessingMode};
 
@@ -1,10 +1,16 @@
-use doc_parser::{Args, DocParser, OutputProcessor, Result};
+use doc_parser::cli::{ChunkArgs, Command, OutlineArgs, TablesArgs};
+use doc_parser::output::{chunk, slugify, Chunker, TableWriter};
+use doc_parser::{Args, DocData, DocParser, OutputProcessor, Result};
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::{self, BufWriter, Write};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 fn main() -> Result<()> {
     let args = Args::parse_args();
+    if let Some(command) = &args.command {
+        return run_command(command);
+    }
 
     // 验证参数
     if let Err(e) = args.validate() {
@@ -21,6 +27,116 @@ fn main() -> Result<()> {
     process_single_file(&args)
 }
 
+fn run_command(command: &Command) -> Result<()> {
+    let result = match command {
+        Command::Tables(args) => args.validate().and_then(|()| export_tables(args)),
+        Command::Outline(args) => args.validate().and_then(|()| print_outline(args)),
+        Command::Chunk(args) => args.validate().and_then(|()| write_chunks(args)),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e.user_friendly_message());
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 每个表格写成一个文件，文件名取自表格题注，没有题注时为 table-N
+fn export_tables(args: &TablesArgs) -> Result<()> {
+    let format = args.get_table_format()?;
+    let parser = DocParser::new().with_revisions(args.get_revision_mode()?);
+    let tables = parser.extract_tables(&args.input)?;
+    if tables.is_empty() {
+        eprintln!("⚠️  No tables found in {}", args.input.display());
+        return Ok(());
+    }
+
+    let output_dir = match &args.output_dir {
+        Some(dir) => dir.clone(),
+        None => std::env::current_dir()?,
+    };
+    std::fs::create_dir_all(&output_dir)?;
+    let writer = TableWriter::new(format.clone());
+    let mut used_names = HashSet::new();
+    let mut written = 0;
+
+    for (index, table) in tables.iter().enumerate() {
+        let base = table
+            .title
+            .as_deref()
+            .map(slugify)
+            .filter(|slug| !slug.is_empty())
+            .unwrap_or_else(|| format!("table-{}", index + 1));
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !used_names.insert(name.clone()) {
+            name = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+
+        let path = output_dir.join(format!("{}.{}", name, format.extension()));
+        if path.exists() && !args.overwrite {
+            eprintln!("⚠️  Output file exists, skipping: {}", path.display());
+            continue;
+        }
+        let mut file = BufWriter::new(File::create(&path)?);
+        writer.write_table(table, &mut file)?;
+        file.flush()?;
+        written += 1;
+        if args.verbose {
+            eprintln!("📄 Table {}: {}", index + 1, path.display());
+        }
+    }
+
+    if args.verbose {
+        eprintln!("✅ Wrote {} of {} tables to {}", written, tables.len(), output_dir.display());
+    }
+    Ok(())
+}
+
+/// 输出标题大纲：文本为缩进的编号标题，JSON 为嵌套的标题树
+fn print_outline(args: &OutlineArgs) -> Result<()> {
+    let parser = DocParser::new().with_heading_heuristic(args.heading_heuristic);
+    let outline = parser.outline(&args.input)?;
+    let rendered = match args.format.to_lowercase().as_str() {
+        "json" if args.pretty => serde_json::to_string_pretty(&outline)? + "\n",
+        "json" => serde_json::to_string(&outline)? + "\n",
+        _ => outline.to_string(),
+    };
+
+    match &args.output {
+        Some(path) => std::fs::write(path, rendered)?,
+        None => io::stdout().write_all(rendered.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// 按标题和长度预算切块，每块一行 JSON
+fn write_chunks(args: &ChunkArgs) -> Result<()> {
+    let parser = DocParser::new()
+        .with_heading_heuristic(args.heading_heuristic)
+        .with_revisions(args.get_revision_mode()?);
+    let data = parser.parse(&args.input)?;
+    let mut chunks = Chunker::new(args.get_chunk_config()).chunk(&data);
+    let source = args.input.display().to_string();
+    for chunk in &mut chunks {
+        chunk.source = Some(source.clone());
+    }
+
+    match &args.output {
+        Some(path) => {
+            let mut file = BufWriter::new(File::create(path)?);
+            chunk::write_jsonl(&chunks, &mut file)?;
+            file.flush()?;
+        }
+        None => {
+            let stdout = io::stdout();
+            let mut handle = stdout.lock();
+            chunk::write_jsonl(&chunks, &mut handle)?;
+        }
+    }
+    Ok(())
+}
+
 fn process_batch(args: &Args) -> Result<()> {
     let files = args.get_batch_files()?;
     
@@ -90,6 +206,7 @@ fn generate_output_path(input_path: &Path, format: &str, output_dir: &Option<std
         "text" => "txt",
         "markdown" | "md" => "md",
         "json" => "json",
+        "html" => "html",
         _ => "txt",
     };
 
@@ -106,7 +223,7 @@ fn generate_output_path(input_path: &Path, format: &str, output_dir: &Option<std
 }
 
 fn process_single_file(args: &Args) -> Result<()> {
-    process_single_file_internal(&args.input, args.output.as_deref(), args)
+    process_single_file_internal(args.input_path()?, args.output.as_deref(), args)
 }
 
 fn process_single_file_internal(input_path: &Path, output_path: Option<&Path>, args: &Args) -> Result<()> {
@@ -116,10 +233,14 @@ fn process_single_file_internal(input_path: &Path, output_path: Option<&Path>, a
     }
 
     // 创建解析器
-    let parser = DocParser::new();
+    let parser = DocParser::new()
+        .with_heading_heuristic(args.heading_heuristic)
+        .with_revisions(args.get_revision_mode()?)
+        .with_shapes(args.get_shape_mode()?)
+        .with_image_dir(args.extract_images.as_ref());
 
     // 根据处理模式解析文档
-    let doc_data = match args.get_processing_mode() {
+    let mut doc_data = match args.get_processing_mode() {
         doc_parser::cli::ProcessingMode::TextOnly => {
             // 只提取文本，最快模式
             if args.verbose {
@@ -163,6 +284,11 @@ fn process_single_file_internal(input_path: &Path, output_path: Option<&Path>, a
         }
     };
 
+    // 导出的图片按输出文件的位置引用
+    if args.extract_images.is_some() {
+        relativize_image_paths(&mut doc_data, output_path);
+    }
+
     if args.verbose {
         eprintln!("Document parsed successfully:");
         eprintln!("  - Paragraphs: {}", doc_data.metadata.paragraph_count);
@@ -210,6 +336,41 @@ fn process_single_file_internal(input_path: &Path, output_path: Option<&Path>, a
     Ok(())
 }
 
+/// 导出图片的路径改为相对输出文件所在目录（输出到标准输出时相对当前目录）
+fn relativize_image_paths(doc_data: &mut DocData, output_path: Option<&Path>) {
+    let base = output_path
+        .and_then(Path::parent)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let Ok(base) = base.canonicalize() else {
+        return;
+    };
+    for image in doc_data.sections.iter_mut().filter_map(|section| section.image.as_mut()) {
+        let relative = image
+            .path
+            .as_deref()
+            .and_then(|path| Path::new(path).canonicalize().ok())
+            .and_then(|path| relative_path(&path, &base));
+        if let Some(relative) = relative {
+            // Markdown 和 JSON 中统一使用正斜杠
+            image.path = Some(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+}
+
+/// `path` 相对于目录 `base` 的路径；两者须为绝对路径，不在同一根下时返回 None
+fn relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
+    let path: Vec<Component> = path.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    if path.first() != base.first() {
+        return None;
+    }
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut relative: PathBuf = base[common..].iter().map(|_| Component::ParentDir).collect();
+    relative.extend(&path[common..]);
+    Some(relative)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,4 +387,11 @@ mod tests {
         let output_path = generate_output_path(input_path, "text", &None).unwrap();
         assert!(output_path.to_string_lossy().ends_with("test.txt"));
     }
+
+    #[test]
+    fn test_relative_path() {
+        let relative = |path: &str, base: &str| relative_path(Path::new(path), Path::new(base)).unwrap();
+        assert_eq!(relative("/out/images/a.png", "/out"), PathBuf::from("images/a.png"));
+        assert_eq!(relative("/data/images/a.png", "/data/out/md"), PathBuf::from("../../images/a.png"));
+    }
 }
\ No newline at end of file
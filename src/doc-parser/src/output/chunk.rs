@@ -0,0 +1,571 @@
+use crate::error::Result;
+use crate::parser::{DocData, DocSection, SectionType};
+use serde::Serialize;
+use std::io::Write;
+
+/// 分块大小的计量单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkUnit {
+    Chars,
+    /// 估算的 token 数，见 [`estimate_tokens`]
+    Tokens,
+}
+
+impl ChunkUnit {
+    pub fn measure(self, text: &str) -> usize {
+        match self {
+            ChunkUnit::Chars => text.chars().count(),
+            ChunkUnit::Tokens => estimate_tokens(text),
+        }
+    }
+}
+
+/// 粗略估算 token 数：拉丁文字约 4 个字符一个 token，中日韩文字每字一个 token
+pub fn estimate_tokens(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| {
+            let wide = word.chars().filter(|&c| is_wide(c)).count();
+            let narrow = word.chars().count() - wide;
+            wide + narrow.div_ceil(4)
+        })
+        .sum()
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c, '\u{2E80}'..='\u{9FFF}' | '\u{AC00}'..='\u{D7AF}' | '\u{F900}'..='\u{FAFF}' | '\u{FF00}'..='\u{FFEF}')
+}
+
+#[derive(Debug, Clone)]
+pub struct ChunkConfig {
+    /// 每块的最大长度
+    pub max_size: usize,
+    /// 相邻块之间重复的长度（按整段/整行计，不超过此值）
+    pub overlap: usize,
+    pub unit: ChunkUnit,
+}
+
+impl ChunkConfig {
+    /// 按字符计的预算，重叠默认为预算的 10%
+    pub fn chars(max_size: usize) -> Self {
+        Self {
+            max_size,
+            overlap: max_size / 10,
+            unit: ChunkUnit::Chars,
+        }
+    }
+
+    /// 按估算 token 计的预算，重叠默认为预算的 10%
+    pub fn tokens(max_size: usize) -> Self {
+        Self {
+            max_size,
+            overlap: max_size / 10,
+            unit: ChunkUnit::Tokens,
+        }
+    }
+
+    pub fn with_overlap(mut self, overlap: usize) -> Self {
+        self.overlap = overlap;
+        self
+    }
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self::chars(2000)
+    }
+}
+
+/// 供检索使用的文本块
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Chunk {
+    /// 块序号，从 0 开始
+    pub index: usize,
+    /// 来源文件
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// 所在标题路径，从最外层标题开始
+    pub heading_path: Vec<String>,
+    /// 块内容来自的段（`DocData::sections` 下标）
+    pub sections: Vec<usize>,
+    /// 在 `DocData::content` 中的字符范围 [start_offset, end_offset)
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub text: String,
+    pub char_count: usize,
+    pub token_estimate: usize,
+}
+
+/// 按标题层级和长度预算切分文档：标题开始新块，段落过长时按句子、词切开，
+/// 表格只在行之间切开，续块重复表头
+pub struct Chunker {
+    config: ChunkConfig,
+}
+
+/// 块的组成单位：标题、段落（或其一部分）、表格行
+#[derive(Debug, Clone, PartialEq)]
+struct Piece {
+    text: String,
+    section_index: usize,
+    start: usize,
+    end: usize,
+    kind: PieceKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PieceKind {
+    Heading,
+    Text,
+    TableHeader,
+    /// 表格行，附带所在表格的表头
+    TableRow(Option<Box<Piece>>),
+}
+
+impl Chunker {
+    pub fn new(config: ChunkConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn chunk(&self, data: &DocData) -> Vec<Chunk> {
+        let mut builder = ChunkBuilder {
+            config: &self.config,
+            chunks: Vec::new(),
+            current: Vec::new(),
+            headings: Vec::new(),
+        };
+        let mut last_offset = 0;
+
+        for ((section_index, section), offset) in data.sections.iter().enumerate().zip(data.section_offsets()) {
+            let offset = offset.unwrap_or(last_offset);
+            last_offset = offset;
+            match &section.section_type {
+                SectionType::Heading(level) => {
+                    let title = section.content.trim();
+                    if title.is_empty() {
+                        continue;
+                    }
+                    // 标题之间没有正文时合并到同一块
+                    if builder.current.iter().any(|piece| piece.kind != PieceKind::Heading) {
+                        builder.flush();
+                    }
+                    while builder.headings.last().is_some_and(|(outer, _)| outer >= level) {
+                        builder.headings.pop();
+                    }
+                    builder.headings.push((*level, title.to_string()));
+                    let start = offset + section.leading_whitespace();
+                    builder.push(Piece {
+                        text: title.to_string(),
+                        section_index,
+                        start,
+                        end: start + title.chars().count(),
+                        kind: PieceKind::Heading,
+                    });
+                }
+                SectionType::Header | SectionType::Footer => {}
+                SectionType::Table => {
+                    for piece in table_pieces(section, section_index, offset) {
+                        builder.push(piece);
+                    }
+                }
+                SectionType::Image => {
+                    if let Some(description) = section.image.as_ref().and_then(|image| image.description()) {
+                        builder.push(Piece {
+                            text: description.to_string(),
+                            section_index,
+                            start: offset,
+                            end: offset,
+                            kind: PieceKind::Text,
+                        });
+                    }
+                }
+                SectionType::Paragraph | SectionType::List | SectionType::Hyperlink | SectionType::Shape => {
+                    let text = section.content.trim();
+                    let offset = offset + section.leading_whitespace();
+                    for (start, end) in split_text(text, self.config.max_size, self.config.unit) {
+                        builder.push(Piece {
+                            text: slice_chars(text, start, end),
+                            section_index,
+                            start: offset + start,
+                            end: offset + end,
+                            kind: PieceKind::Text,
+                        });
+                    }
+                }
+            }
+        }
+
+        builder.flush();
+        builder.chunks
+    }
+}
+
+struct ChunkBuilder<'a> {
+    config: &'a ChunkConfig,
+    chunks: Vec<Chunk>,
+    current: Vec<Piece>,
+    /// 当前标题路径（级别、标题）
+    headings: Vec<(u8, String)>,
+}
+
+impl ChunkBuilder<'_> {
+    fn measure(&self, pieces: &[Piece]) -> usize {
+        self.config.unit.measure(&join_pieces(pieces))
+    }
+
+    fn push(&mut self, piece: Piece) {
+        if piece.text.is_empty() {
+            return;
+        }
+        let mut candidate = self.current.clone();
+        candidate.push(piece.clone());
+        if self.current.is_empty() || self.measure(&candidate) <= self.config.max_size {
+            self.current = candidate;
+            return;
+        }
+
+        let tail = self.overlap_tail();
+        self.flush();
+        self.current = tail;
+        // 表格续块以表头开始
+        if let PieceKind::TableRow(Some(header)) = &piece.kind {
+            let has_header = self
+                .current
+                .iter()
+                .any(|existing| existing.kind == PieceKind::TableHeader && existing.section_index == header.section_index);
+            if !has_header {
+                self.current.push((**header).clone());
+            }
+        }
+        self.current.push(piece);
+        // 重叠部分不能让块超出预算
+        while self.current.len() > 1
+            && self.current[0].kind != PieceKind::TableHeader
+            && self.measure(&self.current) > self.config.max_size
+        {
+            self.current.remove(0);
+        }
+    }
+
+    /// 块末尾不超过重叠长度的整段/整行
+    fn overlap_tail(&self) -> Vec<Piece> {
+        let mut tail = Vec::new();
+        for piece in self.current.iter().rev() {
+            if piece.kind == PieceKind::Heading {
+                break;
+            }
+            let mut candidate = vec![piece.clone()];
+            candidate.extend(tail.iter().cloned());
+            if self.measure(&candidate) > self.config.overlap {
+                break;
+            }
+            tail = candidate;
+        }
+        tail
+    }
+
+    fn flush(&mut self) {
+        if self.current.is_empty() {
+            return;
+        }
+        let pieces = std::mem::take(&mut self.current);
+        let text = join_pieces(&pieces);
+        let mut sections: Vec<usize> = pieces.iter().map(|piece| piece.section_index).collect();
+        sections.sort_unstable();
+        sections.dedup();
+        self.chunks.push(Chunk {
+            index: self.chunks.len(),
+            source: None,
+            heading_path: self.headings.iter().map(|(_, title)| title.clone()).collect(),
+            sections,
+            start_offset: pieces.iter().map(|piece| piece.start).min().unwrap_or_default(),
+            end_offset: pieces.iter().map(|piece| piece.end).max().unwrap_or_default(),
+            char_count: text.chars().count(),
+            token_estimate: estimate_tokens(&text),
+            text,
+        });
+    }
+}
+
+/// 同一段落切出的部分以空格相连，其余以换行分隔
+fn join_pieces(pieces: &[Piece]) -> String {
+    let mut text = String::new();
+    for (index, piece) in pieces.iter().enumerate() {
+        if index > 0 {
+            let same_paragraph = piece.kind == PieceKind::Text
+                && pieces[index - 1].kind == PieceKind::Text
+                && pieces[index - 1].section_index == piece.section_index;
+            text.push(if same_paragraph { ' ' } else { '\n' });
+        }
+        text.push_str(&piece.text);
+    }
+    text
+}
+
+/// 表格每个非空行一块，偏移在表格纯文本中查找
+fn table_pieces(section: &DocSection, section_index: usize, offset: usize) -> Vec<Piece> {
+    let Some(table) = &section.table else {
+        return Vec::new();
+    };
+    let mut pieces = Vec::new();
+    let mut header: Option<Box<Piece>> = None;
+    let mut cursor = 0;
+    let mut char_cursor = 0;
+
+    for row in table.rows.iter().filter(|row| !row.is_blank()) {
+        let text = row.plain_text();
+        let mut start = char_cursor;
+        // 从上一行末尾继续查找，相同的行各自得到自己的位置
+        if let Some(found) = section.content[cursor..].find(&text) {
+            start += section.content[cursor..cursor + found].chars().count();
+            char_cursor = start + text.chars().count();
+            cursor += found + text.len();
+        }
+        let piece = Piece {
+            section_index,
+            start: offset + start,
+            end: offset + start + text.chars().count(),
+            text,
+            kind: if row.is_header { PieceKind::TableHeader } else { PieceKind::TableRow(header.clone()) },
+        };
+        if row.is_header {
+            header = Some(Box::new(piece.clone()));
+        }
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// 把过长的文本切成不超过预算的字符范围：先按句子，句子过长时按词，词过长时按字符。
+/// 切出的范围由块组装时再合并，以便重叠按句子计
+fn split_text(text: &str, max_size: usize, unit: ChunkUnit) -> Vec<(usize, usize)> {
+    let chars: Vec<char> = text.chars().collect();
+    let measure = |start: usize, end: usize| unit.measure(&chars[start..end].iter().collect::<String>());
+    if chars.is_empty() {
+        return Vec::new();
+    }
+    if measure(0, chars.len()) <= max_size {
+        return vec![(0, chars.len())];
+    }
+
+    let mut ranges = Vec::new();
+    for sentence in boundaries(&chars, 0, chars.len(), is_sentence_end) {
+        if measure(sentence.0, sentence.1) <= max_size {
+            ranges.push(sentence);
+            continue;
+        }
+        for word in boundaries(&chars, sentence.0, sentence.1, |chars, index| chars[index].is_whitespace()) {
+            if measure(word.0, word.1) <= max_size {
+                ranges.push(word);
+                continue;
+            }
+            // 没有空白可断开（如中文长句），按字符切开
+            let mut start = word.0;
+            while start < word.1 {
+                let end = match unit {
+                    ChunkUnit::Chars => (start + max_size.max(1)).min(word.1),
+                    ChunkUnit::Tokens => {
+                        let mut end = start + 1;
+                        while end < word.1 && measure(start, end + 1) <= max_size {
+                            end += 1;
+                        }
+                        end
+                    }
+                };
+                ranges.push((start, end));
+                start = end;
+            }
+        }
+    }
+    ranges
+}
+
+/// 在 `is_end` 为真的位置之后断开 [start, end)，去掉各段首尾空白
+fn boundaries(chars: &[char], start: usize, end: usize, is_end: fn(&[char], usize) -> bool) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut range_start = start;
+    for index in start..end {
+        if index + 1 == end || is_end(chars, index) {
+            let mut first = range_start;
+            let mut last = index + 1;
+            while first < last && chars[first].is_whitespace() {
+                first += 1;
+            }
+            while last > first && chars[last - 1].is_whitespace() {
+                last -= 1;
+            }
+            if first < last {
+                ranges.push((first, last));
+            }
+            range_start = index + 1;
+        }
+    }
+    ranges
+}
+
+fn is_sentence_end(chars: &[char], index: usize) -> bool {
+    match chars[index] {
+        '。' | '！' | '？' | '；' => true,
+        '.' | '!' | '?' | ';' => chars.get(index + 1).is_none_or(|next| next.is_whitespace()),
+        _ => false,
+    }
+}
+
+fn slice_chars(text: &str, start: usize, end: usize) -> String {
+    text.chars().skip(start).take(end - start).collect()
+}
+
+/// 每块一行 JSON
+pub fn write_jsonl<W: Write>(chunks: &[Chunk], writer: &mut W) -> Result<()> {
+    for chunk in chunks {
+        serde_json::to_writer(&mut *writer, chunk)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{DocMetadata, TableCell, TableData, TableRow};
+
+    fn table_section(rows: &[[&str; 2]]) -> DocSection {
+        let mut table = TableData::new();
+        for (index, cells) in rows.iter().enumerate() {
+            let mut row = TableRow::new(index);
+            for cell in cells {
+                row.add_cell(TableCell::new(cell.to_string()));
+            }
+            if index == 0 {
+                row.mark_as_header();
+            }
+            table.add_row(row);
+        }
+        let mut section = DocSection::new(SectionType::Table, table.plain_text());
+        section.table = Some(table);
+        section
+    }
+
+    fn doc(sections: Vec<DocSection>) -> DocData {
+        let content = sections.iter().map(|section| section.content.as_str()).collect::<Vec<_>>().join("\n");
+        DocData {
+            raw_text: content.clone(),
+            content,
+            metadata: DocMetadata {
+                title: None,
+                author: None,
+                subject: None,
+                created: None,
+                modified: None,
+                word_count: 0,
+                paragraph_count: 0,
+                page_count: None,
+                character_count: 0,
+                keywords: None,
+                description: None,
+                category: None,
+                last_modified_by: None,
+                revision: None,
+                template: None,
+                application: None,
+                recorded_word_count: None,
+                custom_properties: Default::default(),
+            },
+            sections,
+            footnotes: Vec::new(),
+            endnotes: Vec::new(),
+            comments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_headings_start_chunks() {
+        let data = doc(vec![
+            DocSection::new(SectionType::Heading(1), "Intro"),
+            DocSection::new(SectionType::Heading(2), "Scope"),
+            DocSection::new(SectionType::Paragraph, "Covers the API."),
+            DocSection::new(SectionType::Heading(1), "Design"),
+            DocSection::new(SectionType::Paragraph, "Two layers."),
+        ]);
+        let chunks = Chunker::new(ChunkConfig::chars(200)).chunk(&data);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].text, "Intro\nScope\nCovers the API.");
+        assert_eq!(chunks[0].heading_path, vec!["Intro", "Scope"]);
+        assert_eq!(chunks[0].sections, vec![0, 1, 2]);
+        assert_eq!((chunks[0].start_offset, chunks[0].end_offset), (0, 27));
+        assert_eq!(chunks[1].text, "Design\nTwo layers.");
+        assert_eq!(chunks[1].heading_path, vec!["Design"]);
+        assert_eq!((chunks[1].start_offset, chunks[1].end_offset), (28, 46));
+        assert_eq!(&data.content[28..46], "Design\nTwo layers.");
+    }
+
+    #[test]
+    fn test_long_paragraph_splits_on_sentences_with_overlap() {
+        let data = doc(vec![DocSection::new(
+            SectionType::Paragraph,
+            "First sentence here. Second sentence here. Third sentence here.",
+        )]);
+        let chunks = Chunker::new(ChunkConfig::chars(45).with_overlap(25)).chunk(&data);
+
+        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "First sentence here. Second sentence here.",
+                "Second sentence here. Third sentence here.",
+            ]
+        );
+        assert_eq!((chunks[1].start_offset, chunks[1].end_offset), (21, 63));
+    }
+
+    #[test]
+    fn test_tables_split_between_rows_with_header() {
+        let data = doc(vec![table_section(&[
+            ["Item", "Qty"],
+            ["Bolt", "12"],
+            ["Washer", "40"],
+            ["Nut", "7"],
+        ])]);
+        let chunks = Chunker::new(ChunkConfig::chars(30).with_overlap(0)).chunk(&data);
+
+        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(texts, vec!["Item | Qty\nBolt | 12", "Item | Qty\nWasher | 40\nNut | 7"]);
+        assert!(chunks.iter().all(|chunk| chunk.sections == vec![0]));
+    }
+
+    #[test]
+    fn test_offsets_of_repeated_rows_and_indented_paragraphs() {
+        let table = table_section(&[["Item", "Qty"], ["Bolt", "12"], ["Bolt", "12"], ["Bolt", "1"]]);
+        let starts: Vec<(usize, usize)> = table_pieces(&table, 0, 5).iter().map(|piece| (piece.start, piece.end)).collect();
+        assert_eq!(starts, vec![(5, 15), (16, 25), (26, 35), (36, 44)]);
+
+        let data = doc(vec![
+            DocSection::new(SectionType::Paragraph, "Intro"),
+            DocSection::new(SectionType::Paragraph, "   Indented text."),
+        ]);
+        let chunks = Chunker::new(ChunkConfig::chars(16).with_overlap(0)).chunk(&data);
+        assert_eq!(chunks[1].text, "Indented text.");
+        assert_eq!((chunks[1].start_offset, chunks[1].end_offset), (9, 23));
+        assert_eq!(&data.content[9..23], "Indented text.");
+    }
+
+    #[test]
+    fn test_token_budget_and_jsonl() {
+        assert_eq!(estimate_tokens("Tokenization matters"), 5);
+        assert_eq!(estimate_tokens("表格数据"), 4);
+
+        let data = doc(vec![DocSection::new(SectionType::Paragraph, "alpha beta gamma delta")]);
+        let chunks = Chunker::new(ChunkConfig::tokens(4).with_overlap(0)).chunk(&data);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|chunk| chunk.token_estimate <= 4));
+
+        let mut buffer = Vec::new();
+        write_jsonl(&chunks, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["index"], 0);
+        assert_eq!(first["sections"], serde_json::json!([0]));
+        assert!(first.get("source").is_none());
+    }
+}
@@ -0,0 +1,659 @@
+use crate::error::Result;
+use crate::output::table::escape_html;
+use crate::output::{note_entries, note_label, render_spans, revision_attribution, InlineMarkup, OutputWriter, TableWriter};
+use crate::parser::{DocData, DocMetadata, DocSection, ImageInfo, NoteKind, NoteReference, Outline, OutlineEntry, Revision, RevisionKind, SectionType, ShapeKind, TableOutputFormat};
+use std::collections::HashMap;
+use std::io::Write;
+
+/// 语义化 HTML5 输出：标题、段落、嵌套列表、表格、图片、链接与脚注
+pub struct HtmlOutput {
+    include_metadata: bool,
+    include_css: bool,
+    toc: bool,
+}
+
+impl HtmlOutput {
+    pub fn new(include_metadata: bool, include_css: bool) -> Self {
+        Self {
+            include_metadata,
+            include_css,
+            toc: false,
+        }
+    }
+
+    /// 在正文前插入链接到各标题的目录
+    pub fn with_toc(mut self, toc: bool) -> Self {
+        self.toc = toc;
+        self
+    }
+
+    fn write_body<W: Write>(&self, sections: &[DocSection], anchors: &HashMap<usize, &str>, writer: &mut W) -> Result<()> {
+        let mut lists = ListNesting::default();
+        for (index, section) in sections.iter().enumerate() {
+            match (&section.section_type, &section.list) {
+                (SectionType::List, Some(list)) => {
+                    lists.open_item(writer, list.level as usize + 1, list.ordered, list.number)?;
+                    write!(writer, "{}", inline_html(section, false))?;
+                    continue;
+                }
+                (SectionType::List, None) => {
+                    lists.open_item(writer, 1, false, 1)?;
+                    write!(writer, "{}", inline_html(section, false))?;
+                    continue;
+                }
+                _ => lists.close_all(writer)?,
+            }
+            writeln!(writer, "{}", convert_section_to_html(section, anchors.get(&index).copied()))?;
+        }
+        lists.close_all(writer)?;
+        Ok(())
+    }
+
+    fn write_css<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writeln!(writer, r#"    <style>
+body {{
+    font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
+    line-height: 1.6;
+    max-width: 860px;
+    margin: 0 auto;
+    padding: 20px;
+    color: #2c3e50;
+}}
+
+h1, h2, h3, h4, h5, h6 {{
+    line-height: 1.25;
+    margin: 1.5em 0 0.5em;
+}}
+
+table {{
+    border-collapse: collapse;
+    margin: 1em 0;
+}}
+
+th, td {{
+    border: 1px solid #bdc3c7;
+    padding: 6px 10px;
+    text-align: left;
+    vertical-align: top;
+}}
+
+th {{
+    background-color: #ecf0f1;
+}}
+
+figure {{
+    margin: 1em 0;
+}}
+
+figure img {{
+    max-width: 100%;
+    height: auto;
+}}
+
+.page-header, .page-footer {{
+    color: #7f8c8d;
+    font-size: 0.9em;
+}}
+
+.document-metadata dt {{
+    font-weight: bold;
+    float: left;
+    clear: left;
+    margin-right: 0.5em;
+}}
+
+.notes {{
+    border-top: 1px solid #bdc3c7;
+    margin-top: 2em;
+    font-size: 0.9em;
+}}
+
+.toc ul {{
+    list-style: none;
+    padding-left: 1.2em;
+}}
+
+.notes dt {{
+    float: left;
+    clear: left;
+    margin-right: 0.5em;
+}}
+
+ins {{
+    background-color: #e6ffed;
+}}
+
+del {{
+    background-color: #ffeef0;
+}}
+    </style>"#)?;
+        Ok(())
+    }
+
+    fn write_metadata<W: Write>(&self, writer: &mut W, metadata: &DocMetadata) -> Result<()> {
+        let mut entries: Vec<(&str, String)> = Vec::new();
+        let fields = [
+            ("Author", &metadata.author),
+            ("Subject", &metadata.subject),
+            ("Keywords", &metadata.keywords),
+            ("Category", &metadata.category),
+            ("Description", &metadata.description),
+            ("Created", &metadata.created),
+            ("Modified", &metadata.modified),
+            ("Last modified by", &metadata.last_modified_by),
+            ("Template", &metadata.template),
+            ("Application", &metadata.application),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                entries.push((name, value.clone()));
+            }
+        }
+        if let Some(revision) = metadata.revision {
+            entries.push(("Revision", revision.to_string()));
+        }
+        if let Some(page_count) = metadata.page_count {
+            entries.push(("Pages", page_count.to_string()));
+        }
+        entries.push(("Words", metadata.word_count.to_string()));
+
+        writeln!(writer, "<header class=\"document-metadata\">")?;
+        writeln!(writer, "<dl>")?;
+        for (name, value) in entries {
+            writeln!(writer, "  <dt>{}</dt><dd>{}</dd>", name, escape_html(&value))?;
+        }
+        for (name, value) in &metadata.custom_properties {
+            writeln!(writer, "  <dt>{}</dt><dd>{}</dd>", escape_html(name), escape_html(value))?;
+        }
+        writeln!(writer, "</dl>")?;
+        writeln!(writer, "</header>")?;
+        Ok(())
+    }
+}
+
+impl OutputWriter for HtmlOutput {
+    fn write_doc_data<W: Write>(&self, data: &DocData, writer: &mut W) -> Result<()> {
+        let metadata = &data.metadata;
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html>")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "    <meta charset=\"UTF-8\">")?;
+        writeln!(writer, "    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">")?;
+        writeln!(writer, "    <title>{}</title>", escape_html(metadata.title.as_deref().unwrap_or("Document")))?;
+        if self.include_metadata {
+            for (name, value) in [("author", &metadata.author), ("keywords", &metadata.keywords), ("description", &metadata.description)] {
+                if let Some(value) = value {
+                    writeln!(writer, "    <meta name=\"{}\" content=\"{}\">", name, escape_html(value))?;
+                }
+            }
+        }
+        if self.include_css {
+            self.write_css(writer)?;
+        }
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<article>")?;
+
+        if self.include_metadata {
+            self.write_metadata(writer, metadata)?;
+        }
+        let outline = if self.toc { Outline::from_doc(data) } else { Outline::default() };
+        if !outline.is_empty() {
+            writeln!(writer, "<nav class=\"toc\" aria-label=\"Table of contents\">")?;
+            write_toc_entries(writer, &outline.entries)?;
+            writeln!(writer, "</nav>")?;
+        }
+        self.write_body(&data.sections, &outline.anchors(), writer)?;
+
+        // 脚注、尾注和批注，与正文中的引用互相链接
+        let notes = note_entries(data);
+        if !notes.is_empty() {
+            writeln!(writer, "<section class=\"notes\">")?;
+            writeln!(writer, "<dl>")?;
+            for (label, text) in notes {
+                writeln!(
+                    writer,
+                    "  <dt id=\"note-{}\">{}</dt><dd>{} <a href=\"#ref-{}\" class=\"note-back\">↩</a></dd>",
+                    label,
+                    label,
+                    escape_html(&text).replace('\n', "<br>"),
+                    label
+                )?;
+            }
+            writeln!(writer, "</dl>")?;
+            writeln!(writer, "</section>")?;
+        }
+
+        writeln!(writer, "</article>")?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+
+    fn write_sections<W: Write>(&self, sections: &[DocSection], writer: &mut W) -> Result<()> {
+        self.write_body(sections, &HashMap::new(), writer)
+    }
+}
+
+/// 目录：嵌套列表，每项链接到标题的 id
+fn write_toc_entries<W: Write>(writer: &mut W, entries: &[OutlineEntry]) -> Result<()> {
+    writeln!(writer, "<ul>")?;
+    for entry in entries {
+        write!(
+            writer,
+            "<li><a href=\"#{}\">{} {}</a>",
+            escape_html(&entry.anchor),
+            entry.number,
+            escape_html(&entry.title)
+        )?;
+        if !entry.children.is_empty() {
+            writeln!(writer)?;
+            write_toc_entries(writer, &entry.children)?;
+        }
+        writeln!(writer, "</li>")?;
+    }
+    writeln!(writer, "</ul>")?;
+    Ok(())
+}
+
+/// 打开中的列表及其最后一项；列表项的 `<li>` 保持打开，以便下一级列表嵌套在其中
+#[derive(Default)]
+struct ListNesting {
+    tags: Vec<&'static str>,
+}
+
+impl ListNesting {
+    fn open_item<W: Write>(&mut self, writer: &mut W, depth: usize, ordered: bool, number: u32) -> Result<()> {
+        let tag = if ordered { "ol" } else { "ul" };
+        while self.tags.len() > depth {
+            self.close_one(writer)?;
+        }
+        if self.tags.len() == depth {
+            if self.tags.last() == Some(&tag) {
+                writeln!(writer, "</li>")?;
+            } else {
+                self.close_one(writer)?;
+            }
+        }
+        while self.tags.len() < depth {
+            if !self.tags.is_empty() {
+                writeln!(writer)?;
+            }
+            match tag {
+                "ol" if number > 1 => writeln!(writer, "<ol start=\"{}\">", number)?,
+                _ => writeln!(writer, "<{}>", tag)?,
+            }
+            self.tags.push(tag);
+        }
+        write!(writer, "<li>")?;
+        Ok(())
+    }
+
+    fn close_one<W: Write>(&mut self, writer: &mut W) -> Result<()> {
+        if let Some(tag) = self.tags.pop() {
+            writeln!(writer, "</li>")?;
+            write!(writer, "</{}>", tag)?;
+            if self.tags.is_empty() {
+                writeln!(writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn close_all<W: Write>(&mut self, writer: &mut W) -> Result<()> {
+        while !self.tags.is_empty() {
+            self.close_one(writer)?;
+        }
+        Ok(())
+    }
+}
+
+const HTML_MARKUP: InlineMarkup = InlineMarkup {
+    bold: ("<strong>", "</strong>"),
+    italic: ("<em>", "</em>"),
+    underline: ("<u>", "</u>"),
+    strike: ("<s>", "</s>"),
+    highlight: ("<mark>", "</mark>"),
+    superscript: ("<sup>", "</sup>"),
+    subscript: ("<sub>", "</sub>"),
+    link: |text, href| match safe_href(href) {
+        Some(href) => format!("<a href=\"{}\">{}</a>", escape_html(href), text),
+        None => text.to_string(),
+    },
+    note: note_reference,
+    revision: html_revision,
+    escape: escape_html,
+    math: |equation| equation.mathml.clone(),
+};
+
+/// 脚注引用：上标编号链接到文末注释
+fn note_reference(note: &NoteReference) -> String {
+    let label = note_label(note.kind, note.id);
+    let class = match note.kind {
+        NoteKind::Footnote => "footnote-ref",
+        NoteKind::Endnote => "endnote-ref",
+        NoteKind::Comment => "comment-ref",
+    };
+    format!("<sup class=\"{}\"><a href=\"#note-{}\" id=\"ref-{}\">{}</a></sup>", class, label, label, label)
+}
+
+/// 修订：`<ins>`/`<del>`，作者和日期写在 title 中
+fn html_revision(text: &str, revision: &Revision) -> String {
+    let tag = match revision.kind {
+        RevisionKind::Insertion => "ins",
+        RevisionKind::Deletion => "del",
+    };
+    let datetime = revision
+        .date
+        .as_deref()
+        .map(|date| format!(" datetime=\"{}\"", escape_html(date)))
+        .unwrap_or_default();
+    format!("<{} title=\"{}\"{}>{}</{}>", tag, escape_html(&revision_attribution(revision)), datetime, text, tag)
+}
+
+fn convert_section_to_html(section: &DocSection, anchor: Option<&str>) -> String {
+    match &section.section_type {
+        SectionType::Heading(level) => {
+            let level = (*level).clamp(1, 6);
+            // 编号标题保留编号
+            let number = match &section.list {
+                Some(list) if list.ordered && !list.label.is_empty() => format!("{} ", escape_html(&list.label)),
+                _ => String::new(),
+            };
+            let id = anchor.map(|anchor| format!(" id=\"{}\"", escape_html(anchor))).unwrap_or_default();
+            format!("<h{}{}>{}{}</h{}>", level, id, number, inline_html(section, true), level)
+        }
+        SectionType::Table => match &section.table {
+            Some(table) => TableWriter::new(TableOutputFormat::HTML)
+                .table_to_string(table)
+                .map(|html| html.trim_end().to_string())
+                .unwrap_or_default(),
+            None => format!("<pre>{}</pre>", escape_html(&section.content)),
+        },
+        SectionType::Image => match &section.image {
+            Some(image) => image_html(image),
+            None => format!("<figure><img{} alt=\"\"></figure>", src_attribute(&section.content)),
+        },
+        SectionType::Shape => shape_html(section),
+        SectionType::Header => format!("<header class=\"page-header\"><p>{}</p></header>", inline_html(section, false)),
+        SectionType::Footer => format!("<footer class=\"page-footer\"><p>{}</p></footer>", inline_html(section, false)),
+        SectionType::Hyperlink if section.spans.is_empty() => {
+            // 假设内容格式为 "text|url"
+            let (text, url) = section.content.split_once('|').unwrap_or((&section.content, &section.content));
+            match safe_href(url.trim()) {
+                Some(url) => format!("<p><a href=\"{}\">{}</a></p>", escape_html(url), escape_html(text.trim())),
+                None => format!("<p>{}</p>", escape_html(text.trim())),
+            }
+        }
+        SectionType::List | SectionType::Hyperlink | SectionType::Paragraph => {
+            format!("<p>{}</p>", inline_html(section, false))
+        }
+    }
+}
+
+/// 图形中的文字：`<aside>` 中每段一个 `<p>`，class 标明图形种类
+fn shape_html(section: &DocSection) -> String {
+    let class = match section.shape.as_ref().map(|shape| shape.kind) {
+        Some(ShapeKind::Canvas) => "canvas",
+        Some(ShapeKind::SmartArt) => "smartart",
+        Some(ShapeKind::TextBox) | None => "text-box",
+    };
+    let title = section
+        .shape
+        .as_ref()
+        .and_then(|shape| shape.name.as_deref())
+        .map(|name| format!(" title=\"{}\"", escape_html(name)))
+        .unwrap_or_default();
+    let paragraphs: String = section
+        .content
+        .lines()
+        .map(|line| format!("<p>{}</p>", escape_html(line)))
+        .collect();
+    format!("<aside class=\"shape {}\"{}>{}</aside>", class, title, paragraphs)
+}
+
+/// 段落内容：书签作为锚点，其后为带格式、链接和注释引用的文本
+fn inline_html(section: &DocSection, skip_bold: bool) -> String {
+    let anchors: String = section
+        .bookmarks
+        .iter()
+        .map(|name| format!("<a id=\"{}\"></a>", escape_html(name)))
+        .collect();
+    let text = if section.spans.is_empty() {
+        escape_html(&section.content)
+    } else {
+        render_spans(&section.spans, &section.notes, &HTML_MARKUP, skip_bold)
+    };
+    anchors + &text.replace('\n', "<br>")
+}
+
+/// URL 的协议名（小写）；相对路径和页内锚点没有协议。浏览器会忽略协议中的空白和控制字符
+fn url_scheme(url: &str) -> Option<String> {
+    let (scheme, _) = url.split_once(':')?;
+    let scheme: String = scheme.chars().filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control()).collect();
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then(|| scheme.to_ascii_lowercase())
+}
+
+/// 链接只保留 http(s)、mailto 和页内锚点，`javascript:` 等其他目标返回 None
+fn safe_href(url: &str) -> Option<&str> {
+    let url = url.trim();
+    match url_scheme(url).as_deref() {
+        Some("http" | "https" | "mailto") => Some(url),
+        None if url.starts_with('#') => Some(url),
+        _ => None,
+    }
+}
+
+/// 图片的 `src` 属性：允许 http(s) 和本地路径（含 Windows 盘符），其他协议省略该属性
+fn src_attribute(url: &str) -> String {
+    let url = url.trim();
+    match url_scheme(url).as_deref() {
+        Some("http" | "https") | None => format!(" src=\"{}\"", escape_html(url)),
+        Some(drive) if drive.len() == 1 => format!(" src=\"{}\"", escape_html(url)),
+        _ => String::new(),
+    }
+}
+
+/// 图片：替代文字作为 alt，标题作为 figcaption
+fn image_html(image: &ImageInfo) -> String {
+    let mut attributes = format!(
+        "{} alt=\"{}\"",
+        src_attribute(image.source()),
+        escape_html(image.description().unwrap_or(""))
+    );
+    if let Some(width) = image.width {
+        attributes.push_str(&format!(" width=\"{}\"", width));
+    }
+    if let Some(height) = image.height {
+        attributes.push_str(&format!(" height=\"{}\"", height));
+    }
+    match image.title.as_deref().filter(|title| Some(*title) != image.alt_text.as_deref()) {
+        Some(title) => format!("<figure><img{}><figcaption>{}</figcaption></figure>", attributes, escape_html(title)),
+        None => format!("<figure><img{}></figure>", attributes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{FormatInfo, Link, ListInfo, TextSpan};
+
+    fn render(sections: &[DocSection]) -> String {
+        let mut buffer = Vec::new();
+        HtmlOutput::new(false, false).write_sections(sections, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_headings_paragraphs_and_escaping() {
+        let mut heading = DocSection::new(SectionType::Heading(2), "Q&A");
+        heading.level = Some(2);
+        let mut paragraph = DocSection::new(SectionType::Paragraph, "Use <b> & see docs");
+        paragraph.spans = vec![
+            TextSpan { text: "Use <b> & ".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+            TextSpan {
+                text: "see docs".to_string(),
+                formatting: FormatInfo { italic: true, ..Default::default() },
+                link: Some(Link { url: Some("https://example.com/?a=1&b=2".to_string()), anchor: None }),
+                revision: None,
+                equation: None,
+            },
+        ];
+        paragraph.notes = vec![NoteReference { kind: NoteKind::Footnote, id: 1, offset: 18 }];
+
+        assert_eq!(
+            render(&[heading, paragraph]),
+            concat!(
+                "<h2>Q&amp;A</h2>\n",
+                "<p>Use &lt;b&gt; &amp; <a href=\"https://example.com/?a=1&amp;b=2\"><em>see docs</em></a>",
+                "<sup class=\"footnote-ref\"><a href=\"#note-1\" id=\"ref-1\">1</a></sup></p>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_unsafe_urls_are_dropped() {
+        let linked = |text: &str, url: &str| TextSpan {
+            text: text.to_string(),
+            formatting: FormatInfo::default(),
+            link: Some(Link { url: Some(url.to_string()), anchor: None }),
+            revision: None,
+            equation: None,
+        };
+        let mut paragraph = DocSection::new(SectionType::Paragraph, "a b c d");
+        paragraph.spans = vec![
+            linked("a", "javascript:alert(1)"),
+            linked(" b", " JaVa\tScript:alert(1)"),
+            linked(" c", "mailto:team@example.com"),
+            linked(" d", "data:text/html,<script>"),
+        ];
+        let mut image = DocSection::new(SectionType::Image, "");
+        image.image = Some(ImageInfo { target: "javascript:alert(1)".to_string(), alt_text: Some("Chart".to_string()), ..Default::default() });
+        let mut exported = DocSection::new(SectionType::Image, "");
+        exported.image = Some(ImageInfo { path: Some("C:\\out\\image1.png".to_string()), ..Default::default() });
+
+        assert_eq!(
+            render(&[paragraph, image, exported]),
+            concat!(
+                "<p>a b <a href=\"mailto:team@example.com\">c</a> d</p>\n",
+                "<figure><img alt=\"Chart\"></figure>\n",
+                "<figure><img src=\"C:\\out\\image1.png\" alt=\"\"></figure>\n",
+            )
+        );
+        assert_eq!(safe_href("#results"), Some("#results"));
+        assert_eq!(safe_href("https://example.com"), Some("https://example.com"));
+        assert_eq!(safe_href("file:///etc/passwd"), None);
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        let item = |text: &str, level: u8, ordered: bool, number: u32| {
+            let mut item = DocSection::new(SectionType::List, text);
+            item.list = Some(ListInfo { level, ordered, label: String::new(), number });
+            item
+        };
+        let html = render(&[
+            item("One", 0, true, 1),
+            item("Detail", 1, false, 1),
+            item("Two", 0, true, 2),
+            DocSection::new(SectionType::Paragraph, "After"),
+        ]);
+        assert_eq!(
+            html,
+            "<ol>\n<li>One\n<ul>\n<li>Detail</li>\n</ul></li>\n<li>Two</li>\n</ol>\n<p>After</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_table_spans() {
+        use crate::parser::{TableCell, TableData, TableRow};
+
+        let mut table = TableData::new();
+        let mut first = TableRow::new(0);
+        let mut merged = TableCell::new("Sales".to_string());
+        merged.colspan = Some(2);
+        let mut covered = TableCell::new(String::new());
+        covered.set_merged(None, None);
+        first.add_cell(merged);
+        first.add_cell(covered);
+        table.add_row(first);
+        let mut second = TableRow::new(1);
+        second.add_cell(TableCell::new("Q1".to_string()));
+        second.add_cell(TableCell::new("Q2".to_string()));
+        table.add_row(second);
+
+        let mut tables = DocSection::new(SectionType::Table, table.plain_text());
+        tables.table = Some(table);
+        let html = render(&[tables]);
+        assert!(html.starts_with("<table>\n"));
+        assert!(html.contains("<tr><td colspan=\"2\">Sales</td></tr>"));
+        assert!(html.contains("<tr><td>Q1</td><td>Q2</td></tr>"));
+        assert!(html.ends_with("</table>\n"));
+    }
+
+    #[test]
+    fn test_document_with_notes_and_css() {
+        use crate::parser::Note;
+
+        let data = DocData {
+            content: "Body".to_string(),
+            raw_text: "Body".to_string(),
+            metadata: DocMetadata {
+                title: Some("Report <draft>".to_string()),
+                author: Some("Ann".to_string()),
+                subject: None,
+                created: None,
+                modified: None,
+                word_count: 1,
+                paragraph_count: 1,
+                page_count: None,
+                character_count: 4,
+                keywords: None,
+                description: None,
+                category: None,
+                last_modified_by: None,
+                revision: None,
+                template: None,
+                application: None,
+                recorded_word_count: None,
+                custom_properties: Default::default(),
+            },
+            sections: vec![DocSection::new(SectionType::Paragraph, "Body")],
+            footnotes: vec![Note { id: 1, content: "A & B".to_string() }],
+            endnotes: Vec::new(),
+            comments: Vec::new(),
+        };
+
+        let mut buffer = Vec::new();
+        HtmlOutput::new(true, true).write_doc_data(&data, &mut buffer).unwrap();
+        let html = String::from_utf8(buffer).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>\n<head>\n"));
+        assert!(html.contains("<title>Report &lt;draft&gt;</title>"));
+        assert!(html.contains("<meta name=\"author\" content=\"Ann\">"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<dt>Author</dt><dd>Ann</dd>"));
+        assert!(html.contains("<p>Body</p>"));
+        assert!(html.contains("<dt id=\"note-1\">1</dt><dd>A &amp; B <a href=\"#ref-1\" class=\"note-back\">↩</a></dd>"));
+        assert!(html.ends_with("</article>\n</body>\n</html>\n"));
+        assert!(!html.contains("class=\"toc\""));
+
+        // 目录链接到标题的 id
+        let mut data = data;
+        data.sections = vec![
+            DocSection::new(SectionType::Heading(1), "Scope & Goals"),
+            DocSection::new(SectionType::Heading(2), "Terms"),
+            DocSection::new(SectionType::Paragraph, "Body"),
+        ];
+        let mut buffer = Vec::new();
+        HtmlOutput::new(false, false).with_toc(true).write_doc_data(&data, &mut buffer).unwrap();
+        let html = String::from_utf8(buffer).unwrap();
+        assert!(html.contains(concat!(
+            "<nav class=\"toc\" aria-label=\"Table of contents\">\n<ul>\n",
+            "<li><a href=\"#scope-goals\">1 Scope &amp; Goals</a>\n<ul>\n",
+            "<li><a href=\"#terms\">1.1 Terms</a></li>\n</ul>\n</li>\n</ul>\n</nav>\n",
+            "<h1 id=\"scope-goals\">Scope &amp; Goals</h1>\n<h2 id=\"terms\">Terms</h2>\n"
+        )));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::error::Result;
 use crate::output::OutputWriter;
-use crate::parser::{DocData, DocSection, DocMetadata, SectionType, FormatInfo};
+use crate::parser::table::{CellAlignment, CellBlock, CellType, TableData};
+use crate::parser::{Comment, DocData, DocSection, DocMetadata, SectionType, FormatInfo, ImageInfo, Link, Note, NoteKind, NoteReference, Revision, RevisionKind, ShapeInfo, ShapeKind, TextSpan, VerticalAlign};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::io::Write;
 
 pub struct JsonOutput {
@@ -55,6 +57,73 @@ struct JsonDocData {
     raw_text: String,
     metadata: JsonDocMetadata,
     sections: Vec<JsonDocSection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    footnotes: Vec<JsonNote>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    endnotes: Vec<JsonNote>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    comments: Vec<JsonComment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    revisions: Vec<JsonRevisionEntry>,
+}
+
+/// 显示修订时的一处插入或删除：所在段落序号及文字
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonRevisionEntry {
+    section: usize,
+    text: String,
+    #[serde(flatten)]
+    revision: JsonRevision,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonRevision {
+    kind: JsonRevisionKind,
+    author: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+enum JsonRevisionKind {
+    Insertion,
+    Deletion,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonNote {
+    id: usize,
+    content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonComment {
+    id: usize,
+    author: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    anchor_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent_id: Option<usize>,
+}
+
+/// 段落中脚注、尾注或批注引用的位置
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonNoteReference {
+    kind: JsonNoteKind,
+    id: usize,
+    offset: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+enum JsonNoteKind {
+    Footnote,
+    Endnote,
+    Comment,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -68,6 +137,24 @@ struct JsonDocMetadata {
     paragraph_count: usize,
     page_count: Option<usize>,
     character_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    keywords: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    revision: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    application: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recorded_word_count: Option<usize>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    custom_properties: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -77,6 +164,179 @@ struct JsonDocSection {
     level: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     formatting: Option<JsonFormatInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spans: Vec<JsonTextSpan>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    list: Option<JsonListInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    links: Vec<JsonLink>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    bookmarks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<JsonNoteReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    equations: Vec<JsonEquation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image: Option<JsonImage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    table: Option<JsonTable>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shape: Option<JsonShape>,
+}
+
+/// 公式：在 content 中的字符位置（content 中为其 LaTeX），以及 MathML 和原始 OMML
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonEquation {
+    offset: usize,
+    latex: String,
+    mathml: String,
+    omml: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    display: bool,
+}
+
+/// 表格结构：逐行逐单元格，保留表头标记和合并范围
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonTable {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    headers: Option<Vec<String>>,
+    row_count: usize,
+    column_count: usize,
+    rows: Vec<JsonTableRow>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonTableRow {
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    is_header: bool,
+    cells: Vec<JsonTableCell>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonTableCell {
+    content: String,
+    cell_type: JsonCellType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    colspan: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rowspan: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alignment: Option<JsonCellAlignment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocks: Vec<JsonCellBlock>,
+}
+
+/// 单元格中的块：段落、列表项或嵌套表格
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonCellBlock {
+    Paragraph {
+        text: String,
+    },
+    ListItem {
+        level: u8,
+        ordered: bool,
+        label: String,
+        text: String,
+    },
+    Table {
+        table: JsonTable,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+enum JsonCellType {
+    Header,
+    Data,
+    Merged,
+    Empty,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+enum JsonCellAlignment {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+/// 图片：`src` 为导出的文件路径，未导出时为包内路径或外部地址
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonImage {
+    src: String,
+    target: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    external: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alt_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    anchored: bool,
+}
+
+/// 含文字的图形：种类和绘图对象名称，文字在 `content` 中
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonShape {
+    kind: JsonShapeKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    anchored: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+enum JsonShapeKind {
+    TextBox,
+    Canvas,
+    #[serde(rename = "smartart")]
+    SmartArt,
+}
+
+/// 段落中的一个链接：链接文字及目标
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonLink {
+    text: String,
+    #[serde(flatten)]
+    target: JsonLinkTarget,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonLinkTarget {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anchor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonListInfo {
+    level: u8,
+    ordered: bool,
+    label: String,
+    number: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonTextSpan {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    formatting: Option<JsonFormatInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    link: Option<JsonLinkTarget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    revision: Option<JsonRevision>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -98,6 +358,8 @@ enum JsonSectionType {
     Header,
     #[serde(rename = "hyperlink")]
     Hyperlink,
+    #[serde(rename = "shape")]
+    Shape,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -105,10 +367,17 @@ struct JsonFormatInfo {
     bold: bool,
     italic: bool,
     underline: bool,
+    strike: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
-    font_size: Option<u32>,
+    font_size: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     font_family: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    highlight: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vertical_align: Option<String>,
 }
 
 impl JsonDocData {
@@ -122,6 +391,78 @@ impl JsonDocData {
                 .iter()
                 .map(|s| JsonDocSection::from_doc_section(s, include_formatting))
                 .collect(),
+            footnotes: data.footnotes.iter().map(JsonNote::from_note).collect(),
+            endnotes: data.endnotes.iter().map(JsonNote::from_note).collect(),
+            comments: data.comments.iter().map(JsonComment::from_comment).collect(),
+            revisions: JsonRevisionEntry::from_sections(&data.sections),
+        }
+    }
+}
+
+impl JsonRevisionEntry {
+    /// 相邻且属于同一修订的文字合并为一条
+    fn from_sections(sections: &[DocSection]) -> Vec<Self> {
+        let mut entries = Vec::new();
+        for (index, section) in sections.iter().enumerate() {
+            for run in section.spans.chunk_by(|a, b| a.revision == b.revision) {
+                if let Some(revision) = &run[0].revision {
+                    entries.push(Self {
+                        section: index,
+                        text: run.iter().map(|span| span.text.as_str()).collect(),
+                        revision: JsonRevision::from_revision(revision),
+                    });
+                }
+            }
+        }
+        entries
+    }
+}
+
+impl JsonRevision {
+    fn from_revision(revision: &Revision) -> Self {
+        Self {
+            kind: match revision.kind {
+                RevisionKind::Insertion => JsonRevisionKind::Insertion,
+                RevisionKind::Deletion => JsonRevisionKind::Deletion,
+            },
+            author: revision.author.clone(),
+            date: revision.date.clone(),
+        }
+    }
+}
+
+impl JsonNote {
+    fn from_note(note: &Note) -> Self {
+        Self {
+            id: note.id,
+            content: note.content.clone(),
+        }
+    }
+}
+
+impl JsonComment {
+    fn from_comment(comment: &Comment) -> Self {
+        Self {
+            id: comment.id,
+            author: comment.author.clone(),
+            date: comment.date.clone(),
+            content: comment.content.clone(),
+            anchor_text: comment.anchor_text.clone(),
+            parent_id: comment.parent_id,
+        }
+    }
+}
+
+impl JsonNoteReference {
+    fn from_note_reference(reference: &NoteReference) -> Self {
+        Self {
+            kind: match reference.kind {
+                NoteKind::Footnote => JsonNoteKind::Footnote,
+                NoteKind::Endnote => JsonNoteKind::Endnote,
+                NoteKind::Comment => JsonNoteKind::Comment,
+            },
+            id: reference.id,
+            offset: reference.offset,
         }
     }
 }
@@ -138,6 +479,15 @@ impl JsonDocMetadata {
             paragraph_count: metadata.paragraph_count,
             page_count: metadata.page_count,
             character_count: metadata.character_count,
+            keywords: metadata.keywords.clone(),
+            description: metadata.description.clone(),
+            category: metadata.category.clone(),
+            last_modified_by: metadata.last_modified_by.clone(),
+            revision: metadata.revision,
+            template: metadata.template.clone(),
+            application: metadata.application.clone(),
+            recorded_word_count: metadata.recorded_word_count,
+            custom_properties: metadata.custom_properties.clone(),
         }
     }
 }
@@ -153,6 +503,146 @@ impl JsonDocSection {
             } else {
                 None
             },
+            spans: if include_formatting {
+                section.spans.iter().map(JsonTextSpan::from_text_span).collect()
+            } else {
+                Vec::new()
+            },
+            list: section.list.as_ref().map(|list| JsonListInfo {
+                level: list.level,
+                ordered: list.ordered,
+                label: list.label.clone(),
+                number: list.number,
+            }),
+            links: section
+                .links()
+                .into_iter()
+                .map(|(text, link)| JsonLink {
+                    text,
+                    target: JsonLinkTarget::from_link(link),
+                })
+                .collect(),
+            bookmarks: section.bookmarks.clone(),
+            notes: section.notes.iter().map(JsonNoteReference::from_note_reference).collect(),
+            equations: section
+                .equations()
+                .into_iter()
+                .map(|(offset, equation)| JsonEquation {
+                    offset,
+                    latex: equation.latex.clone(),
+                    mathml: equation.mathml.clone(),
+                    omml: equation.omml.clone(),
+                    display: equation.display,
+                })
+                .collect(),
+            image: section.image.as_ref().map(JsonImage::from_image),
+            table: section.table.as_ref().map(JsonTable::from_table),
+            shape: section.shape.as_ref().map(JsonShape::from_shape),
+        }
+    }
+}
+
+impl JsonTable {
+    fn from_table(table: &TableData) -> Self {
+        Self {
+            title: table.title.clone(),
+            headers: table.headers.clone(),
+            row_count: table.rows.len(),
+            column_count: table.column_count,
+            rows: table
+                .rows
+                .iter()
+                .map(|row| JsonTableRow {
+                    is_header: row.is_header,
+                    cells: row
+                        .cells
+                        .iter()
+                        .map(|cell| JsonTableCell {
+                            content: cell.content.clone(),
+                            cell_type: match cell.cell_type {
+                                CellType::Header => JsonCellType::Header,
+                                CellType::Data => JsonCellType::Data,
+                                CellType::Merged => JsonCellType::Merged,
+                                CellType::Empty => JsonCellType::Empty,
+                            },
+                            colspan: cell.colspan,
+                            rowspan: cell.rowspan,
+                            alignment: cell.alignment.as_ref().map(|alignment| match alignment {
+                                CellAlignment::Left => JsonCellAlignment::Left,
+                                CellAlignment::Center => JsonCellAlignment::Center,
+                                CellAlignment::Right => JsonCellAlignment::Right,
+                                CellAlignment::Justify => JsonCellAlignment::Justify,
+                            }),
+                            blocks: cell.blocks.iter().map(JsonCellBlock::from_cell_block).collect(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl JsonCellBlock {
+    fn from_cell_block(block: &CellBlock) -> Self {
+        match block {
+            CellBlock::Paragraph { text } => JsonCellBlock::Paragraph { text: text.clone() },
+            CellBlock::ListItem { level, ordered, label, text } => JsonCellBlock::ListItem {
+                level: *level,
+                ordered: *ordered,
+                label: label.clone(),
+                text: text.clone(),
+            },
+            CellBlock::Table { table } => JsonCellBlock::Table { table: JsonTable::from_table(table) },
+        }
+    }
+}
+
+impl JsonImage {
+    fn from_image(image: &ImageInfo) -> Self {
+        Self {
+            src: image.source().to_string(),
+            target: image.target.clone(),
+            external: image.external,
+            alt_text: image.alt_text.clone(),
+            title: image.title.clone(),
+            name: image.name.clone(),
+            width: image.width,
+            height: image.height,
+            anchored: image.anchored,
+        }
+    }
+}
+
+impl JsonShape {
+    fn from_shape(shape: &ShapeInfo) -> Self {
+        Self {
+            kind: match shape.kind {
+                ShapeKind::TextBox => JsonShapeKind::TextBox,
+                ShapeKind::Canvas => JsonShapeKind::Canvas,
+                ShapeKind::SmartArt => JsonShapeKind::SmartArt,
+            },
+            name: shape.name.clone(),
+            anchored: shape.anchored,
+        }
+    }
+}
+
+impl JsonTextSpan {
+    fn from_text_span(span: &TextSpan) -> Self {
+        Self {
+            text: span.text.clone(),
+            formatting: (!span.formatting.is_plain()).then(|| JsonFormatInfo::from_format_info(&span.formatting)),
+            link: span.link.as_ref().map(JsonLinkTarget::from_link),
+            revision: span.revision.as_ref().map(JsonRevision::from_revision),
+        }
+    }
+}
+
+impl JsonLinkTarget {
+    fn from_link(link: &Link) -> Self {
+        Self {
+            url: link.url.clone(),
+            anchor: link.anchor.clone(),
         }
     }
 }
@@ -168,6 +658,7 @@ impl JsonSectionType {
             SectionType::Footer => JsonSectionType::Footer,
             SectionType::Header => JsonSectionType::Header,
             SectionType::Hyperlink => JsonSectionType::Hyperlink,
+            SectionType::Shape => JsonSectionType::Shape,
         }
     }
 }
@@ -178,8 +669,16 @@ impl JsonFormatInfo {
             bold: format_info.bold,
             italic: format_info.italic,
             underline: format_info.underline,
+            strike: format_info.strike,
             font_size: format_info.font_size,
             font_family: format_info.font_family.clone(),
+            color: format_info.color.clone(),
+            highlight: format_info.highlight.clone(),
+            vertical_align: match format_info.vertical_align {
+                VerticalAlign::Baseline => None,
+                VerticalAlign::Superscript => Some("superscript".to_string()),
+                VerticalAlign::Subscript => Some("subscript".to_string()),
+            },
         }
     }
 }
@@ -203,6 +702,7 @@ mod tests {
             content: "Hello World".to_string(),
             level: None,
             formatting: None,
+            ..Default::default()
         };
 
         let json_section = JsonDocSection::from_doc_section(&section, false);
@@ -212,6 +712,67 @@ mod tests {
         assert!(json_string.contains("\"type\":\"paragraph\""));
     }
 
+    #[test]
+    fn test_serialize_table_section() {
+        use crate::parser::{TableCell, TableData, TableRow};
+
+        let mut table = TableData::new();
+        let mut header = TableRow::new(0);
+        header.add_cell(TableCell::new("Name".to_string()));
+        header.add_cell(TableCell::new("Qty".to_string()));
+        header.mark_as_header();
+        table.add_row(header);
+        let mut row = TableRow::new(1);
+        row.add_cell(TableCell::new("Bolt".to_string()));
+        row.add_cell(TableCell::empty());
+        table.add_row(row);
+        let section = DocSection {
+            section_type: SectionType::Table,
+            content: table.plain_text(),
+            level: None,
+            formatting: None,
+            table: Some(table),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(JsonDocSection::from_doc_section(&section, false)).unwrap();
+        let table = &json["table"];
+        assert_eq!(table["row_count"], 2);
+        assert_eq!(table["column_count"], 2);
+        assert_eq!(table["rows"][0]["is_header"], true);
+        assert_eq!(table["rows"][0]["cells"][0]["cell_type"], "header");
+        assert_eq!(table["rows"][1]["cells"][0]["content"], "Bolt");
+        assert_eq!(table["rows"][1]["cells"][1]["cell_type"], "empty");
+        assert!(table["rows"][1].get("is_header").is_none());
+    }
+
+    #[test]
+    fn test_serialize_nested_table() {
+        use crate::parser::table::CellBlock;
+        use crate::parser::{TableCell, TableData, TableRow};
+
+        let single_cell_table = |cell: TableCell| {
+            let mut table = TableData::new();
+            let mut row = TableRow::new(0);
+            row.add_cell(cell);
+            table.add_row(row);
+            table
+        };
+        let inner = single_cell_table(TableCell::new("inner".to_string()));
+        let outer = single_cell_table(TableCell::from_blocks(vec![
+            CellBlock::Paragraph { text: "before".to_string() },
+            CellBlock::Table { table: inner },
+        ]));
+
+        let json = serde_json::to_value(JsonTable::from_table(&outer)).unwrap();
+        let blocks = &json["rows"][0]["cells"][0]["blocks"];
+        assert_eq!(blocks[0]["type"], "paragraph");
+        assert_eq!(blocks[0]["text"], "before");
+        assert_eq!(blocks[1]["type"], "table");
+        assert_eq!(blocks[1]["table"]["rows"][0]["cells"][0]["content"], "inner");
+        assert!(blocks[1]["table"]["rows"][0]["cells"][0].get("blocks").is_none());
+    }
+
     #[test]
     fn test_serialize_heading_section() {
         let section = DocSection {
@@ -219,6 +780,7 @@ mod tests {
             content: "Chapter Title".to_string(),
             level: Some(2),
             formatting: None,
+            ..Default::default()
         };
 
         let json_section = JsonDocSection::from_doc_section(&section, false);
@@ -234,8 +796,9 @@ mod tests {
             bold: true,
             italic: false,
             underline: true,
-            font_size: Some(14),
+            font_size: Some(14.0),
             font_family: Some("Arial".to_string()),
+            ..Default::default()
         };
 
         let section = DocSection {
@@ -243,6 +806,7 @@ mod tests {
             content: "Formatted text".to_string(),
             level: None,
             formatting: Some(formatting),
+            ..Default::default()
         };
 
         let json_section = JsonDocSection::from_doc_section(&section, true);
@@ -255,6 +819,173 @@ mod tests {
         assert!(json_string.contains("\"font_family\":\"Arial\""));
     }
 
+    #[test]
+    fn test_serialize_spans() {
+        let section = DocSection {
+            section_type: SectionType::Paragraph,
+            content: "E = mc2".to_string(),
+            level: None,
+            formatting: None,
+            spans: vec![
+                TextSpan { text: "E = mc".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+                TextSpan {
+                    text: "2".to_string(),
+                    formatting: FormatInfo {
+                        vertical_align: VerticalAlign::Superscript,
+                        color: Some("#FF0000".to_string()),
+                        ..Default::default()
+                    },
+                    link: None,
+                    revision: None,
+                    equation: None,
+                },
+            ],
+            list: None,
+            bookmarks: Vec::new(),
+            notes: Vec::new(),
+            image: None,
+            table: None,
+            shape: None,
+        };
+
+        let json_string = serde_json::to_string(&JsonDocSection::from_doc_section(&section, true)).unwrap();
+        assert!(json_string.contains(r#"{"text":"E = mc"}"#));
+        assert!(json_string.contains(r#""vertical_align":"superscript""#));
+        assert!(json_string.contains(r##""color":"#FF0000""##));
+
+        // Spans are part of the formatting details
+        let json_string = serde_json::to_string(&JsonDocSection::from_doc_section(&section, false)).unwrap();
+        assert!(!json_string.contains("spans"));
+    }
+
+    #[test]
+    fn test_serialize_links() {
+        let link = Link { url: Some("https://example.com".to_string()), anchor: None };
+        let span = |text: &str, link: Option<&Link>| TextSpan {
+            text: text.to_string(),
+            formatting: FormatInfo::default(),
+            link: link.cloned(),
+            revision: None,
+            equation: None,
+        };
+        let section = DocSection {
+            section_type: SectionType::Paragraph,
+            content: "Visit our site today".to_string(),
+            level: None,
+            formatting: None,
+            spans: vec![span("Visit ", None), span("our ", Some(&link)), span("site", Some(&link)), span(" today", None)],
+            bookmarks: vec!["Contact".to_string()],
+            ..Default::default()
+        };
+
+        // Links are reported even without formatting details
+        let json_string = serde_json::to_string(&JsonDocSection::from_doc_section(&section, false)).unwrap();
+        assert!(json_string.contains(r#""links":[{"text":"our site","url":"https://example.com"}]"#));
+        assert!(json_string.contains(r#""bookmarks":["Contact"]"#));
+
+        let json_string = serde_json::to_string(&JsonDocSection::from_doc_section(&section, true)).unwrap();
+        assert!(json_string.contains(r#"{"text":"site","link":{"url":"https://example.com"}}"#));
+    }
+
+    #[test]
+    fn test_serialize_revisions() {
+        let inserted = Revision {
+            kind: RevisionKind::Insertion,
+            author: "Ann".to_string(),
+            date: Some("2024-01-02T00:00:00Z".to_string()),
+        };
+        let span = |text: &str, revision: Option<&Revision>| TextSpan {
+            text: text.to_string(),
+            formatting: FormatInfo::default(),
+            link: None,
+            revision: revision.cloned(),
+            equation: None,
+        };
+        let section = DocSection {
+            section_type: SectionType::Paragraph,
+            content: "Net 30 days".to_string(),
+            level: None,
+            formatting: None,
+            spans: vec![
+                span("Net ", None),
+                span("30", Some(&inserted)),
+                span(" ", Some(&inserted)),
+                span("days", None),
+            ],
+            ..Default::default()
+        };
+
+        let entries = JsonRevisionEntry::from_sections(&[section]);
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "section": 0,
+                "text": "30 ",
+                "kind": "insertion",
+                "author": "Ann",
+                "date": "2024-01-02T00:00:00Z"
+            }])
+        );
+    }
+
+    #[test]
+    fn test_serialize_notes_and_comments() {
+        let metadata = DocMetadata {
+            title: None,
+            author: None,
+            subject: None,
+            created: None,
+            modified: None,
+            word_count: 1,
+            paragraph_count: 1,
+            page_count: None,
+            character_count: 4,
+            keywords: None,
+            description: None,
+            category: None,
+            last_modified_by: None,
+            revision: None,
+            template: None,
+            application: None,
+            recorded_word_count: None,
+            custom_properties: Default::default(),
+        };
+        let section = DocSection {
+            section_type: SectionType::Paragraph,
+            content: "Text".to_string(),
+            level: None,
+            formatting: None,
+            notes: vec![NoteReference { kind: NoteKind::Footnote, id: 1, offset: 4 }],
+            ..Default::default()
+        };
+        let doc_data = DocData {
+            content: "Text".to_string(),
+            raw_text: "Text".to_string(),
+            metadata,
+            sections: vec![section],
+            footnotes: vec![Note { id: 1, content: "A note".to_string() }],
+            endnotes: Vec::new(),
+            comments: vec![Comment {
+                id: 0,
+                author: "Ann".to_string(),
+                date: None,
+                content: "Check".to_string(),
+                anchor_text: Some("Text".to_string()),
+                parent_id: None,
+            }],
+        };
+
+        let json = serde_json::to_value(JsonDocData::from_doc_data(&doc_data, false)).unwrap();
+        assert_eq!(json["sections"][0]["notes"][0]["kind"], "footnote");
+        assert_eq!(json["sections"][0]["notes"][0]["offset"], 4);
+        assert_eq!(json["footnotes"][0]["content"], "A note");
+        assert!(json.get("endnotes").is_none());
+        assert_eq!(json["comments"][0]["author"], "Ann");
+        assert_eq!(json["comments"][0]["anchor_text"], "Text");
+        assert!(json["comments"][0].get("date").is_none());
+    }
+
     #[test]
     fn test_serialize_metadata() {
         let metadata = DocMetadata {
@@ -267,6 +998,15 @@ mod tests {
             paragraph_count: 5,
             page_count: Some(2),
             character_count: 500,
+            keywords: None,
+            description: None,
+            category: None,
+            last_modified_by: None,
+            revision: None,
+            template: None,
+            application: None,
+            recorded_word_count: None,
+            custom_properties: Default::default(),
         };
 
         let json_metadata = JsonDocMetadata::from_doc_metadata(&metadata);
@@ -285,6 +1025,7 @@ mod tests {
             content: "Test".to_string(),
             level: None,
             formatting: None,
+            ..Default::default()
         };
 
         let output = JsonOutput::new(true, false);
@@ -301,6 +1042,9 @@ mod tests {
         let doc_data = DocData {
             content: "Test Document\n\nThis is a test paragraph.".to_string(),
             raw_text: "Test Document This is a test paragraph.".to_string(),
+            footnotes: Vec::new(),
+            endnotes: Vec::new(),
+            comments: Vec::new(),
             metadata: DocMetadata {
                 title: Some("Test Document".to_string()),
                 author: Some("Test Author".to_string()),
@@ -311,6 +1055,15 @@ mod tests {
                 paragraph_count: 2,
                 page_count: Some(1),
                 character_count: 42,
+                keywords: None,
+                description: None,
+                category: None,
+                last_modified_by: None,
+                revision: None,
+                template: None,
+                application: None,
+                recorded_word_count: None,
+                custom_properties: Default::default(),
             },
             sections: vec![
                 DocSection {
@@ -318,12 +1071,14 @@ mod tests {
                     content: "Test Document".to_string(),
                     level: Some(1),
                     formatting: None,
+                    ..Default::default()
                 },
                 DocSection {
                     section_type: SectionType::Paragraph,
                     content: "This is a test paragraph.".to_string(),
                     level: None,
                     formatting: None,
+                    ..Default::default()
                 },
             ],
         };
@@ -1,11 +1,13 @@
 use crate::error::Result;
-use crate::output::OutputWriter;
-use crate::parser::{DocData, DocSection, SectionType};
+use crate::output::{note_entries, render_spans, shape_label, OutputWriter, TableWriter, MARKDOWN_MARKUP};
+use crate::parser::{DocData, DocSection, ImageInfo, ListInfo, Outline, SectionType, TableOutputFormat};
+use std::collections::HashMap;
 use std::io::Write;
 
 pub struct MarkdownOutput {
     preserve_structure: bool,
     include_metadata: bool,
+    toc: bool,
 }
 
 impl MarkdownOutput {
@@ -13,8 +15,44 @@ impl MarkdownOutput {
         Self {
             preserve_structure,
             include_metadata,
+            toc: false,
         }
     }
+
+    /// 在正文前插入链接到各标题的目录
+    pub fn with_toc(mut self, toc: bool) -> Self {
+        self.toc = toc;
+        self
+    }
+
+    fn write_body<W: Write>(&self, sections: &[DocSection], anchors: &HashMap<usize, &str>, writer: &mut W) -> Result<()> {
+        let mut in_list = false;
+        for (index, section) in sections.iter().enumerate() {
+            // 列表前后用空行与其他内容分隔
+            let is_list = matches!(section.section_type, SectionType::List);
+            if is_list != in_list {
+                writeln!(writer)?;
+                in_list = is_list;
+            }
+
+            let mut markdown_content = convert_section_to_markdown(section);
+            // 目录链接的锚点放在标题文字之前
+            if let (SectionType::Heading(level), Some(anchor)) = (&section.section_type, anchors.get(&index)) {
+                markdown_content.insert_str(*level as usize + 1, &format!("<a id=\"{}\"></a>", anchor));
+            }
+            writeln!(writer, "{}", markdown_content)?;
+            
+            // 在某些类型的内容后添加额外的换行
+            match section.section_type {
+                SectionType::Heading(_) | SectionType::Table => {
+                    writeln!(writer)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl OutputWriter for MarkdownOutput {
@@ -25,7 +63,19 @@ impl OutputWriter for MarkdownOutput {
         }
 
         if self.preserve_structure {
-            self.write_sections(&data.sections, writer)?;
+            let outline = if self.toc { Outline::from_doc(data) } else { Outline::default() };
+            if !outline.is_empty() {
+                write_toc(writer, &outline)?;
+            }
+            self.write_body(&data.sections, &outline.anchors(), writer)?;
+            // 脚注定义，续行缩进以留在同一脚注内
+            let notes = note_entries(data);
+            if !notes.is_empty() {
+                writeln!(writer)?;
+            }
+            for (label, text) in notes {
+                writeln!(writer, "[^{}]: {}", label, text.replace('\n', "\n    "))?;
+            }
         } else {
             // 简单的文本到Markdown转换
             for line in data.raw_text.lines() {
@@ -39,21 +89,20 @@ impl OutputWriter for MarkdownOutput {
     }
 
     fn write_sections<W: Write>(&self, sections: &[DocSection], writer: &mut W) -> Result<()> {
-        for section in sections {
-            let markdown_content = convert_section_to_markdown(section);
-            writeln!(writer, "{}", markdown_content)?;
-            
-            // 在某些类型的内容后添加额外的换行
-            match section.section_type {
-                SectionType::Heading(_) | SectionType::Table => {
-                    writeln!(writer)?;
-                }
-                _ => {}
-            }
-        }
+        self.write_body(sections, &HashMap::new(), writer)
+    }
+}
 
-        Ok(())
+/// 目录：按层级缩进的链接列表，链接到标题前的锚点
+fn write_toc<W: Write>(writer: &mut W, outline: &Outline) -> Result<()> {
+    writeln!(writer, "## 目录")?;
+    writeln!(writer)?;
+    for (depth, entry) in outline.flatten() {
+        let title = entry.title.replace(['[', ']'], "");
+        writeln!(writer, "{}- [{} {}](#{})", "  ".repeat(depth), entry.number, title, entry.anchor)?;
     }
+    writeln!(writer)?;
+    Ok(())
 }
 
 fn write_metadata_as_frontmatter<W: Write>(writer: &mut W, metadata: &crate::parser::DocMetadata) -> Result<()> {
@@ -74,6 +123,27 @@ fn write_metadata_as_frontmatter<W: Write>(writer: &mut W, metadata: &crate::par
     if let Some(modified) = &metadata.modified {
         writeln!(writer, "modified: \"{}\"", modified)?;
     }
+    if let Some(last_modified_by) = &metadata.last_modified_by {
+        writeln!(writer, "last_modified_by: \"{}\"", escape_yaml_string(last_modified_by))?;
+    }
+    if let Some(keywords) = &metadata.keywords {
+        writeln!(writer, "keywords: \"{}\"", escape_yaml_string(keywords))?;
+    }
+    if let Some(category) = &metadata.category {
+        writeln!(writer, "category: \"{}\"", escape_yaml_string(category))?;
+    }
+    if let Some(description) = &metadata.description {
+        writeln!(writer, "description: \"{}\"", escape_yaml_string(description))?;
+    }
+    if let Some(revision) = metadata.revision {
+        writeln!(writer, "revision: {}", revision)?;
+    }
+    if let Some(template) = &metadata.template {
+        writeln!(writer, "template: \"{}\"", escape_yaml_string(template))?;
+    }
+    if let Some(application) = &metadata.application {
+        writeln!(writer, "application: \"{}\"", escape_yaml_string(application))?;
+    }
     
     writeln!(writer, "word_count: {}", metadata.word_count)?;
     writeln!(writer, "paragraph_count: {}", metadata.paragraph_count)?;
@@ -82,6 +152,15 @@ fn write_metadata_as_frontmatter<W: Write>(writer: &mut W, metadata: &crate::par
     if let Some(page_count) = metadata.page_count {
         writeln!(writer, "page_count: {}", page_count)?;
     }
+    if let Some(recorded_word_count) = metadata.recorded_word_count {
+        writeln!(writer, "recorded_word_count: {}", recorded_word_count)?;
+    }
+    if !metadata.custom_properties.is_empty() {
+        writeln!(writer, "custom_properties:")?;
+        for (name, value) in &metadata.custom_properties {
+            writeln!(writer, "  \"{}\": \"{}\"", escape_yaml_string(name), escape_yaml_string(value))?;
+        }
+    }
     
     writeln!(writer, "---")?;
     
@@ -91,17 +170,32 @@ fn write_metadata_as_frontmatter<W: Write>(writer: &mut W, metadata: &crate::par
 fn convert_section_to_markdown(section: &DocSection) -> String {
     match &section.section_type {
         SectionType::Heading(level) => {
-            format!("{} {}", "#".repeat(*level as usize), section.content)
+            // 标题本身通常为粗体，不再重复标记；编号标题保留编号
+            let number = match &section.list {
+                Some(list) if list.ordered && !list.label.is_empty() => format!("{} ", list.label),
+                _ => String::new(),
+            };
+            format!("{} {}{}", "#".repeat(*level as usize), number, inline_markdown(section, true))
         }
         SectionType::List => {
-            format!("- {}", section.content)
-        }
-        SectionType::Table => {
-            convert_table_to_markdown(&section.content)
-        }
-        SectionType::Image => {
-            format!("![图片]({})", section.content)
+            let (indent, marker) = match &section.list {
+                Some(list) => ("    ".repeat(list.level as usize), list_marker(list)),
+                None => (String::new(), "-".to_string()),
+            };
+            format!("{}{} {}", indent, marker, inline_markdown(section, false))
         }
+        SectionType::Table => match &section.table {
+            Some(table) => TableWriter::new(TableOutputFormat::Markdown)
+                .table_to_string(table)
+                .map(|markdown| markdown.trim_end().to_string())
+                .unwrap_or_default(),
+            None => convert_table_to_markdown(&section.content),
+        },
+        SectionType::Image => match &section.image {
+            Some(image) => image_markdown(image),
+            None => format!("![图片]({})", section.content),
+        },
+        SectionType::Hyperlink if !section.spans.is_empty() => inline_markdown(section, false),
         SectionType::Hyperlink => {
             // 假设内容格式为 "text|url"
             if let Some((text, url)) = section.content.split_once('|') {
@@ -113,17 +207,60 @@ fn convert_section_to_markdown(section: &DocSection) -> String {
         SectionType::Header => {
             format!("> **页眉**: {}", section.content)
         }
+        // 图形中的文字作为引用块，每段一行
+        SectionType::Shape => {
+            let lines: Vec<String> = section.content.lines().map(|line| format!("> {}", line)).collect();
+            format!("> **{}**\n>\n{}", shape_label(section), lines.join("\n>\n"))
+        }
         SectionType::Footer => {
             format!("> **页脚**: {}", section.content)
         }
-        SectionType::Paragraph => {
-            // 检查是否包含格式化信息
-            if let Some(formatting) = &section.formatting {
-                apply_markdown_formatting(&section.content, formatting)
-            } else {
-                section.content.clone()
-            }
-        }
+        SectionType::Paragraph => inline_markdown(section, false),
+    }
+}
+
+/// Markdown 列表标记。Markdown 只支持十进制编号，字母/罗马数字等编号改用计数值
+fn list_marker(list: &ListInfo) -> String {
+    if !list.ordered {
+        return "-".to_string();
+    }
+    let label = list.label.trim();
+    let is_decimal_marker = label
+        .strip_suffix('.')
+        .or_else(|| label.strip_suffix(')'))
+        .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    if is_decimal_marker {
+        label.to_string()
+    } else {
+        format!("{}.", list.number)
+    }
+}
+
+/// 段落文本：优先按文本片段渲染行内格式和链接，否则使用整体格式。
+/// 段落中的书签输出为 HTML 锚点，供内部链接跳转
+fn inline_markdown(section: &DocSection, skip_bold: bool) -> String {
+    let anchors: String = section
+        .bookmarks
+        .iter()
+        .map(|name| format!("<a id=\"{}\"></a>", name))
+        .collect();
+    let text = if !section.spans.is_empty() {
+        render_spans(&section.spans, &section.notes, &MARKDOWN_MARKUP, skip_bold)
+    } else if let Some(formatting) = section.formatting.as_ref().filter(|_| !skip_bold) {
+        apply_markdown_formatting(&section.content, formatting)
+    } else {
+        section.content.clone()
+    };
+    anchors + &text
+}
+
+/// 图片：替代文字作为 alt，标题（若与之不同）作为 Markdown 图片标题
+fn image_markdown(image: &ImageInfo) -> String {
+    let alt = image.description().unwrap_or("图片").replace(['[', ']'], "");
+    let source = image.source().replace(' ', "%20");
+    match image.title.as_deref().filter(|title| *title != alt) {
+        Some(title) => format!("![{}]({} \"{}\")", alt, source, title.replace('"', "'")),
+        None => format!("![{}]({})", alt, source),
     }
 }
 
@@ -179,7 +316,7 @@ fn escape_yaml_string(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::{DocMetadata, DocSection, SectionType, FormatInfo};
+    use crate::parser::{DocMetadata, DocSection, SectionType, FormatInfo, TextSpan, Link, Note, NoteKind, NoteReference};
 
     #[test]
     fn test_markdown_output_creation() {
@@ -194,6 +331,7 @@ mod tests {
             content: "Chapter 1".to_string(),
             level: Some(2),
             formatting: None,
+            ..Default::default()
         };
 
         let markdown = convert_section_to_markdown(&section);
@@ -207,12 +345,103 @@ mod tests {
             content: "First item".to_string(),
             level: None,
             formatting: None,
+            ..Default::default()
         };
 
         let markdown = convert_section_to_markdown(&section);
         assert_eq!(markdown, "- First item");
     }
 
+    #[test]
+    fn test_convert_image_to_markdown() {
+        let image = ImageInfo {
+            target: "word/media/image1.png".to_string(),
+            alt_text: Some("Revenue chart".to_string()),
+            title: Some("Q3 \"final\"".to_string()),
+            ..Default::default()
+        };
+        let mut section = DocSection {
+            section_type: SectionType::Image,
+            content: "Revenue chart".to_string(),
+            level: None,
+            formatting: None,
+            image: Some(image),
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_section_to_markdown(&section),
+            "![Revenue chart](word/media/image1.png \"Q3 'final'\")"
+        );
+
+        if let Some(image) = section.image.as_mut() {
+            image.path = Some("images/report image1.png".to_string());
+            image.title = None;
+        }
+        assert_eq!(convert_section_to_markdown(&section), "![Revenue chart](images/report%20image1.png)");
+    }
+
+    #[test]
+    fn test_convert_shape_to_markdown() {
+        use crate::parser::{ShapeInfo, ShapeKind};
+
+        let section = DocSection {
+            section_type: SectionType::Shape,
+            content: "Plan\nShip".to_string(),
+            level: None,
+            formatting: None,
+            shape: Some(ShapeInfo { kind: ShapeKind::SmartArt, name: None, anchored: false }),
+            ..Default::default()
+        };
+        assert_eq!(convert_section_to_markdown(&section), "> **SmartArt**\n>\n> Plan\n>\n> Ship");
+    }
+
+    #[test]
+    fn test_nested_list_markdown() {
+        let item = |text: &str, level: u8, ordered: bool, label: &str, number: u32| DocSection {
+            section_type: SectionType::List,
+            content: text.to_string(),
+            level: None,
+            formatting: None,
+            list: Some(ListInfo { level, ordered, label: label.to_string(), number }),
+            ..Default::default()
+        };
+        let sections = vec![
+            DocSection {
+                section_type: SectionType::Paragraph,
+                content: "Steps:".to_string(),
+                level: None,
+                formatting: None,
+                ..Default::default()
+            },
+            item("Prepare", 0, true, "1.", 1),
+            item("Check tools", 1, true, "a)", 1),
+            item("Check parts", 1, true, "b)", 2),
+            item("Note", 2, false, "•", 1),
+            item("Assemble", 0, true, "2)", 2),
+        ];
+
+        let mut buffer = Vec::new();
+        MarkdownOutput::new(true, false).write_sections(&sections, &mut buffer).unwrap();
+        let result = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            result,
+            "Steps:\n\n1. Prepare\n    1. Check tools\n    2. Check parts\n        - Note\n2) Assemble\n"
+        );
+    }
+
+    #[test]
+    fn test_numbered_heading() {
+        let section = DocSection {
+            section_type: SectionType::Heading(2),
+            content: "Scope".to_string(),
+            level: Some(2),
+            formatting: None,
+            list: Some(ListInfo { level: 1, ordered: true, label: "1.2".to_string(), number: 2 }),
+            ..Default::default()
+        };
+        assert_eq!(convert_section_to_markdown(&section), "## 1.2 Scope");
+    }
+
     #[test]
     fn test_apply_formatting() {
         let formatting = FormatInfo {
@@ -221,12 +450,91 @@ mod tests {
             underline: false,
             font_size: None,
             font_family: None,
+            ..Default::default()
         };
 
         let result = apply_markdown_formatting("Bold text", &formatting);
         assert_eq!(result, "**Bold text**");
     }
 
+    #[test]
+    fn test_inline_span_formatting() {
+        let bold = FormatInfo { bold: true, ..Default::default() };
+        let spans = vec![
+            TextSpan { text: "Release ".to_string(), formatting: bold.clone(), link: None, revision: None, equation: None },
+            TextSpan { text: "notes".to_string(), formatting: FormatInfo { italic: true, ..bold.clone() }, link: None, revision: None, equation: None },
+            TextSpan { text: " are ".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+            TextSpan { text: "out".to_string(), formatting: FormatInfo { strike: true, ..Default::default() }, link: None, revision: None, equation: None },
+        ];
+        let mut section = DocSection {
+            section_type: SectionType::Paragraph,
+            content: "Release notes are out".to_string(),
+            level: None,
+            formatting: None,
+            spans,
+            ..Default::default()
+        };
+        assert_eq!(convert_section_to_markdown(&section), "**Release** ***notes*** are ~~out~~");
+
+        // Heading styles are bold already
+        section.section_type = SectionType::Heading(2);
+        assert_eq!(convert_section_to_markdown(&section), "## Release *notes* are ~~out~~");
+    }
+
+    #[test]
+    fn test_links_and_bookmarks() {
+        let link = |url: Option<&str>, anchor: Option<&str>| Link {
+            url: url.map(str::to_string),
+            anchor: anchor.map(str::to_string),
+        };
+        let heading = DocSection {
+            section_type: SectionType::Heading(1),
+            content: "Intro".to_string(),
+            level: Some(1),
+            formatting: None,
+            spans: vec![TextSpan { text: "Intro".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None }],
+            bookmarks: vec!["Intro".to_string()],
+            ..Default::default()
+        };
+        let paragraph = DocSection {
+            section_type: SectionType::Paragraph,
+            content: "See the docs or the intro.".to_string(),
+            level: None,
+            formatting: None,
+            spans: vec![
+                TextSpan { text: "See ".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+                TextSpan {
+                    text: "the docs".to_string(),
+                    formatting: FormatInfo { underline: true, ..Default::default() },
+                    link: Some(link(Some("https://example.com/docs"), None)),
+                    revision: None,
+                    equation: None,
+                },
+                TextSpan { text: " or ".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+                TextSpan {
+                    text: "the intro".to_string(),
+                    formatting: FormatInfo::default(),
+                    link: Some(link(None, Some("Intro"))),
+                    revision: None,
+                    equation: None,
+                },
+                TextSpan { text: ".".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+            ],
+            list: None,
+            bookmarks: Vec::new(),
+            notes: Vec::new(),
+            image: None,
+            table: None,
+            shape: None,
+        };
+
+        assert_eq!(convert_section_to_markdown(&heading), "# <a id=\"Intro\"></a>Intro");
+        assert_eq!(
+            convert_section_to_markdown(&paragraph),
+            "See [the docs](https://example.com/docs) or [the intro](#Intro)."
+        );
+    }
+
     #[test]
     fn test_convert_table_to_markdown() {
         let table_content = "Header1\tHeader2\nRow1Col1\tRow1Col2\nRow2Col1\tRow2Col2";
@@ -237,6 +545,33 @@ mod tests {
         assert!(markdown.contains("| Row1Col1 | Row1Col2 |"));
     }
 
+    #[test]
+    fn test_convert_structured_table_to_markdown() {
+        use crate::parser::{TableCell, TableData, TableRow};
+
+        let mut table = TableData::new();
+        for (index, cells) in [["Option", "Values"], ["mode", "fast|slow"]].iter().enumerate() {
+            let mut row = TableRow::new(index);
+            for cell in cells {
+                row.add_cell(TableCell::new(cell.to_string()));
+            }
+            table.add_row(row);
+        }
+        let section = DocSection {
+            section_type: SectionType::Table,
+            content: table.plain_text(),
+            level: None,
+            formatting: None,
+            table: Some(table),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            convert_section_to_markdown(&section),
+            "| Option | Values |\n| --- | --- |\n| mode | fast\\|slow |"
+        );
+    }
+
     #[test]
     fn test_escape_yaml_string() {
         let text = "Title with \"quotes\" and\nnewlines";
@@ -249,6 +584,9 @@ mod tests {
         let doc_data = DocData {
             content: "Test Document\n\nThis is a test paragraph.".to_string(),
             raw_text: "Test Document This is a test paragraph.".to_string(),
+            footnotes: vec![Note { id: 1, content: "First line\nsecond line".to_string() }],
+            endnotes: Vec::new(),
+            comments: Vec::new(),
             metadata: DocMetadata {
                 title: Some("Test Document".to_string()),
                 author: Some("Test Author".to_string()),
@@ -259,6 +597,15 @@ mod tests {
                 paragraph_count: 2,
                 page_count: Some(1),
                 character_count: 42,
+                keywords: None,
+                description: None,
+                category: None,
+                last_modified_by: None,
+                revision: None,
+                template: None,
+                application: None,
+                recorded_word_count: None,
+                custom_properties: Default::default(),
             },
             sections: vec![
                 DocSection {
@@ -266,18 +613,29 @@ mod tests {
                     content: "Test Document".to_string(),
                     level: Some(1),
                     formatting: None,
+                    ..Default::default()
                 },
                 DocSection {
                     section_type: SectionType::Paragraph,
                     content: "This is a test paragraph.".to_string(),
                     level: None,
                     formatting: None,
+                    spans: vec![TextSpan {
+                        text: "This is a test paragraph.".to_string(),
+                        formatting: FormatInfo::default(),
+                        link: None,
+                        revision: None,
+                        equation: None,
+                    }],
+                    notes: vec![NoteReference { kind: NoteKind::Footnote, id: 1, offset: 25 }],
+                    ..Default::default()
                 },
                 DocSection {
                     section_type: SectionType::Table,
                     content: "Header1\tHeader2\nCell1\tCell2".to_string(),
                     level: None,
                     formatting: None,
+                    ..Default::default()
                 },
             ],
         };
@@ -299,6 +657,17 @@ mod tests {
         assert!(result.contains("This is a test paragraph."));
         assert!(result.contains("| Header1 | Header2 |"));
         assert!(result.contains("| Cell1 | Cell2 |"));
+
+        // 脚注引用与定义
+        assert!(result.contains("This is a test paragraph.[^1]"));
+        assert!(result.contains("[^1]: First line\n    second line"));
+
+        // 目录链接到标题前的锚点
+        let mut buffer = Vec::new();
+        MarkdownOutput::new(true, false).with_toc(true).write_doc_data(&doc_data, &mut buffer).unwrap();
+        let result = String::from_utf8(buffer).unwrap();
+        assert!(result.starts_with("## 目录\n\n- [1 Test Document](#test-document)\n\n"));
+        assert!(result.contains("# <a id=\"test-document\"></a>Test Document\n"));
     }
 
     #[test]
@@ -306,6 +675,9 @@ mod tests {
         let doc_data = DocData {
             content: "Simple content".to_string(),
             raw_text: "Simple content".to_string(),
+            footnotes: Vec::new(),
+            endnotes: Vec::new(),
+            comments: Vec::new(),
             metadata: DocMetadata {
                 title: None,
                 author: None,
@@ -316,6 +688,15 @@ mod tests {
                 paragraph_count: 1,
                 page_count: None,
                 character_count: 14,
+                keywords: None,
+                description: None,
+                category: None,
+                last_modified_by: None,
+                revision: None,
+                template: None,
+                application: None,
+                recorded_word_count: None,
+                custom_properties: Default::default(),
             },
             sections: vec![
                 DocSection {
@@ -323,6 +704,7 @@ mod tests {
                     content: "Simple content".to_string(),
                     level: None,
                     formatting: None,
+                    ..Default::default()
                 },
             ],
         };
@@ -349,12 +731,14 @@ mod tests {
                 content: "First item".to_string(),
                 level: None,
                 formatting: None,
+                ..Default::default()
             },
             DocSection {
                 section_type: SectionType::Hyperlink,
                 content: "Click here|https://example.com".to_string(),
                 level: None,
                 formatting: None,
+                ..Default::default()
             },
         ];
 
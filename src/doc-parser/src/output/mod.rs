@@ -1,14 +1,20 @@
 use crate::error::Result;
-use crate::parser::{DocData, DocSection};
+use crate::parser::{DocData, DocSection, Equation, NoteKind, NoteReference, Revision, RevisionKind, ShapeKind, TextSpan, VerticalAlign};
 use std::io::Write;
 
+pub mod chunk;
 pub mod text;
 pub mod markdown;
 pub mod json;
+pub mod html;
+pub mod table;
 
+pub use chunk::{Chunk, ChunkConfig, ChunkUnit, Chunker};
 pub use text::TextOutput;
 pub use markdown::MarkdownOutput;
 pub use json::JsonOutput;
+pub use html::HtmlOutput;
+pub use table::TableWriter;
 
 #[derive(Debug, Clone)]
 pub enum OutputFormat {
@@ -20,11 +26,19 @@ pub enum OutputFormat {
     Markdown {
         preserve_structure: bool,
         include_metadata: bool,
+        /// 在正文前插入目录
+        toc: bool,
     },
     Json {
         pretty: bool,
         include_formatting: bool,
     },
+    Html {
+        include_metadata: bool,
+        include_css: bool,
+        /// 在正文前插入目录
+        toc: bool,
+    },
 }
 
 impl OutputFormat {
@@ -56,6 +70,7 @@ impl OutputFormat {
         Self::Markdown {
             preserve_structure: true,
             include_metadata: false,
+            toc: false,
         }
     }
 
@@ -63,6 +78,7 @@ impl OutputFormat {
         Self::Markdown {
             preserve_structure: true,
             include_metadata: true,
+            toc: false,
         }
     }
 
@@ -79,6 +95,22 @@ impl OutputFormat {
             include_formatting: true,
         }
     }
+
+    pub fn html() -> Self {
+        Self::Html {
+            include_metadata: false,
+            include_css: true,
+            toc: false,
+        }
+    }
+
+    pub fn html_with_metadata() -> Self {
+        Self::Html {
+            include_metadata: true,
+            include_css: true,
+            toc: false,
+        }
+    }
 }
 
 pub trait OutputWriter {
@@ -104,14 +136,18 @@ impl OutputProcessor {
                 let text_output = TextOutput::new(*preserve_formatting, *include_metadata, *line_numbers);
                 text_output.write_doc_data(data, writer)
             }
-            OutputFormat::Markdown { preserve_structure, include_metadata } => {
-                let markdown_output = MarkdownOutput::new(*preserve_structure, *include_metadata);
+            OutputFormat::Markdown { preserve_structure, include_metadata, toc } => {
+                let markdown_output = MarkdownOutput::new(*preserve_structure, *include_metadata).with_toc(*toc);
                 markdown_output.write_doc_data(data, writer)
             }
             OutputFormat::Json { pretty, include_formatting } => {
                 let json_output = JsonOutput::new(*pretty, *include_formatting);
                 json_output.write_doc_data(data, writer)
             }
+            OutputFormat::Html { include_metadata, include_css, toc } => {
+                let html_output = HtmlOutput::new(*include_metadata, *include_css).with_toc(*toc);
+                html_output.write_doc_data(data, writer)
+            }
         }
     }
 
@@ -126,7 +162,7 @@ impl OutputProcessor {
                 let text_output = TextOutput::new(*preserve_formatting, false, *line_numbers);
                 text_output.write_sections(sections, writer)
             }
-            OutputFormat::Markdown { preserve_structure, include_metadata: _ } => {
+            OutputFormat::Markdown { preserve_structure, .. } => {
                 let markdown_output = MarkdownOutput::new(*preserve_structure, false);
                 markdown_output.write_sections(sections, writer)
             }
@@ -134,8 +170,317 @@ impl OutputProcessor {
                 let json_output = JsonOutput::new(*pretty, *include_formatting);
                 json_output.write_sections(sections, writer)
             }
+            OutputFormat::Html { include_css, .. } => {
+                let html_output = HtmlOutput::new(false, *include_css);
+                html_output.write_sections(sections, writer)
+            }
+        }
+    }
+}
+
+/// Opening/closing markers for each kind of inline formatting
+pub(crate) struct InlineMarkup {
+    pub bold: (&'static str, &'static str),
+    pub italic: (&'static str, &'static str),
+    pub underline: (&'static str, &'static str),
+    pub strike: (&'static str, &'static str),
+    pub highlight: (&'static str, &'static str),
+    pub superscript: (&'static str, &'static str),
+    pub subscript: (&'static str, &'static str),
+    /// Render linked text given the rendered text and the link target
+    pub link: fn(&str, &str) -> String,
+    /// Render a footnote, endnote or comment reference
+    pub note: fn(&NoteReference) -> String,
+    /// Render inserted or deleted text given the rendered text and its revision
+    pub revision: fn(&str, &Revision) -> String,
+    /// Escape document text before markup is added
+    pub escape: fn(&str) -> String,
+    /// Render an equation in place of its LaTeX text
+    pub math: fn(&Equation) -> String,
+}
+
+pub(crate) const MARKDOWN_MARKUP: InlineMarkup = InlineMarkup {
+    bold: ("**", "**"),
+    italic: ("*", "*"),
+    underline: ("<u>", "</u>"),
+    strike: ("~~", "~~"),
+    highlight: ("<mark>", "</mark>"),
+    superscript: ("<sup>", "</sup>"),
+    subscript: ("<sub>", "</sub>"),
+    link: markdown_link,
+    note: |note| format!("[^{}]", note_label(note.kind, note.id)),
+    revision: critic_markup,
+    escape: str::to_string,
+    math: |equation| {
+        if equation.display {
+            format!("$${}$$", equation.latex)
+        } else {
+            format!("${}$", equation.latex)
+        }
+    },
+};
+
+/// Lightweight markers for plain text output, the same ones table cells use
+pub(crate) const TEXT_MARKUP: InlineMarkup = InlineMarkup {
+    bold: ("**", "**"),
+    italic: ("*", "*"),
+    underline: ("__", "__"),
+    strike: ("~~", "~~"),
+    highlight: ("", ""),
+    superscript: ("^", "^"),
+    subscript: ("~", "~"),
+    link: |text, href| format!("{} ({})", text, href),
+    note: |note| format!("[{}]", note_label(note.kind, note.id)),
+    revision: |text, revision| match revision.kind {
+        RevisionKind::Insertion => format!("[+{}+] ({})", text, revision_attribution(revision)),
+        RevisionKind::Deletion => format!("[-{}-] ({})", text, revision_attribution(revision)),
+    },
+    escape: str::to_string,
+    math: |equation| equation.latex.clone(),
+};
+
+/// 图形段在文本和 Markdown 输出中的标签
+pub(crate) fn shape_label(section: &DocSection) -> &'static str {
+    match section.shape.as_ref().map(|shape| shape.kind) {
+        Some(ShapeKind::Canvas) => "绘图",
+        Some(ShapeKind::SmartArt) => "SmartArt",
+        Some(ShapeKind::TextBox) | None => "文本框",
+    }
+}
+
+/// Label of a note in rendered output; endnotes and comments are prefixed so they
+/// cannot collide with footnote numbers
+pub(crate) fn note_label(kind: NoteKind, id: usize) -> String {
+    match kind {
+        NoteKind::Footnote => id.to_string(),
+        NoteKind::Endnote => format!("e{}", id),
+        NoteKind::Comment => format!("c{}", id),
+    }
+}
+
+/// `[text](href)`; destinations with spaces or parentheses go in angle brackets
+fn markdown_link(text: &str, href: &str) -> String {
+    if href.contains([' ', '(', ')']) {
+        format!("[{}](<{}>)", text, href)
+    } else {
+        format!("[{}]({})", text, href)
+    }
+}
+
+/// CriticMarkup addition or deletion followed by a comment naming its author
+fn critic_markup(text: &str, revision: &Revision) -> String {
+    let (open, close) = match revision.kind {
+        RevisionKind::Insertion => ("{++", "++}"),
+        RevisionKind::Deletion => ("{--", "--}"),
+    };
+    format!("{}{}{}{{>>{}<<}}", open, text, close, revision_attribution(revision))
+}
+
+/// "author, date", or just the author when the revision is undated
+pub(crate) fn revision_attribution(revision: &Revision) -> String {
+    match &revision.date {
+        Some(date) => format!("{}, {}", revision.author, date),
+        None => revision.author.clone(),
+    }
+}
+
+/// Footnotes, endnotes and comments as (label, text) pairs in output order
+pub(crate) fn note_entries(data: &DocData) -> Vec<(String, String)> {
+    let notes = data
+        .footnotes
+        .iter()
+        .map(|note| (note_label(NoteKind::Footnote, note.id), note.content.clone()))
+        .chain(
+            data.endnotes
+                .iter()
+                .map(|note| (note_label(NoteKind::Endnote, note.id), note.content.clone())),
+        );
+    let comments = data.comments.iter().map(|comment| {
+        let by = match &comment.date {
+            Some(date) => format!("{} ({})", comment.author, date),
+            None => comment.author.clone(),
+        };
+        (note_label(NoteKind::Comment, comment.id), format!("{}: {}", by, comment.content))
+    });
+    notes.chain(comments).collect()
+}
+
+/// Lowercase slug of a title for file names and anchors: letters and digits are kept
+/// (including non-ASCII), everything else collapses into single hyphens
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Render formatted spans inline. Adjacent spans that only differ in formatting the
+/// markup cannot show (size, font, color) are joined first, and markers are kept
+/// tight around the text so surrounding whitespace does not break them. Consecutive
+/// spans with the same link become one link, and consecutive spans of the same
+/// revision one revision mark. Note references are inserted at their offsets,
+/// splitting spans where needed.
+pub(crate) fn render_spans(
+    spans: &[TextSpan],
+    notes: &[NoteReference],
+    markup: &InlineMarkup,
+    skip_bold: bool,
+) -> String {
+    let mut result = String::new();
+    let mut segment: Vec<TextSpan> = Vec::new();
+    let mut offset = 0;
+    let mut pending = notes.iter().peekable();
+
+    for span in spans {
+        if let Some(equation) = &span.equation {
+            while let Some(note) = pending.next_if(|note| note.offset <= offset) {
+                result.push_str(&render_revised(&segment, markup, skip_bold));
+                segment.clear();
+                result.push_str(&(markup.note)(note));
+            }
+            result.push_str(&render_revised(&segment, markup, skip_bold));
+            segment.clear();
+            result.push_str(&(markup.math)(equation));
+            offset += span.text.chars().count();
+            continue;
+        }
+        let mut text = span.text.as_str();
+        loop {
+            while let Some(note) = pending.next_if(|note| note.offset <= offset) {
+                result.push_str(&render_revised(&segment, markup, skip_bold));
+                segment.clear();
+                result.push_str(&(markup.note)(note));
+            }
+            let length = text.chars().count();
+            match pending.peek() {
+                Some(note) if note.offset < offset + length => {
+                    let split = text
+                        .char_indices()
+                        .nth(note.offset - offset)
+                        .map_or(text.len(), |(index, _)| index);
+                    segment.push(TextSpan { text: text[..split].to_string(), ..span.clone() });
+                    offset = note.offset;
+                    text = &text[split..];
+                }
+                _ => {
+                    segment.push(TextSpan { text: text.to_string(), ..span.clone() });
+                    offset += length;
+                    break;
+                }
+            }
+        }
+    }
+    result.push_str(&render_revised(&segment, markup, skip_bold));
+    pending.for_each(|note| result.push_str(&(markup.note)(note)));
+    result
+}
+
+/// Plain span text with only revision marks applied
+pub(crate) fn render_revisions(spans: &[TextSpan], markup: &InlineMarkup) -> String {
+    let mut result = String::new();
+    for run in spans.chunk_by(|a, b| a.revision == b.revision) {
+        let text = (markup.escape)(&run.iter().map(|span| span.text.as_str()).collect::<String>());
+        match &run[0].revision {
+            Some(revision) => result.push_str(&(markup.revision)(&text, revision)),
+            None => result.push_str(&text),
+        }
+    }
+    result
+}
+
+/// Whether a section contains shown revisions
+pub(crate) fn has_revisions(section: &DocSection) -> bool {
+    section.spans.iter().any(|span| span.revision.is_some())
+}
+
+fn render_revised(spans: &[TextSpan], markup: &InlineMarkup, skip_bold: bool) -> String {
+    let mut result = String::new();
+    for run in spans.chunk_by(|a, b| a.revision == b.revision) {
+        let text = render_linked(run, markup, skip_bold);
+        match &run[0].revision {
+            Some(revision) => result.push_str(&(markup.revision)(&text, revision)),
+            None => result.push_str(&text),
+        }
+    }
+    result
+}
+
+fn render_linked(spans: &[TextSpan], markup: &InlineMarkup, skip_bold: bool) -> String {
+    let mut result = String::new();
+    for run in spans.chunk_by(|a, b| a.link == b.link) {
+        let text = render_formatted(run, markup, skip_bold);
+        match &run[0].link {
+            Some(link) if !text.trim().is_empty() => {
+                let core = text.trim();
+                result.push_str(&text[..text.len() - text.trim_start().len()]);
+                result.push_str(&(markup.link)(core, &link.href()));
+                result.push_str(&text[text.trim_end().len()..]);
+            }
+            _ => result.push_str(&text),
         }
     }
+    result
+}
+
+fn render_formatted(spans: &[TextSpan], markup: &InlineMarkup, skip_bold: bool) -> String {
+    let mut groups: Vec<(Vec<(&str, &str)>, String)> = Vec::new();
+    for span in spans {
+        let markers = span_markers(span, markup, skip_bold);
+        match groups.last_mut() {
+            Some((last, text)) if *last == markers => text.push_str(&span.text),
+            _ => groups.push((markers, span.text.clone())),
+        }
+    }
+
+    let mut result = String::new();
+    for (markers, text) in groups {
+        let core = text.trim();
+        if core.is_empty() || markers.is_empty() {
+            result.push_str(&(markup.escape)(&text));
+            continue;
+        }
+        let leading = &text[..text.len() - text.trim_start().len()];
+        let trailing = &text[text.trim_end().len()..];
+        result.push_str(leading);
+        markers.iter().for_each(|(open, _)| result.push_str(open));
+        result.push_str(&(markup.escape)(core));
+        markers.iter().rev().for_each(|(_, close)| result.push_str(close));
+        result.push_str(trailing);
+    }
+    result
+}
+
+fn span_markers(span: &TextSpan, markup: &InlineMarkup, skip_bold: bool) -> Vec<(&'static str, &'static str)> {
+    let f = &span.formatting;
+    let mut markers = Vec::new();
+    if f.bold && !skip_bold {
+        markers.push(markup.bold);
+    }
+    if f.italic {
+        markers.push(markup.italic);
+    }
+    if f.strike {
+        markers.push(markup.strike);
+    }
+    // Hyperlink styles underline their text; the link itself already shows that
+    if f.underline && span.link.is_none() {
+        markers.push(markup.underline);
+    }
+    if f.highlight.is_some() {
+        markers.push(markup.highlight);
+    }
+    match f.vertical_align {
+        VerticalAlign::Superscript => markers.push(markup.superscript),
+        VerticalAlign::Subscript => markers.push(markup.subscript),
+        VerticalAlign::Baseline => {}
+    }
+    markers.retain(|(open, close)| !open.is_empty() || !close.is_empty());
+    markers
 }
 
 impl Default for OutputProcessor {
@@ -156,11 +501,155 @@ mod tests {
         let _markdown = OutputFormat::markdown();
         let _json = OutputFormat::json();
         let _json_pretty = OutputFormat::json_pretty();
+        let _html = OutputFormat::html();
+        let _html_meta = OutputFormat::html_with_metadata();
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Table 1: Quarterly Revenue (USD)"), "table-1-quarterly-revenue-usd");
+        assert_eq!(slugify("  Übersicht – 2024 "), "übersicht-2024");
+        assert_eq!(slugify("表 2 销售额"), "表-2-销售额");
+        assert_eq!(slugify("***"), "");
     }
 
     #[test]
     fn test_output_processor_creation() {
         let _processor = OutputProcessor::new();
-        let _processor_default = OutputProcessor::default();
+        let _processor_default: OutputProcessor = Default::default();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_render_spans() {
+        use crate::parser::FormatInfo;
+
+        let span = |text: &str, formatting: FormatInfo| TextSpan { text: text.to_string(), formatting, link: None, revision: None, equation: None };
+        let bold = FormatInfo { bold: true, ..Default::default() };
+        let spans = vec![
+            span("Say ", FormatInfo::default()),
+            span("hello ", bold.clone()),
+            // Differs only in color, which markdown cannot show
+            span("world", FormatInfo { color: Some("#FF0000".to_string()), ..bold.clone() }),
+            span(" x", FormatInfo::default()),
+            span("2", FormatInfo { vertical_align: VerticalAlign::Superscript, ..Default::default() }),
+            span(" ", FormatInfo { italic: true, ..Default::default() }),
+        ];
+
+        assert_eq!(render_spans(&spans, &[], &MARKDOWN_MARKUP, false), "Say **hello world** x<sup>2</sup> ");
+        assert_eq!(render_spans(&spans, &[], &TEXT_MARKUP, false), "Say **hello world** x^2^ ");
+        assert_eq!(render_spans(&spans, &[], &MARKDOWN_MARKUP, true), "Say hello world x<sup>2</sup> ");
+    }
+
+    #[test]
+    fn test_render_linked_spans() {
+        use crate::parser::{FormatInfo, Link};
+
+        let site = Link { url: Some("https://example.com/a b".to_string()), anchor: None };
+        let intro = Link { url: None, anchor: Some("Intro".to_string()) };
+        let linked = |text: &str, formatting: FormatInfo, link: &Link| TextSpan {
+            text: text.to_string(),
+            formatting,
+            link: Some(link.clone()),
+            revision: None,
+            equation: None,
+        };
+        let underlined = FormatInfo { underline: true, ..Default::default() };
+        let spans = vec![
+            TextSpan { text: "See ".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+            linked("the ", underlined.clone(), &site),
+            linked("site", FormatInfo { bold: true, ..underlined.clone() }, &site),
+            TextSpan { text: " and ".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+            linked("intro ", FormatInfo::default(), &intro),
+        ];
+
+        assert_eq!(
+            render_spans(&spans, &[], &MARKDOWN_MARKUP, false),
+            "See [the **site**](<https://example.com/a b>) and [intro](#Intro) "
+        );
+        assert_eq!(
+            render_spans(&spans, &[], &TEXT_MARKUP, true),
+            "See the site (https://example.com/a b) and intro (#Intro) "
+        );
+    }
+
+    #[test]
+    fn test_render_note_references() {
+        use crate::parser::FormatInfo;
+
+        let bold = FormatInfo { bold: true, ..Default::default() };
+        let spans = vec![
+            TextSpan { text: "Water boils".to_string(), formatting: bold, link: None, revision: None, equation: None },
+            TextSpan { text: " at 100 °C.".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+        ];
+        let note = |kind, id, offset| NoteReference { kind, id, offset };
+        let notes = vec![
+            note(NoteKind::Footnote, 1, 5),
+            note(NoteKind::Comment, 0, 11),
+            note(NoteKind::Endnote, 2, 22),
+        ];
+
+        assert_eq!(
+            render_spans(&spans, &notes, &MARKDOWN_MARKUP, false),
+            "**Water**[^1] **boils**[^c0] at 100 °C.[^e2]"
+        );
+        assert_eq!(render_spans(&spans, &notes, &TEXT_MARKUP, true), "Water[1] boils[c0] at 100 °C.[e2]");
+    }
+
+    #[test]
+    fn test_render_equations() {
+        use crate::parser::{Equation, FormatInfo};
+
+        let plain = |text: &str| TextSpan { text: text.to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None };
+        let equation = Equation {
+            latex: "x^{2}".to_string(),
+            mathml: "<math><msup><mi>x</mi><mn>2</mn></msup></math>".to_string(),
+            omml: String::new(),
+            display: false,
+        };
+        let spans = vec![
+            plain("Let "),
+            TextSpan { equation: Some(equation.clone()), ..plain("x^{2}") },
+            plain(" grow."),
+        ];
+        let notes = vec![NoteReference { kind: NoteKind::Footnote, id: 1, offset: 9 }];
+
+        assert_eq!(render_spans(&spans, &notes, &MARKDOWN_MARKUP, false), "Let $x^{2}$[^1] grow.");
+        assert_eq!(render_spans(&spans, &[], &TEXT_MARKUP, false), "Let x^{2} grow.");
+
+        let display = TextSpan { equation: Some(Equation { display: true, ..equation }), ..plain("x^{2}") };
+        assert_eq!(render_spans(&[display], &[], &MARKDOWN_MARKUP, false), "$$x^{2}$$");
+    }
+
+    #[test]
+    fn test_render_revisions() {
+        use crate::parser::FormatInfo;
+
+        let revision = |kind, date: Option<&str>| Revision {
+            kind,
+            author: "Ann".to_string(),
+            date: date.map(str::to_string),
+        };
+        let span = |text: &str, revision: Option<Revision>| TextSpan {
+            text: text.to_string(),
+            formatting: FormatInfo::default(),
+            link: None,
+            revision,
+            equation: None,
+        };
+        let spans = vec![
+            span("The fee is ", None),
+            span("$10", Some(revision(RevisionKind::Deletion, Some("2024-01-02T00:00:00Z")))),
+            span("$12", Some(revision(RevisionKind::Insertion, None))),
+            span(".", None),
+        ];
+
+        assert_eq!(
+            render_spans(&spans, &[], &MARKDOWN_MARKUP, false),
+            "The fee is {--$10--}{>>Ann, 2024-01-02T00:00:00Z<<}{++$12++}{>>Ann<<}."
+        );
+        assert_eq!(
+            render_spans(&spans, &[], &TEXT_MARKUP, false),
+            "The fee is [-$10-] (Ann, 2024-01-02T00:00:00Z)[+$12+] (Ann)."
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use crate::error::Result;
+use crate::parser::table::{CellBlock, CellType, TableCell, TableData, TableOutputFormat};
+use std::io::Write;
+
+/// 将单个表格写成独立的 CSV/TSV/Markdown/JSON/HTML/纯文本内容
+pub struct TableWriter {
+    format: TableOutputFormat,
+}
+
+impl TableWriter {
+    pub fn new(format: TableOutputFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn write_table<W: Write>(&self, table: &TableData, writer: &mut W) -> Result<()> {
+        match self.format {
+            TableOutputFormat::PlainText => write_plain_text(table, writer),
+            TableOutputFormat::CSV => write_delimited(table, writer, ','),
+            TableOutputFormat::TSV => write_delimited(table, writer, '\t'),
+            TableOutputFormat::Markdown => write_markdown(table, writer),
+            TableOutputFormat::JSON => {
+                writeln!(writer, "{}", serde_json::to_string_pretty(table)?)?;
+                Ok(())
+            }
+            TableOutputFormat::HTML => write_html(table, writer),
+        }
+    }
+
+    /// 表格序列化为字符串
+    pub fn table_to_string(&self, table: &TableData) -> Result<String> {
+        let mut buffer = Vec::new();
+        self.write_table(table, &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+}
+
+/// 按列对齐的单元格文本；行补齐到相同列数，单元格内换行改为空格
+fn grid(table: &TableData) -> Vec<Vec<String>> {
+    let columns = table.rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let mut cells: Vec<String> = row.cells.iter().map(|cell| cell.content.clone()).collect();
+            cells.resize(columns, String::new());
+            cells
+        })
+        .collect()
+}
+
+fn write_plain_text<W: Write>(table: &TableData, writer: &mut W) -> Result<()> {
+    let rows: Vec<Vec<String>> = grid(table)
+        .into_iter()
+        .map(|row| row.into_iter().map(|cell| single_line(&cell)).collect())
+        .collect();
+    let columns = rows.first().map_or(0, Vec::len);
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| rows.iter().map(|row| row[col].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    if let Some(title) = &table.title {
+        writeln!(writer, "{}", title)?;
+    }
+    for (index, row) in rows.iter().enumerate() {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.chars().count())))
+            .collect();
+        writeln!(writer, "{}", line.join("  ").trim_end())?;
+        if table.rows[index].is_header && !table.rows.get(index + 1).is_some_and(|next| next.is_header) {
+            let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+            writeln!(writer, "{}", rule.join("  "))?;
+        }
+    }
+    Ok(())
+}
+
+/// CSV 按 RFC 4180 加引号；TSV 没有转义机制，单元格内的制表符和换行替换为空格
+fn write_delimited<W: Write>(table: &TableData, writer: &mut W, delimiter: char) -> Result<()> {
+    for row in grid(table) {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|cell| {
+                if delimiter == '\t' {
+                    single_line(&cell.replace('\t', " "))
+                } else if cell.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", cell.replace('"', "\"\""))
+                } else {
+                    cell.clone()
+                }
+            })
+            .collect();
+        write!(writer, "{}\r\n", fields.join(&delimiter.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Markdown 表格必须有表头，没有表头行时以第一行充当
+fn write_markdown<W: Write>(table: &TableData, writer: &mut W) -> Result<()> {
+    let rows = grid(table);
+    let Some(columns) = rows.first().map(Vec::len).filter(|columns| *columns > 0) else {
+        return Ok(());
+    };
+    let markdown_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| escape_markdown(cell).replace('\n', "<br>"))
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    if let Some(title) = &table.title {
+        writeln!(writer, "**{}**\n", escape_markdown(title))?;
+    }
+    let header_rows = table.rows.iter().take_while(|row| row.is_header).count().max(1);
+    // 多行表头合并为一行
+    let header: Vec<String> = (0..columns)
+        .map(|col| {
+            let parts: Vec<&str> = rows[..header_rows]
+                .iter()
+                .map(|row| row[col].as_str())
+                .filter(|cell| !cell.is_empty())
+                .collect();
+            parts.join(" ")
+        })
+        .collect();
+    writeln!(writer, "{}", markdown_row(&header))?;
+    writeln!(writer, "|{}", " --- |".repeat(columns))?;
+    for row in &rows[header_rows..] {
+        writeln!(writer, "{}", markdown_row(row))?;
+    }
+    Ok(())
+}
+
+fn write_html<W: Write>(table: &TableData, writer: &mut W) -> Result<()> {
+    writeln!(writer, "<table>")?;
+    if let Some(title) = &table.title {
+        writeln!(writer, "  <caption>{}</caption>", escape_html(title))?;
+    }
+    let header_rows = table.rows.iter().take_while(|row| row.is_header).count();
+    for (index, row) in table.rows.iter().enumerate() {
+        if index == 0 && header_rows > 0 {
+            writeln!(writer, "  <thead>")?;
+        } else if index == header_rows {
+            writeln!(writer, "  <tbody>")?;
+        }
+        let tag = if row.is_header { "th" } else { "td" };
+        write!(writer, "    <tr>")?;
+        for cell in row.cells.iter().filter(|cell| !is_covered(cell)) {
+            write!(writer, "<{}{}>{}</{}>", tag, span_attributes(cell), cell_html(cell)?, tag)?;
+        }
+        writeln!(writer, "</tr>")?;
+        if index + 1 == header_rows {
+            writeln!(writer, "  </thead>")?;
+        }
+    }
+    if table.rows.len() > header_rows {
+        writeln!(writer, "  </tbody>")?;
+    }
+    writeln!(writer, "</table>")?;
+    Ok(())
+}
+
+/// 单元格内容：单段落直接输出文本，否则按块输出段落、列表和嵌套表格
+fn cell_html(cell: &TableCell) -> Result<String> {
+    if cell.blocks.is_empty() {
+        return Ok(escape_html(&cell.content).replace('\n', "<br>"));
+    }
+    let mut html = String::new();
+    // 当前打开的列表标签，按层级嵌套；每层的最后一个 <li> 保持打开，以便容纳子列表
+    let mut lists: Vec<&str> = Vec::new();
+    for block in &cell.blocks {
+        let (depth, tag) = match block {
+            CellBlock::ListItem { level, ordered, .. } => (*level as usize + 1, if *ordered { "ol" } else { "ul" }),
+            _ => (0, ""),
+        };
+        while lists.len() > depth {
+            html.push_str(&format!("</li></{}>", lists.pop().unwrap_or("ul")));
+        }
+        if depth > 0 && lists.len() == depth {
+            if lists.last() == Some(&tag) {
+                html.push_str("</li>");
+            } else {
+                html.push_str(&format!("</li></{}>", lists.pop().unwrap_or("ul")));
+            }
+        }
+        match block {
+            CellBlock::Paragraph { text } => html.push_str(&format!("<p>{}</p>", escape_html(text))),
+            CellBlock::ListItem { text, .. } => {
+                while lists.len() < depth {
+                    html.push_str(&format!("<{}>", tag));
+                    lists.push(tag);
+                }
+                html.push_str(&format!("<li>{}", escape_html(text)));
+            }
+            CellBlock::Table { table } => {
+                let mut nested = Vec::new();
+                write_html(table, &mut nested)?;
+                html.push('\n');
+                html.push_str(&String::from_utf8_lossy(&nested));
+            }
+        }
+    }
+    while let Some(tag) = lists.pop() {
+        html.push_str(&format!("</li></{}>", tag));
+    }
+    Ok(html)
+}
+
+/// 被合并单元格覆盖的位置（不是合并区域左上角的单元格）
+fn is_covered(cell: &TableCell) -> bool {
+    matches!(cell.cell_type, CellType::Merged) && cell.colspan.is_none() && cell.rowspan.is_none()
+}
+
+fn span_attributes(cell: &TableCell) -> String {
+    let mut attributes = String::new();
+    if let Some(colspan) = cell.colspan.filter(|span| *span > 1) {
+        attributes.push_str(&format!(" colspan=\"{}\"", colspan));
+    }
+    if let Some(rowspan) = cell.rowspan.filter(|span| *span > 1) {
+        attributes.push_str(&format!(" rowspan=\"{}\"", rowspan));
+    }
+    attributes
+}
+
+fn single_line(text: &str) -> String {
+    text.split(['\n', '\r']).filter(|line| !line.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+/// Markdown 行内文本转义：有特殊含义的标点前加反斜杠
+pub(crate) fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// HTML 文本转义
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::table::TableRow;
+
+    fn sample_table() -> TableData {
+        let mut table = TableData::new();
+        for (index, cells) in [["Name", "Note"], ["Widget, large", "Says \"hi\""], ["Gadget", "Line 1\nLine 2"]]
+            .iter()
+            .enumerate()
+        {
+            let mut row = TableRow::new(index);
+            for cell in cells {
+                row.add_cell(TableCell::new(cell.to_string()));
+            }
+            if index == 0 {
+                row.mark_as_header();
+            }
+            table.add_row(row);
+        }
+        table.title = Some("Inventory".to_string());
+        table
+    }
+
+    fn render(format: TableOutputFormat) -> String {
+        TableWriter::new(format).table_to_string(&sample_table()).unwrap()
+    }
+
+    #[test]
+    fn test_csv_and_tsv_escaping() {
+        assert_eq!(
+            render(TableOutputFormat::CSV),
+            "Name,Note\r\n\"Widget, large\",\"Says \"\"hi\"\"\"\r\nGadget,\"Line 1\nLine 2\"\r\n"
+        );
+        assert_eq!(
+            render(TableOutputFormat::TSV),
+            "Name\tNote\r\nWidget, large\tSays \"hi\"\r\nGadget\tLine 1 Line 2\r\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_and_plain_text() {
+        assert_eq!(
+            render(TableOutputFormat::Markdown),
+            "**Inventory**\n\n| Name | Note |\n| --- | --- |\n| Widget, large | Says \"hi\" |\n| Gadget | Line 1<br>Line 2 |\n"
+        );
+        assert_eq!(
+            render(TableOutputFormat::PlainText),
+            "Inventory\nName           Note\n-------------  -------------\nWidget, large  Says \"hi\"\nGadget         Line 1 Line 2\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escaping() {
+        let mut table = sample_table();
+        table.title = Some("Q*1* [draft]".to_string());
+        table.rows[1].cells[1].content = "snake_case <b>bold</b> `code`".to_string();
+
+        let markdown = TableWriter::new(TableOutputFormat::Markdown).table_to_string(&table).unwrap();
+        assert!(markdown.starts_with("**Q\\*1\\* \\[draft\\]**\n\n"));
+        assert!(markdown.contains("| Widget, large | snake\\_case \\<b\\>bold\\</b\\> \\`code\\` |\n"));
+    }
+
+    #[test]
+    fn test_html_table() {
+        let html = render(TableOutputFormat::HTML);
+        assert!(html.starts_with("<table>\n  <caption>Inventory</caption>\n  <thead>\n    <tr><th>Name</th><th>Note</th></tr>\n  </thead>\n  <tbody>\n"));
+        assert!(html.contains("<td>Says &quot;hi&quot;</td>"));
+        assert!(html.contains("<td>Line 1<br>Line 2</td>"));
+        assert!(html.ends_with("  </tbody>\n</table>\n"));
+    }
+
+    #[test]
+    fn test_html_nested_blocks() {
+        let mut inner = TableData::new();
+        let mut inner_row = TableRow::new(0);
+        inner_row.add_cell(TableCell::new("x < 1".to_string()));
+        inner_row.add_cell(TableCell::new("y".to_string()));
+        inner.add_row(inner_row);
+
+        let cell = TableCell::from_blocks(vec![
+            CellBlock::Paragraph { text: "Steps".to_string() },
+            CellBlock::ListItem { level: 0, ordered: true, label: "1.".to_string(), text: "Mix".to_string() },
+            CellBlock::ListItem { level: 1, ordered: false, label: "•".to_string(), text: "Slowly".to_string() },
+            CellBlock::ListItem { level: 0, ordered: true, label: "2.".to_string(), text: "Bake".to_string() },
+            CellBlock::Table { table: inner },
+        ]);
+        assert_eq!(cell.content, "Steps\n1. Mix\n  • Slowly\n2. Bake\nx < 1 | y");
+
+        let mut table = TableData::new();
+        let mut row = TableRow::new(0);
+        row.add_cell(cell);
+        table.add_row(row);
+        let html = TableWriter::new(TableOutputFormat::HTML).table_to_string(&table).unwrap();
+        assert!(html.contains("<td><p>Steps</p><ol><li>Mix<ul><li>Slowly</li></ul></li><li>Bake</li></ol>\n<table>\n  <tbody>\n    <tr><td>x &lt; 1</td><td>y</td></tr>\n  </tbody>\n</table>\n</td>"));
+
+        let markdown = TableWriter::new(TableOutputFormat::Markdown).table_to_string(&table).unwrap();
+        assert!(markdown.starts_with("| Steps<br>1. Mix<br>  • Slowly<br>2. Bake<br>x \\< 1 \\| y |\n"));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = render(TableOutputFormat::JSON);
+        let table: TableData = serde_json::from_str(&json).unwrap();
+        assert_eq!(table.title.as_deref(), Some("Inventory"));
+        assert_eq!(table.rows[1].cells[0].content, "Widget, large");
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::Result;
-use crate::output::OutputWriter;
-use crate::parser::{DocData, DocSection, SectionType};
+use crate::output::{has_revisions, note_entries, render_revisions, render_spans, shape_label, OutputWriter, TableWriter, TEXT_MARKUP};
+use crate::parser::{DocData, DocSection, SectionType, TableOutputFormat};
 use std::io::Write;
 
 pub struct TextOutput {
@@ -28,6 +28,16 @@ impl OutputWriter for TextOutput {
 
         if self.preserve_formatting {
             self.write_sections(&data.sections, writer)?;
+            let notes = note_entries(data);
+            if !notes.is_empty() {
+                writeln!(writer, "\n=== 注释 ===")?;
+            }
+            for (label, text) in notes {
+                writeln!(writer, "[{}] {}", label, text)?;
+            }
+        } else if data.sections.iter().any(has_revisions) {
+            // 显示修订时逐段输出，以便标出插入和删除
+            self.write_sections(&data.sections, writer)?;
         } else {
             if self.line_numbers {
                 write_text_with_line_numbers(writer, &data.raw_text)?;
@@ -45,6 +55,10 @@ impl OutputWriter for TextOutput {
         for section in sections {
             let formatted_content = if self.preserve_formatting {
                 format_section_content(section)
+            } else if has_revisions(section) {
+                render_revisions(&section.spans, &TEXT_MARKUP)
+            } else if let Some(grid) = table_grid(section) {
+                grid
             } else {
                 section.content.clone()
             };
@@ -81,6 +95,30 @@ fn write_metadata<W: Write>(writer: &mut W, metadata: &crate::parser::DocMetadat
     if let Some(modified) = &metadata.modified {
         writeln!(writer, "修改时间: {}", modified)?;
     }
+    if let Some(last_modified_by) = &metadata.last_modified_by {
+        writeln!(writer, "最后修改者: {}", last_modified_by)?;
+    }
+    if let Some(keywords) = &metadata.keywords {
+        writeln!(writer, "关键词: {}", keywords)?;
+    }
+    if let Some(category) = &metadata.category {
+        writeln!(writer, "类别: {}", category)?;
+    }
+    if let Some(description) = &metadata.description {
+        writeln!(writer, "备注: {}", description)?;
+    }
+    if let Some(revision) = metadata.revision {
+        writeln!(writer, "修订次数: {}", revision)?;
+    }
+    if let Some(template) = &metadata.template {
+        writeln!(writer, "模板: {}", template)?;
+    }
+    if let Some(application) = &metadata.application {
+        writeln!(writer, "应用程序: {}", application)?;
+    }
+    for (name, value) in &metadata.custom_properties {
+        writeln!(writer, "{}: {}", name, value)?;
+    }
     
     writeln!(writer, "段落数: {}", metadata.paragraph_count)?;
     writeln!(writer, "字数: {}", metadata.word_count)?;
@@ -89,6 +127,9 @@ fn write_metadata<W: Write>(writer: &mut W, metadata: &crate::parser::DocMetadat
     if let Some(page_count) = metadata.page_count {
         writeln!(writer, "页数: {}", page_count)?;
     }
+    if let Some(recorded_word_count) = metadata.recorded_word_count {
+        writeln!(writer, "Word 统计字数: {}", recorded_word_count)?;
+    }
     
     writeln!(writer, "\n=== 文档内容 ===")?;
     
@@ -102,37 +143,69 @@ fn write_text_with_line_numbers<W: Write>(writer: &mut W, text: &str) -> Result<
     Ok(())
 }
 
+/// 表格按列对齐输出
+fn table_grid(section: &DocSection) -> Option<String> {
+    let table = section.table.as_ref()?;
+    let grid = TableWriter::new(TableOutputFormat::PlainText).table_to_string(table).ok()?;
+    Some(grid.trim_end().to_string())
+}
+
 fn format_section_content(section: &DocSection) -> String {
     match &section.section_type {
         SectionType::Heading(level) => {
-            format!("{} {}", "#".repeat(*level as usize), section.content)
-        }
-        SectionType::List => {
-            format!("• {}", section.content)
-        }
-        SectionType::Table => {
-            format!("[表格] {}", section.content)
-        }
-        SectionType::Image => {
-            format!("[图片] {}", section.content)
+            let number = match &section.list {
+                Some(list) if list.ordered && !list.label.is_empty() => format!("{} ", list.label),
+                _ => String::new(),
+            };
+            format!("{} {}{}", "#".repeat(*level as usize), number, inline_text(section, true))
         }
+        SectionType::List => match &section.list {
+            Some(list) => {
+                let label = if list.label.trim().is_empty() { "•" } else { list.label.trim() };
+                format!("{}{} {}", "  ".repeat(list.level as usize), label, inline_text(section, false))
+            }
+            None => format!("• {}", inline_text(section, false)),
+        },
+        SectionType::Table => match table_grid(section) {
+            Some(grid) => format!("[表格]\n{}", grid),
+            None => format!("[表格] {}", section.content),
+        },
+        SectionType::Image => match &section.image {
+            Some(image) => match image.description() {
+                Some(description) => format!("[图片] {} ({})", description, image.source()),
+                None => format!("[图片] {}", image.source()),
+            },
+            None => format!("[图片] {}", section.content),
+        },
         SectionType::Hyperlink => {
             format!("[链接] {}", section.content)
         }
+        SectionType::Shape => {
+            format!("[{}] {}", shape_label(section), section.content)
+        }
         SectionType::Header => {
             format!("[页眉] {}", section.content)
         }
         SectionType::Footer => {
             format!("[页脚] {}", section.content)
         }
-        SectionType::Paragraph => section.content.clone(),
+        SectionType::Paragraph => inline_text(section, false),
+    }
+}
+
+/// 带行内格式标记的段落文本
+fn inline_text(section: &DocSection, skip_bold: bool) -> String {
+    if section.spans.is_empty() {
+        section.content.clone()
+    } else {
+        render_spans(&section.spans, &section.notes, &TEXT_MARKUP, skip_bold)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::{DocMetadata, DocSection, SectionType};
+    use crate::parser::{DocSection, SectionType};
 
     #[test]
     fn test_text_output_creation() {
@@ -150,12 +223,14 @@ mod tests {
                 content: "Hello World".to_string(),
                 level: None,
                 formatting: None,
+                ..Default::default()
             },
             DocSection {
                 section_type: SectionType::Heading(1),
                 content: "Title".to_string(),
                 level: Some(1),
                 formatting: None,
+                ..Default::default()
             },
         ];
 
@@ -168,6 +243,88 @@ mod tests {
         assert!(result.contains("# Title"));
     }
 
+    #[test]
+    fn test_preserve_formatting_marks_spans() {
+        use crate::parser::{FormatInfo, TextSpan};
+
+        let sections = vec![DocSection {
+            section_type: SectionType::Paragraph,
+            content: "Use caution".to_string(),
+            level: None,
+            formatting: None,
+            spans: vec![
+                TextSpan { text: "Use ".to_string(), formatting: FormatInfo::default(), link: None, revision: None, equation: None },
+                TextSpan { text: "caution".to_string(), formatting: FormatInfo { bold: true, underline: true, ..Default::default() }, link: None, revision: None, equation: None },
+            ],
+            list: None,
+            bookmarks: Vec::new(),
+            notes: Vec::new(),
+            image: None,
+            table: None,
+            shape: None,
+        }];
+
+        let mut buffer = Vec::new();
+        TextOutput::new(true, false, false).write_sections(&sections, &mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Use **__caution__**\n");
+
+        let mut buffer = Vec::new();
+        TextOutput::new(false, false, false).write_sections(&sections, &mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Use caution\n");
+    }
+
+    #[test]
+    fn test_list_labels() {
+        use crate::parser::ListInfo;
+
+        let item = |text: &str, level: u8, label: &str| DocSection {
+            section_type: SectionType::List,
+            content: text.to_string(),
+            level: None,
+            formatting: None,
+            list: Some(ListInfo { level, ordered: label != "•", label: label.to_string(), number: 1 }),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        TextOutput::new(true, false, false)
+            .write_sections(&[item("First", 0, "1."), item("Detail", 1, "iv."), item("Point", 2, "•")], &mut buffer)
+            .unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "1. First\n  iv. Detail\n    • Point\n");
+    }
+
+    #[test]
+    fn test_table_grid() {
+        use crate::parser::{TableCell, TableData, TableRow};
+
+        let mut table = TableData::new();
+        for (index, cells) in [["Item", "Qty"], ["Bolt", "12"], ["Washer", ""]].iter().enumerate() {
+            let mut row = TableRow::new(index);
+            for cell in cells {
+                row.add_cell(TableCell::new(cell.to_string()));
+            }
+            if index == 0 {
+                row.mark_as_header();
+            }
+            table.add_row(row);
+        }
+        let sections = vec![DocSection {
+            section_type: SectionType::Table,
+            content: table.plain_text(),
+            level: None,
+            formatting: None,
+            table: Some(table),
+            ..Default::default()
+        }];
+
+        let mut buffer = Vec::new();
+        TextOutput::new(true, false, false).write_sections(&sections, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "[表格]\nItem    Qty\n------  ---\nBolt    12\nWasher\n"
+        );
+    }
+
     #[test]
     fn test_write_with_line_numbers() {
         let sections = vec![
@@ -176,12 +333,14 @@ mod tests {
                 content: "Line 1".to_string(),
                 level: None,
                 formatting: None,
+                ..Default::default()
             },
             DocSection {
                 section_type: SectionType::Paragraph,
                 content: "Line 2".to_string(),
                 level: None,
                 formatting: None,
+                ..Default::default()
             },
         ];
 
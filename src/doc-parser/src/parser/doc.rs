@@ -1,72 +1,31 @@
 use crate::error::{DocParserError, Result};
-use crate::parser::DocData;
+use crate::parser::{DocData, ParseOptions};
 use std::path::Path;
 
-/// 解析老式DOC文件
+#[cfg(feature = "legacy-doc")]
+use crate::parser::{
+    formatting::{common_formatting, push_span},
+    inline::parse_hyperlink_instruction,
+    summary_info::SummaryInformation,
+    table::{TableCell, TableData, TableRow},
+    text_extractor::TextCleaner,
+    word97::{Fib, Unit, WordDocument},
+    DocMetadata, DocSection, FormatInfo, Link, Note, NoteKind, NoteReference, SectionType, TextSpan,
+};
+#[cfg(feature = "legacy-doc")]
+use std::collections::HashMap;
+
+/// 解析老式DOC文件（Word 97-2003 二进制格式）
 /// 注意: 此功能需要 "legacy-doc" feature 启用
-pub fn parse_doc<P: AsRef<Path>>(file_path: P) -> Result<DocData> {
-    let _file_path = file_path.as_ref();
-    
+pub fn parse_doc<P: AsRef<Path>>(file_path: P, options: &ParseOptions) -> Result<DocData> {
     #[cfg(feature = "legacy-doc")]
     {
-        // 使用dotext库提取DOC文件文本
-        use dotext::Docx;
-        use crate::parser::{DocMetadata, DocSection, SectionType};
-        
-        let mut file = Docx::open(file_path)
-            .map_err(|e| DocParserError::DocParsing {
-                file: file_path.as_ref().display().to_string(),
-                details: format!("Failed to open DOC file: {:?}", e),
-            })?;
-        
-        let mut content = String::new();
-        file.read_to_string(&mut content)
-            .map_err(|e| DocParserError::TextExtraction {
-                file: file_path.as_ref().display().to_string(),
-                details: format!("Failed to extract text: {:?}", e),
-            })?;
-        
-        // 清理文本
-        let cleaned_content = crate::parser::text_extractor::TextCleaner::clean_text(&content);
-        
-        // 简单的段落分割
-        let sections: Vec<DocSection> = cleaned_content
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(|line| DocSection {
-                section_type: SectionType::Paragraph,
-                content: line.to_string(),
-                level: None,
-                formatting: None,
-            })
-            .collect();
-        
-        let word_count = cleaned_content.split_whitespace().count();
-        let character_count = cleaned_content.chars().count();
-        let paragraph_count = sections.len();
-        
-        let metadata = DocMetadata {
-            title: None,
-            author: None,
-            subject: None,
-            created: None,
-            modified: None,
-            word_count,
-            paragraph_count,
-            page_count: None,
-            character_count,
-        };
-        
-        Ok(DocData {
-            content: content.clone(),
-            metadata,
-            sections,
-            raw_text: cleaned_content,
-        })
+        parse_word_document(file_path.as_ref(), options)
     }
-    
+
     #[cfg(not(feature = "legacy-doc"))]
     {
+        let _ = options;
         Err(DocParserError::UnsupportedFormat {
             format: "doc".to_string(),
             file: file_path.as_ref().display().to_string(),
@@ -74,6 +33,385 @@ pub fn parse_doc<P: AsRef<Path>>(file_path: P) -> Result<DocData> {
     }
 }
 
+#[cfg(feature = "legacy-doc")]
+fn parse_word_document(file_path: &Path, options: &ParseOptions) -> Result<DocData> {
+    let file = file_path.display().to_string();
+    let error = |details: String| DocParserError::DocParsing {
+        file: file.clone(),
+        details,
+    };
+
+    // DOC 文件是 OLE 复合文档：正文在 WordDocument 流，索引结构在 0Table/1Table 流
+    let mut compound = cfb::open(file_path).map_err(|e| error(format!("Failed to open OLE compound file: {}", e)))?;
+    let read = |compound: &mut cfb::CompoundFile<std::fs::File>, name: &str| {
+        read_stream(compound, name).map_err(|e| DocParserError::IoError {
+            file: file.clone(),
+            source: e,
+        })
+    };
+    let stream = read(&mut compound, "/WordDocument")?.ok_or_else(|| error("Missing WordDocument stream".to_string()))?;
+    let fib = Fib::parse(&stream).map_err(&error)?;
+    let table = read(&mut compound, &format!("/{}", fib.table_stream))?
+        .ok_or_else(|| error(format!("Missing {} stream", fib.table_stream)))?;
+    let document = WordDocument::new(stream, table, fib).map_err(&error)?;
+    let summary = read(&mut compound, "/\u{5}SummaryInformation")?
+        .and_then(|data| SummaryInformation::parse(&data))
+        .unwrap_or_default();
+
+    // 正文：主文档的字符位置 [0, ccpText)
+    let mut body = Body::new(&document);
+    for unit in document.units(0, document.fib.ccp_text) {
+        body.push(unit);
+    }
+    let mut body = body.finish();
+
+    if options.heading_heuristic && !body.has_style_headings {
+        super::docx::apply_heading_heuristic(&mut body.sections);
+    }
+
+    let notes = |kind| -> Vec<Note> {
+        document
+            .notes(kind)
+            .into_iter()
+            .enumerate()
+            .map(|(index, note)| Note {
+                id: index + 1,
+                content: plain_text(&document.units(note.text.start, note.text.end)),
+            })
+            .collect()
+    };
+    let footnotes = notes(NoteKind::Footnote);
+    let endnotes = notes(NoteKind::Endnote);
+
+    // 标题缺失时回退到首个短段落，与 DOCX 一致
+    let title = summary.title.or_else(|| {
+        body.sections
+            .iter()
+            .find(|section| !matches!(section.section_type, SectionType::Table))
+            .map(|section| section.content.clone())
+            .filter(|text| text.len() < 100)
+    });
+    let character_count = body.raw_text.chars().count();
+    let metadata = DocMetadata {
+        title,
+        author: summary.author,
+        subject: summary.subject,
+        created: summary.created,
+        modified: summary.modified,
+        word_count: body.word_count,
+        paragraph_count: body.paragraph_count,
+        page_count: summary.pages,
+        character_count,
+        keywords: summary.keywords,
+        description: summary.comments,
+        category: None,
+        last_modified_by: summary.last_author,
+        revision: summary.revision,
+        template: summary.template,
+        application: summary.application,
+        recorded_word_count: summary.words,
+        custom_properties: Default::default(),
+    };
+
+    Ok(DocData {
+        content: body.content.trim().to_string(),
+        metadata,
+        sections: body.sections,
+        raw_text: body.raw_text.trim().to_string(),
+        footnotes,
+        endnotes,
+        comments: Vec::new(),
+    })
+}
+
+#[cfg(feature = "legacy-doc")]
+fn read_stream(compound: &mut cfb::CompoundFile<std::fs::File>, name: &str) -> std::io::Result<Option<Vec<u8>>> {
+    use std::io::Read;
+
+    if !compound.is_stream(name) {
+        return Ok(None);
+    }
+    let mut data = Vec::new();
+    compound.open_stream(name)?.read_to_end(&mut data)?;
+    Ok(Some(data))
+}
+
+// 特殊字符
+#[cfg(feature = "legacy-doc")]
+const PARAGRAPH_MARK: u16 = 0x0D;
+#[cfg(feature = "legacy-doc")]
+const CELL_MARK: u16 = 0x07;
+#[cfg(feature = "legacy-doc")]
+const PAGE_BREAK: u16 = 0x0C;
+#[cfg(feature = "legacy-doc")]
+const NOTE_REFERENCE: u16 = 0x02;
+#[cfg(feature = "legacy-doc")]
+const FIELD_BEGIN: u16 = 0x13;
+#[cfg(feature = "legacy-doc")]
+const FIELD_SEPARATOR: u16 = 0x14;
+#[cfg(feature = "legacy-doc")]
+const FIELD_END: u16 = 0x15;
+
+/// 正文中可见的字符；控制字符（图片、批注引用、可选连字符等）被丢弃
+#[cfg(feature = "legacy-doc")]
+fn visible(code: u16) -> Option<u16> {
+    match code {
+        0x09 => Some(code),
+        // 手动换行
+        0x0B => Some('\n' as u16),
+        // 不间断连字符
+        0x1E => Some('-' as u16),
+        0x00..=0x1F => None,
+        _ => Some(code),
+    }
+}
+
+/// 域代码：`0x13 指令 0x14 结果 0x15`，只保留结果
+#[cfg(feature = "legacy-doc")]
+#[derive(Default)]
+struct Field {
+    instruction: String,
+    link: Option<Link>,
+    in_result: bool,
+}
+
+#[cfg(feature = "legacy-doc")]
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+    row: Vec<String>,
+    cell: Vec<String>,
+}
+
+/// 逐字符构建段落和表格
+#[cfg(feature = "legacy-doc")]
+struct Body<'a> {
+    document: &'a WordDocument,
+    /// 脚注/尾注引用的字符位置
+    references: HashMap<u32, (NoteKind, usize)>,
+    fields: Vec<Field>,
+    /// 当前段落：按链接分段的 UTF-16 文本
+    segments: Vec<(Option<Link>, Vec<u16>)>,
+    length: usize,
+    notes: Vec<NoteReference>,
+    table: Option<Table>,
+    sections: Vec<DocSection>,
+    content: String,
+    raw_text: String,
+    paragraph_count: usize,
+    word_count: usize,
+    has_style_headings: bool,
+}
+
+#[cfg(feature = "legacy-doc")]
+impl<'a> Body<'a> {
+    fn new(document: &'a WordDocument) -> Self {
+        let mut references = HashMap::new();
+        for kind in [NoteKind::Footnote, NoteKind::Endnote] {
+            for (index, note) in document.notes(kind).into_iter().enumerate() {
+                references.insert(note.reference, (kind, index + 1));
+            }
+        }
+        Self {
+            document,
+            references,
+            fields: Vec::new(),
+            segments: Vec::new(),
+            length: 0,
+            notes: Vec::new(),
+            table: None,
+            sections: Vec::new(),
+            content: String::new(),
+            raw_text: String::new(),
+            paragraph_count: 0,
+            word_count: 0,
+            has_style_headings: false,
+        }
+    }
+
+    fn push(&mut self, unit: Unit) {
+        match unit.code {
+            PARAGRAPH_MARK | CELL_MARK | PAGE_BREAK => self.end_paragraph(unit),
+            FIELD_BEGIN => self.fields.push(Field::default()),
+            FIELD_SEPARATOR => {
+                if let Some(field) = self.fields.last_mut() {
+                    field.in_result = true;
+                    field.link = parse_hyperlink_instruction(&field.instruction);
+                }
+            }
+            FIELD_END => {
+                self.fields.pop();
+            }
+            _ if self.fields.iter().any(|field| !field.in_result) => {
+                if let Some(field) = self.fields.last_mut().filter(|field| !field.in_result) {
+                    field.instruction.extend(char::from_u32(unit.code as u32));
+                }
+            }
+            NOTE_REFERENCE => {
+                if let Some(&(kind, id)) = self.references.get(&unit.cp) {
+                    self.notes.push(NoteReference {
+                        kind,
+                        id,
+                        offset: self.length,
+                    });
+                }
+            }
+            code => {
+                if let Some(code) = visible(code) {
+                    self.push_unit(code);
+                }
+            }
+        }
+    }
+
+    fn push_unit(&mut self, code: u16) {
+        let link = self.fields.iter().rev().find_map(|field| field.link.as_ref());
+        match self.segments.last_mut() {
+            Some((last, units)) if last.as_ref() == link => units.push(code),
+            _ => self.segments.push((link.cloned(), vec![code])),
+        }
+        // 代理对的低位不单独计为字符
+        if !(0xDC00..=0xDFFF).contains(&code) {
+            self.length += 1;
+        }
+    }
+
+    fn end_paragraph(&mut self, unit: Unit) {
+        let mut spans: Vec<TextSpan> = Vec::new();
+        for (link, units) in std::mem::take(&mut self.segments) {
+            push_span(&mut spans, &String::from_utf16_lossy(&units), FormatInfo::default(), link.as_ref(), None);
+        }
+        let notes = std::mem::take(&mut self.notes);
+        self.length = 0;
+        let text: String = spans.iter().map(|span| span.text.as_str()).collect();
+        let properties = self.document.paragraph_properties(unit.fc);
+
+        // 表格：单元格以 0x07 结束，行尾是单独带 TTP 属性的 0x07 段落；嵌套表格的内容并入外层单元格
+        if properties.in_table || unit.code == CELL_MARK {
+            let table = self.table.get_or_insert_with(Table::default);
+            if properties.row_end && properties.table_depth <= 1 {
+                if !table.cell.is_empty() {
+                    table.row.push(table.cell.join("\n"));
+                    table.cell.clear();
+                }
+                table.rows.push(std::mem::take(&mut table.row));
+                return;
+            }
+            if !text.trim().is_empty() {
+                table.cell.push(text.trim().to_string());
+            }
+            if unit.code == CELL_MARK && properties.table_depth <= 1 {
+                table.row.push(table.cell.join("\n"));
+                table.cell.clear();
+            }
+            return;
+        }
+        self.flush_table();
+
+        if text.trim().is_empty() {
+            return;
+        }
+        self.content.push_str(&text);
+        self.content.push('\n');
+        self.raw_text.push_str(&TextCleaner::clean_text(&text));
+        self.raw_text.push('\n');
+
+        let level = self.document.heading_level(&properties);
+        self.has_style_headings |= level.is_some();
+        self.sections.push(DocSection {
+            section_type: level.map_or(SectionType::Paragraph, SectionType::Heading),
+            content: text.clone(),
+            level,
+            formatting: common_formatting(&spans),
+            spans,
+            notes,
+            ..Default::default()
+        });
+        self.paragraph_count += 1;
+        self.word_count += text.split_whitespace().count();
+    }
+
+    fn flush_table(&mut self) {
+        let Some(mut table) = self.table.take() else {
+            return;
+        };
+        if !table.cell.is_empty() {
+            table.row.push(table.cell.join("\n"));
+        }
+        if !table.row.is_empty() {
+            table.rows.push(table.row);
+        }
+
+        let mut table_data = TableData::with_capacity(table.rows.len());
+        for (index, cells) in table.rows.into_iter().enumerate() {
+            let mut row = TableRow::with_capacity(index, cells.len());
+            for cell in cells {
+                row.add_cell(if cell.is_empty() { TableCell::empty() } else { TableCell::new(cell) });
+            }
+            table_data.add_row(row);
+        }
+        let table_text = table_data.plain_text();
+        if table_text.is_empty() {
+            return;
+        }
+        self.content.push_str(&table_text);
+        self.content.push('\n');
+        self.raw_text.push_str(&TextCleaner::clean_text(&table_text));
+        self.raw_text.push('\n');
+        self.word_count += table_text.split_whitespace().count();
+        self.sections.push(DocSection {
+            section_type: SectionType::Table,
+            content: table_text,
+            level: None,
+            formatting: None,
+            table: Some(table_data),
+            ..Default::default()
+        });
+    }
+
+    fn finish(mut self) -> Self {
+        if !self.segments.is_empty() {
+            self.end_paragraph(Unit {
+                cp: self.document.fib.ccp_text,
+                fc: 0,
+                code: PARAGRAPH_MARK,
+            });
+        }
+        self.flush_table();
+        self
+    }
+}
+
+/// 脚注/尾注等子文档的纯文本，段落之间以换行分隔
+#[cfg(feature = "legacy-doc")]
+fn plain_text(units: &[Unit]) -> String {
+    let mut text = Vec::new();
+    // 每层域是否已进入结果部分
+    let mut fields: Vec<bool> = Vec::new();
+    for unit in units {
+        match unit.code {
+            FIELD_BEGIN => fields.push(false),
+            FIELD_SEPARATOR => {
+                if let Some(in_result) = fields.last_mut() {
+                    *in_result = true;
+                }
+            }
+            FIELD_END => {
+                fields.pop();
+            }
+            _ if fields.contains(&false) => {}
+            PARAGRAPH_MARK | CELL_MARK | PAGE_BREAK => text.push('\n' as u16),
+            code => text.extend(visible(code)),
+        }
+    }
+    String::from_utf16_lossy(&text)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,8 +420,260 @@ mod tests {
     fn test_doc_format_not_supported_without_feature() {
         #[cfg(not(feature = "legacy-doc"))]
         {
-            let result = parse_doc("test.doc");
+            let result = parse_doc("test.doc", &ParseOptions::default());
             assert!(matches!(result, Err(DocParserError::UnsupportedFormat { .. })));
         }
     }
-}
\ No newline at end of file
+
+    /// 最小的 Word 97 文档：FIB、文本片段、一个 PAPX FKP 页，以及表流中的结构
+    /// （CLX、PlcBtePapx、STSH、脚注 PLC）
+    #[cfg(feature = "legacy-doc")]
+    struct TestDocument {
+        /// 正文片段：文字，以及是否按 8 位 CP1252 存储
+        pieces: Vec<(&'static str, bool)>,
+        footnote: &'static str,
+        /// 各段落标记的 (istd, grpprl)，按顺序；缺少的为普通段落
+        paragraphs: Vec<(u16, Vec<u8>)>,
+        summary: Vec<u8>,
+    }
+
+    #[cfg(feature = "legacy-doc")]
+    impl TestDocument {
+        fn write(&self, path: &Path) {
+            use std::io::Write;
+
+            let mut stream = vec![0u8; 1024];
+            let mut pieces = Vec::new();
+            let mut marks = Vec::new();
+            let all = self.pieces.iter().copied().chain([(self.footnote, true)]);
+            let mut cp = 0u32;
+            for (text, compressed) in all {
+                let start = stream.len() as u32;
+                let length = text.encode_utf16().count() as u32;
+                for (index, unit) in text.encode_utf16().enumerate() {
+                    if compressed {
+                        stream.push(unit as u8);
+                    } else {
+                        stream.extend(unit.to_le_bytes());
+                    }
+                    if unit == 0x0D || unit == 0x07 {
+                        let width = if compressed { 1 } else { 2 };
+                        marks.push(start + (index as u32 + 1) * width);
+                    }
+                }
+                pieces.push((cp, cp + length, start, compressed));
+                cp += length;
+                stream.resize(stream.len().div_ceil(512) * 512, 0);
+            }
+            let ccp_text: u32 = self.pieces.iter().map(|(text, _)| text.encode_utf16().count() as u32).sum();
+            let ccp_ftn = self.footnote.encode_utf16().count() as u32;
+
+            // PAPX FKP：每段一个区间，止于段落标记之后
+            let pn = stream.len() / 512;
+            let mut page = vec![0u8; 512];
+            let runs = marks.len();
+            let first_fc = 1024u32;
+            for (i, fc) in std::iter::once(first_fc).chain(marks.iter().copied()).enumerate() {
+                page[i * 4..i * 4 + 4].copy_from_slice(&fc.to_le_bytes());
+            }
+            let mut papx_offset = 300;
+            for (i, (istd, grpprl)) in self.paragraphs.iter().enumerate() {
+                let mut papx = istd.to_le_bytes().to_vec();
+                papx.extend(grpprl);
+                if papx.len() % 2 == 1 {
+                    papx.push(0);
+                }
+                page[(runs + 1) * 4 + i * 13] = (papx_offset / 2) as u8;
+                page[papx_offset] = 0;
+                page[papx_offset + 1] = (papx.len() / 2) as u8;
+                page[papx_offset + 2..papx_offset + 2 + papx.len()].copy_from_slice(&papx);
+                papx_offset += 2 + papx.len();
+            }
+            page[511] = runs as u8;
+            stream.extend(page);
+
+            // 表流
+            let mut table = Vec::new();
+            let mut blobs = HashMap::new();
+            let mut add = |index: usize, data: Vec<u8>, table: &mut Vec<u8>| {
+                blobs.insert(index, (table.len() as u32, data.len() as u32));
+                table.extend(data);
+            };
+            let u32s = |values: &[u32]| values.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<u8>>();
+
+            let mut plc_pcd = u32s(&pieces.iter().map(|p| p.0).chain([cp]).collect::<Vec<_>>());
+            for (_, _, start, compressed) in &pieces {
+                let fc = if *compressed { (start * 2) | 0x4000_0000 } else { *start };
+                plc_pcd.extend([0, 0]);
+                plc_pcd.extend(fc.to_le_bytes());
+                plc_pcd.extend([0, 0]);
+            }
+            let mut clx = vec![0x02];
+            clx.extend((plc_pcd.len() as u32).to_le_bytes());
+            clx.extend(plc_pcd);
+            add(33, clx, &mut table);
+
+            let mut bte = u32s(&[first_fc, *marks.last().unwrap()]);
+            bte.extend((pn as u32).to_le_bytes());
+            add(13, bte, &mut table);
+
+            // 两个样式：Normal（sti 0）和 heading 1（sti 1）
+            let mut stsh = u16s(&[4, 2, 10]);
+            for sti in [0u16, 1] {
+                stsh.extend(u16s(&[10, sti, 0, 0, 0, 0]));
+            }
+            add(1, stsh, &mut table);
+
+            let reference = self.pieces[0].0.encode_utf16().position(|unit| unit == 0x02).unwrap() as u32;
+            let mut plc_ref = u32s(&[reference, ccp_text]);
+            plc_ref.extend([1, 0]);
+            add(2, plc_ref, &mut table);
+            let note_end = self.footnote.find('\r').unwrap() as u32 + 1;
+            add(3, u32s(&[0, note_end, ccp_ftn]), &mut table);
+
+            // FIB：FibBase、14 个 word、22 个 long、93 对 fc/lcb
+            let mut fib = u16s(&[0xA5EC, 0x00C1]);
+            fib.resize(32, 0);
+            fib.extend(u16s(&[14]));
+            fib.extend(vec![0u8; 28]);
+            fib.extend(u16s(&[22]));
+            let mut longs = vec![0u32; 22];
+            longs[3] = ccp_text;
+            longs[4] = ccp_ftn;
+            fib.extend(u32s(&longs));
+            fib.extend(u16s(&[93]));
+            for index in 0..93 {
+                let (fc, lcb) = blobs.get(&index).copied().unwrap_or((0, 0));
+                fib.extend(u32s(&[fc, lcb]));
+            }
+            stream[..fib.len()].copy_from_slice(&fib);
+
+            let mut compound = cfb::create(path).unwrap();
+            compound.create_stream("/WordDocument").unwrap().write_all(&stream).unwrap();
+            compound.create_stream("/0Table").unwrap().write_all(&table).unwrap();
+            if !self.summary.is_empty() {
+                let mut summary = compound.create_stream("/\u{5}SummaryInformation").unwrap();
+                summary.write_all(&self.summary).unwrap();
+            }
+            compound.flush().unwrap();
+        }
+    }
+
+    #[cfg(feature = "legacy-doc")]
+    fn u16s(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    /// 由给定的 (属性 ID, 类型, 值字节) 组成的 SummaryInformation 流
+    #[cfg(feature = "legacy-doc")]
+    fn summary_stream(properties: &[(u32, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut header = u16s(&[0xFFFE, 0]);
+        header.resize(24, 0);
+        header.extend(1u32.to_le_bytes());
+        header.extend([0u8; 16]);
+        header.extend(48u32.to_le_bytes());
+
+        let mut values = Vec::new();
+        let mut index = Vec::new();
+        let table_size = 8 + properties.len() * 8;
+        for (id, kind, data) in properties {
+            index.extend(id.to_le_bytes());
+            index.extend(((table_size + values.len()) as u32).to_le_bytes());
+            values.extend(u16s(&[*kind, 0]));
+            values.extend(data);
+            values.resize(values.len().div_ceil(4) * 4, 0);
+        }
+        let mut set = ((table_size + values.len()) as u32).to_le_bytes().to_vec();
+        set.extend((properties.len() as u32).to_le_bytes());
+        set.extend(index);
+        set.extend(values);
+        header.extend(set);
+        header
+    }
+
+    #[cfg(feature = "legacy-doc")]
+    fn lpstr(text: &str) -> Vec<u8> {
+        let mut data = ((text.len() + 1) as u32).to_le_bytes().to_vec();
+        data.extend(text.as_bytes());
+        data.push(0);
+        data
+    }
+
+    #[cfg(feature = "legacy-doc")]
+    #[test]
+    fn test_parse_word97_document() {
+        const IN_TABLE: [u8; 3] = [0x16, 0x24, 0x01];
+        const ROW_END: [u8; 6] = [0x16, 0x24, 0x01, 0x17, 0x24, 0x01];
+
+        let created = (1_704_067_200u64 + 11_644_473_600) * 10_000_000;
+        let document = TestDocument {
+            pieces: vec![
+                (
+                    "Report\rSee note\u{2} and \u{13} HYPERLINK \"https://example.com\" \u{14}site\u{15}.\rA1\u{7}B1\u{7}\u{7}",
+                    true,
+                ),
+                ("Ünïcode ✓\r", false),
+            ],
+            footnote: "\u{2} Footnote text.\r\r",
+            paragraphs: vec![
+                (1, Vec::new()),
+                (0, Vec::new()),
+                (0, IN_TABLE.to_vec()),
+                (0, IN_TABLE.to_vec()),
+                (0, ROW_END.to_vec()),
+            ],
+            summary: summary_stream(&[
+                (1, 0x0002, u16s(&[1252])),
+                (2, 0x001E, lpstr("Legacy Report")),
+                (4, 0x001E, lpstr("Ann")),
+                (9, 0x001E, lpstr("4")),
+                (12, 0x0040, created.to_le_bytes().to_vec()),
+                (14, 0x0003, 3u32.to_le_bytes().to_vec()),
+            ]),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("legacy.doc");
+        document.write(&path);
+
+        let data = parse_doc(&path, &ParseOptions::default()).unwrap();
+        let sections: Vec<(String, &str)> = data
+            .sections
+            .iter()
+            .map(|section| (format!("{:?}", section.section_type), section.content.as_str()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Heading(1)".to_string(), "Report"),
+                ("Paragraph".to_string(), "See note and site."),
+                ("Table".to_string(), "A1 | B1"),
+                ("Paragraph".to_string(), "Ünïcode ✓"),
+            ]
+        );
+
+        let paragraph = &data.sections[1];
+        assert_eq!(paragraph.notes, vec![NoteReference { kind: NoteKind::Footnote, id: 1, offset: 8 }]);
+        let links: Vec<(String, String)> = paragraph.links().into_iter().map(|(text, link)| (text, link.href())).collect();
+        assert_eq!(links, vec![("site".to_string(), "https://example.com".to_string())]);
+        assert_eq!(data.footnotes, vec![Note { id: 1, content: "Footnote text.".to_string() }]);
+
+        assert_eq!(data.metadata.title.as_deref(), Some("Legacy Report"));
+        assert_eq!(data.metadata.author.as_deref(), Some("Ann"));
+        assert_eq!(data.metadata.created.as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(data.metadata.page_count, Some(3));
+        assert_eq!(data.metadata.revision, Some(4));
+        assert_eq!(data.metadata.paragraph_count, 3);
+    }
+
+    #[cfg(feature = "legacy-doc")]
+    #[test]
+    fn test_rejects_non_ole_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fake.doc");
+        std::fs::write(&path, b"not a compound file").unwrap();
+        assert!(matches!(
+            parse_doc(&path, &ParseOptions::default()),
+            Err(DocParserError::DocParsing { .. })
+        ));
+    }
+}
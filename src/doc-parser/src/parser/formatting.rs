@@ -0,0 +1,315 @@
+//! run 级别的字符格式。
+//!
+//! Word 按多层来源确定 run 的格式：文档默认格式、段落样式链、字符样式链，
+//! 最后是 run 自身的 `w:rPr`。[`RunProperties`] 表示其中一层，未设置的值为 None，
+//! 各层可用 [`RunProperties::merge`] 叠加。
+
+use super::package::{attr, built_elements};
+use super::{FormatInfo, Link, Revision, TextSpan, VerticalAlign};
+use quick_xml::events::BytesStart;
+
+/// 部分指定的 run 属性（样式层级中的一层）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunProperties {
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub strike: Option<bool>,
+    /// 字号，单位为半磅（与 `w:sz` 相同）
+    pub size: Option<u32>,
+    pub font_family: Option<String>,
+    /// 不带 `#` 的十六进制颜色，或 "auto"
+    pub color: Option<String>,
+    /// 突出显示颜色名（如 "yellow"），或 "none"
+    pub highlight: Option<String>,
+    pub vertical_align: Option<VerticalAlign>,
+}
+
+impl RunProperties {
+    /// 叠加另一层，`overlay` 中设置的值优先
+    pub fn merge(&mut self, overlay: &RunProperties) {
+        fn pick<T: Clone>(base: &mut Option<T>, overlay: &Option<T>) {
+            if overlay.is_some() {
+                base.clone_from(overlay);
+            }
+        }
+        pick(&mut self.bold, &overlay.bold);
+        pick(&mut self.italic, &overlay.italic);
+        pick(&mut self.underline, &overlay.underline);
+        pick(&mut self.strike, &overlay.strike);
+        pick(&mut self.size, &overlay.size);
+        pick(&mut self.font_family, &overlay.font_family);
+        pick(&mut self.color, &overlay.color);
+        pick(&mut self.highlight, &overlay.highlight);
+        pick(&mut self.vertical_align, &overlay.vertical_align);
+    }
+
+    /// 应用原始 `w:rPr` 的一个子元素
+    pub fn apply_element(&mut self, element: &BytesStart) {
+        let val = attr(element, "val");
+        match element.local_name().as_ref() {
+            b"b" => self.bold = Some(toggle(val.as_deref())),
+            b"i" => self.italic = Some(toggle(val.as_deref())),
+            b"strike" | b"dstrike" => self.strike = Some(toggle(val.as_deref())),
+            b"u" => self.underline = Some(val.as_deref() != Some("none")),
+            b"sz" => self.size = val.and_then(|v| v.parse::<f64>().ok()).map(|v| v as u32),
+            b"color" => self.color = val,
+            b"highlight" => self.highlight = val,
+            b"vertAlign" => self.vertical_align = val.as_deref().map(vertical_align),
+            b"rFonts" => {
+                let family = ["ascii", "hAnsi", "eastAsia", "cs"]
+                    .iter()
+                    .find_map(|name| attr(element, name));
+                if family.is_some() {
+                    self.font_family = family;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// docx-rs run 的直接格式，从其写回的 `w:rPr` 中读取
+    pub fn from_docx(property: &docx_rs::RunProperty) -> Self {
+        let mut properties = Self::default();
+        // 没有直接格式的 run 无需写回
+        if *property != docx_rs::RunProperty::default() {
+            for (_, element) in built_elements(property).iter().filter(|(depth, _)| *depth == 1) {
+                properties.apply_element(element);
+            }
+        }
+        properties
+    }
+
+    /// 各层合并后的最终格式
+    pub fn to_format_info(&self) -> FormatInfo {
+        FormatInfo {
+            bold: self.bold.unwrap_or(false),
+            italic: self.italic.unwrap_or(false),
+            underline: self.underline.unwrap_or(false),
+            strike: self.strike.unwrap_or(false),
+            font_size: self.size.map(|half_points| half_points as f32 / 2.0),
+            font_family: self.font_family.clone(),
+            color: self
+                .color
+                .as_deref()
+                .filter(|c| !c.eq_ignore_ascii_case("auto"))
+                .map(|c| format!("#{}", c.trim_start_matches('#').to_uppercase())),
+            highlight: self
+                .highlight
+                .as_deref()
+                .filter(|h| !h.eq_ignore_ascii_case("none"))
+                .map(highlight_to_hex),
+            vertical_align: self.vertical_align.unwrap_or_default(),
+        }
+    }
+}
+
+/// 开关属性：存在但没有 `w:val` 表示开启
+fn toggle(val: Option<&str>) -> bool {
+    !matches!(val, Some("0" | "false" | "off"))
+}
+
+fn vertical_align(val: &str) -> VerticalAlign {
+    match val {
+        "superscript" => VerticalAlign::Superscript,
+        "subscript" => VerticalAlign::Subscript,
+        _ => VerticalAlign::Baseline,
+    }
+}
+
+/// 将 `w:highlight` 颜色名转换为十六进制
+pub fn highlight_to_hex(highlight: &str) -> String {
+    match highlight.to_lowercase().as_str() {
+        "yellow" => "#FFFF00".to_string(),
+        "green" => "#00FF00".to_string(),
+        "cyan" => "#00FFFF".to_string(),
+        "magenta" => "#FF00FF".to_string(),
+        "blue" => "#0000FF".to_string(),
+        "red" => "#FF0000".to_string(),
+        "darkblue" => "#000080".to_string(),
+        "darkcyan" => "#008080".to_string(),
+        "darkgreen" => "#008000".to_string(),
+        "darkmagenta" => "#800080".to_string(),
+        "darkred" => "#800000".to_string(),
+        "darkyellow" => "#808000".to_string(),
+        "darkgray" => "#808080".to_string(),
+        "lightgray" => "#C0C0C0".to_string(),
+        "black" => "#000000".to_string(),
+        "white" => "#FFFFFF".to_string(),
+        _ => format!("#{}", highlight), // 视为已是十六进制值
+    }
+}
+
+/// 向片段列表追加文字，格式、链接和修订都相同时并入最后一个片段
+pub fn push_span(
+    spans: &mut Vec<TextSpan>,
+    text: &str,
+    formatting: FormatInfo,
+    link: Option<&Link>,
+    revision: Option<&Revision>,
+) {
+    if text.is_empty() {
+        return;
+    }
+    match spans.last_mut() {
+        Some(last)
+            if last.equation.is_none()
+                && last.formatting == formatting
+                && last.link.as_ref() == link
+                && last.revision.as_ref() == revision =>
+        {
+            last.text.push_str(text)
+        }
+        _ => spans.push(TextSpan {
+            text: text.to_string(),
+            formatting,
+            link: link.cloned(),
+            revision: revision.cloned(),
+            equation: None,
+        }),
+    }
+}
+
+/// 在字符偏移处插入片段，拆分包含该偏移的片段
+pub fn insert_span(spans: &mut Vec<TextSpan>, offset: usize, span: TextSpan) {
+    let mut start = 0;
+    for index in 0..spans.len() {
+        let length = spans[index].text.chars().count();
+        if offset == start {
+            spans.insert(index, span);
+            return;
+        }
+        if offset < start + length {
+            let split = spans[index]
+                .text
+                .char_indices()
+                .nth(offset - start)
+                .map_or(spans[index].text.len(), |(position, _)| position);
+            let tail = TextSpan {
+                text: spans[index].text.split_off(split),
+                ..spans[index].clone()
+            };
+            spans.splice(index + 1..index + 1, [span, tail]);
+            return;
+        }
+        start += length;
+    }
+    spans.push(span);
+}
+
+/// 所有非空白片段共有的格式；没有共同格式时为 None
+pub fn common_formatting(spans: &[TextSpan]) -> Option<FormatInfo> {
+    let mut visible = spans.iter().filter(|span| !span.text.trim().is_empty());
+    let mut common = visible.next()?.formatting.clone();
+    for span in visible {
+        let f = &span.formatting;
+        common.bold &= f.bold;
+        common.italic &= f.italic;
+        common.underline &= f.underline;
+        common.strike &= f.strike;
+        if common.font_size != f.font_size {
+            common.font_size = None;
+        }
+        if common.font_family != f.font_family {
+            common.font_family = None;
+        }
+        if common.color != f.color {
+            common.color = None;
+        }
+        if common.highlight != f.highlight {
+            common.highlight = None;
+        }
+        if common.vertical_align != f.vertical_align {
+            common.vertical_align = VerticalAlign::Baseline;
+        }
+    }
+    (!common.is_plain()).then_some(common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_docx_run_property() {
+        let property = docx_rs::RunProperty::new()
+            .bold()
+            .size(21)
+            .color("ff0000")
+            .highlight("yellow")
+            .strike()
+            .vert_align(docx_rs::VertAlignType::SuperScript)
+            .fonts(docx_rs::RunFonts::new().east_asia("宋体"));
+
+        let format = RunProperties::from_docx(&property).to_format_info();
+        assert!(format.bold && format.strike && !format.italic);
+        assert_eq!(format.font_size, Some(10.5));
+        assert_eq!(format.color.as_deref(), Some("#FF0000"));
+        assert_eq!(format.highlight.as_deref(), Some("#FFFF00"));
+        assert_eq!(format.font_family.as_deref(), Some("宋体"));
+        assert_eq!(format.vertical_align, VerticalAlign::Superscript);
+    }
+
+    #[test]
+    fn test_highlight_to_hex() {
+        // 标准颜色
+        assert_eq!(highlight_to_hex("yellow"), "#FFFF00");
+        assert_eq!(highlight_to_hex("red"), "#FF0000");
+        assert_eq!(highlight_to_hex("blue"), "#0000FF");
+        assert_eq!(highlight_to_hex("green"), "#00FF00");
+        
+        // 深色
+        assert_eq!(highlight_to_hex("darkRed"), "#800000");
+        assert_eq!(highlight_to_hex("darkBlue"), "#000080");
+        
+        // 不区分大小写
+        assert_eq!(highlight_to_hex("YELLOW"), "#FFFF00");
+        assert_eq!(highlight_to_hex("Red"), "#FF0000");
+        
+        // 十六进制值原样保留
+        assert_eq!(highlight_to_hex("FF5500"), "#FF5500");
+    }
+
+    #[test]
+    fn test_merge_layers() {
+        let mut base = RunProperties {
+            bold: Some(true),
+            size: Some(24),
+            color: Some("auto".to_string()),
+            ..Default::default()
+        };
+        base.merge(&RunProperties {
+            bold: Some(false),
+            italic: Some(true),
+            ..Default::default()
+        });
+
+        let format = base.to_format_info();
+        assert!(!format.bold && format.italic);
+        assert_eq!(format.font_size, Some(12.0));
+        assert_eq!(format.color, None);
+    }
+
+    #[test]
+    fn test_push_span_and_common_formatting() {
+        let bold = FormatInfo {
+            bold: true,
+            ..Default::default()
+        };
+        let mut spans = Vec::new();
+        push_span(&mut spans, "Hello ", bold.clone(), None, None);
+        push_span(&mut spans, "world", bold.clone(), None, None);
+        push_span(&mut spans, "", FormatInfo::default(), None, None);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(common_formatting(&spans), Some(bold.clone()));
+
+        push_span(&mut spans, "!", FormatInfo::default(), None, None);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(common_formatting(&spans), None);
+
+        // 格式相同，但链接文字保持独立
+        let link = Link { url: Some("https://example.com".to_string()), anchor: None };
+        push_span(&mut spans, "site", FormatInfo::default(), Some(&link), None);
+        assert_eq!(spans.len(), 3);
+    }
+}
@@ -0,0 +1,145 @@
+//! 文档正文中的图片。
+//!
+//! docx-rs 只保留 `w:drawing` 图片的关系 ID 和尺寸，丢弃了 `wp:docPr`
+//! （替代文字、标题）和 VML `w:pict` 图片，因此图片通过直接扫描文档部件得到，
+//! 其下标与 docx-rs 生成的正文段落一致（见 [`super::package::scan_body_paragraphs`]）。
+//!
+//! 表格和块级内容控件中的图片不会被收集：扫描跳过这些块，
+//! 且表格单元格只包含文字（见 [`super::table::CellBlock`]）。
+
+use super::package::{attr, ParagraphCollector, ScanPosition};
+use quick_xml::events::{BytesEnd, BytesStart};
+
+/// 96 DPI 下每像素的 EMU 数
+const EMU_PER_PIXEL: u64 = 9525;
+
+/// 扫描得到、尚未解析关系的图片
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RawImage {
+    /// 图片的关系 ID（`r:embed`、`r:link` 或 VML 的 `r:id`）
+    pub rel_id: String,
+    pub name: Option<String>,
+    pub alt_text: Option<String>,
+    pub title: Option<String>,
+    /// 显示尺寸（像素）
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// 浮动图片（`wp:anchor`），而非嵌入行内
+    pub anchored: bool,
+}
+
+/// 按文档顺序收集每个正文段落中的图片
+#[derive(Debug, Default)]
+pub struct ImageCollector {
+    pub paragraphs: Vec<Vec<RawImage>>,
+    /// 正在读取的图片，及其 `w:drawing`/`w:pict` 的深度
+    current: Option<(RawImage, usize)>,
+}
+
+impl ParagraphCollector for ImageCollector {
+    fn paragraph(&mut self) {
+        self.paragraphs.push(Vec::new());
+    }
+
+    fn start(&mut self, element: &BytesStart, empty: bool, position: &ScanPosition) {
+        let name = element.local_name();
+        match (name.as_ref(), self.current.as_mut()) {
+            (b"drawing" | b"pict", None) if !empty => self.current = Some((RawImage::default(), position.depth)),
+            (name, Some((image, _))) => read_picture_element(image, name, element),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, _element: &BytesEnd, position: &ScanPosition) {
+        if matches!(self.current, Some((_, depth)) if depth == position.depth) {
+            let (image, _) = self.current.take().unwrap_or_default();
+            if let (false, Some(images)) = (image.rel_id.is_empty(), self.paragraphs.last_mut()) {
+                images.push(image);
+            }
+        }
+    }
+}
+
+/// 从 `w:drawing` 或 `w:pict` 内的元素中收集图片信息
+fn read_picture_element(image: &mut RawImage, element: &[u8], e: &BytesStart) {
+    let non_empty = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+    match element {
+        b"anchor" => image.anchored = true,
+        b"extent" => {
+            let pixels = |key| attr(e, key).and_then(|v| v.parse::<u64>().ok()).map(|emu| (emu / EMU_PER_PIXEL) as u32);
+            image.width = pixels("cx");
+            image.height = pixels("cy");
+        }
+        b"docPr" => {
+            image.name = non_empty(attr(e, "name"));
+            image.alt_text = non_empty(attr(e, "descr"));
+            image.title = non_empty(attr(e, "title"));
+        }
+        // DrawingML 图片：嵌入（`r:embed`）或链接（`r:link`）
+        b"blip" if image.rel_id.is_empty() => {
+            image.rel_id = attr(e, "embed").or_else(|| attr(e, "link")).unwrap_or_default();
+        }
+        // VML 图片：尺寸取自形状样式，图片取自 `v:imagedata`
+        b"shape" => {
+            image.alt_text = image.alt_text.take().or_else(|| non_empty(attr(e, "alt")));
+            let style = attr(e, "style").unwrap_or_default();
+            image.width = image.width.or_else(|| vml_length(&style, "width"));
+            image.height = image.height.or_else(|| vml_length(&style, "height"));
+        }
+        b"imagedata" if image.rel_id.is_empty() => {
+            image.rel_id = attr(e, "id").unwrap_or_default();
+            image.title = image.title.take().or_else(|| non_empty(attr(e, "title")));
+        }
+        _ => {}
+    }
+}
+
+/// VML 样式属性（`width:120pt;height:80pt`）中的长度，换算为像素
+fn vml_length(style: &str, property: &str) -> Option<u32> {
+    let value = style.split(';').find_map(|declaration| {
+        let (name, value) = declaration.split_once(':')?;
+        (name.trim() == property).then(|| value.trim())
+    })?;
+    let split = value.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(value.len());
+    let number: f64 = value[..split].parse().ok()?;
+    let pixels = match &value[split..] {
+        "pt" => number * 96.0 / 72.0,
+        "in" => number * 96.0,
+        "cm" => number * 96.0 / 2.54,
+        "mm" => number * 96.0 / 25.4,
+        "px" | "" => number,
+        _ => return None,
+    };
+    Some(pixels.round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::package::scan_body_paragraphs;
+
+    #[test]
+    fn test_vml_length() {
+        let style = "position:absolute;width:72pt; height:1in";
+        assert_eq!(vml_length(style, "width"), Some(96));
+        assert_eq!(vml_length(style, "height"), Some(96));
+        assert_eq!(vml_length(style, "margin-left"), None);
+    }
+
+    #[test]
+    fn test_fallback_pictures_are_not_repeated() {
+        let xml = r#"<w:document><w:body><w:p><w:r><mc:AlternateContent>
+            <mc:Choice><w:drawing><wp:inline><wp:docPr id="1" name="Picture 1" descr="Chart"/>
+                <a:graphic><a:graphicData><pic:pic><pic:blipFill><a:blip r:embed="rId4"/></pic:blipFill></pic:pic></a:graphicData></a:graphic>
+            </wp:inline></w:drawing></mc:Choice>
+            <mc:Fallback><w:pict><v:shape><v:imagedata r:id="rId4"/></v:shape></w:pict></mc:Fallback>
+        </mc:AlternateContent></w:r></w:p></w:body></w:document>"#;
+        let mut collector = ImageCollector::default();
+        scan_body_paragraphs(xml, &mut [&mut collector]).unwrap();
+        let images = collector.paragraphs;
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].len(), 1);
+        assert_eq!(images[0][0].rel_id, "rId4");
+        assert_eq!(images[0][0].alt_text.as_deref(), Some("Chart"));
+    }
+}
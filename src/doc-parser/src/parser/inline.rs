@@ -0,0 +1,338 @@
+//! DOCX 段落的行内内容。
+//!
+//! 按文档顺序遍历段落的子元素，生成带格式的 [`TextSpan`]。`w:hyperlink` 中的文字
+//! 和 `HYPERLINK` 域结果中的文字保留在行内，并标记其 [`Link`]；在段落中开始的书签
+//! 作为链接目标收集。修订中的插入和删除按 [`RevisionMode`] 处理。
+//! run 边界和批注范围记录为字符偏移，以便之后放置 docx-rs 不支持的引用（脚注、尾注）。
+
+use super::formatting::{push_span, RunProperties};
+use super::package::{attr, built_elements, built_text};
+use super::styles::StyleSheet;
+use super::{FormatInfo, Link, Revision, RevisionKind, RevisionMode, TextSpan};
+use std::collections::HashMap;
+
+/// Word 自己的“上次编辑位置”书签，不会是真正的链接目标
+const GO_BACK_BOOKMARK: &str = "_GoBack";
+
+/// 修订没有 `w:date` 时 docx-rs 填入的日期
+const MISSING_REVISION_DATE: &str = "1970-01-01T00:00:00Z";
+
+/// 解释段落内容所需的文档级信息
+pub struct InlineContext<'a> {
+    pub styles: &'a StyleSheet,
+    /// 超链接关系 ID -> 目标地址
+    pub hyperlinks: &'a HashMap<String, String>,
+    pub revisions: RevisionMode,
+}
+
+/// 一个段落的片段和书签
+#[derive(Debug, Default)]
+pub struct ParagraphContent {
+    pub spans: Vec<TextSpan>,
+    pub bookmarks: Vec<String>,
+    /// 按文档顺序，每个 run 结束处的字符偏移
+    pub run_ends: Vec<usize>,
+    /// 段落中批注范围的开始和结束
+    pub comment_marks: Vec<CommentMark>,
+}
+
+/// 批注范围的开始或结束
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentMark {
+    pub id: usize,
+    /// 在段落文字中的字符偏移
+    pub offset: usize,
+    pub start: bool,
+}
+
+impl ParagraphContent {
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+/// 收集段落的行内内容
+pub fn paragraph_content(paragraph: &docx_rs::Paragraph, context: &InlineContext) -> ParagraphContent {
+    let mut collector = Collector {
+        context,
+        paragraph_style: paragraph.property.style.as_ref().map(|style| style.val.as_str()),
+        content: ParagraphContent::default(),
+        fields: Vec::new(),
+        length: 0,
+    };
+    collector.children(&paragraph.children, None, None);
+    collector.content
+}
+
+/// 段落的纯文本，包括超链接文字，修订按 `mode` 处理
+pub fn paragraph_text(paragraph: &docx_rs::Paragraph, mode: RevisionMode) -> String {
+    let styles = StyleSheet::default();
+    let hyperlinks = HashMap::new();
+    let context = InlineContext {
+        styles: &styles,
+        hyperlinks: &hyperlinks,
+        revisions: mode,
+    };
+    paragraph_content(paragraph, &context).text()
+}
+
+/// 未结束的复杂域（`w:fldChar` begin ... end）
+struct Field {
+    instruction: String,
+    /// 域代码完整且为超链接时设置
+    link: Option<Link>,
+    /// 位于 `separate` 和 `end` 标记之间，即可见的域结果
+    in_result: bool,
+}
+
+struct Collector<'a, 'c> {
+    context: &'c InlineContext<'c>,
+    paragraph_style: Option<&'a str>,
+    content: ParagraphContent,
+    fields: Vec<Field>,
+    /// 已收集的字符数
+    length: usize,
+}
+
+impl<'a, 'c> Collector<'a, 'c> {
+    fn children(&mut self, children: &'a [docx_rs::ParagraphChild], link: Option<&Link>, revision: Option<&Revision>) {
+        for child in children {
+            match child {
+                docx_rs::ParagraphChild::Run(run) => self.run(run, link, revision),
+                docx_rs::ParagraphChild::Hyperlink(hyperlink) => {
+                    let target = self.resolve(&hyperlink.link);
+                    self.children(&hyperlink.children, target.as_ref().or(link), revision);
+                }
+                docx_rs::ParagraphChild::BookmarkStart(bookmark) if bookmark.name != GO_BACK_BOOKMARK => {
+                    self.content.bookmarks.push(bookmark.name.clone());
+                }
+                docx_rs::ParagraphChild::CommentStart(comment) => self.comment_mark(comment.id, true),
+                docx_rs::ParagraphChild::CommentEnd(comment) => self.comment_end(comment),
+                docx_rs::ParagraphChild::Insert(insert) => {
+                    let inserted = revision_of(RevisionKind::Insertion, &insert.author, &insert.date);
+                    for child in &insert.children {
+                        match child {
+                            docx_rs::InsertChild::Run(run) => self.run(run, link, Some(&inserted)),
+                            docx_rs::InsertChild::Delete(delete) => self.delete(delete, link),
+                            docx_rs::InsertChild::CommentStart(comment) => self.comment_mark(comment.id, true),
+                            docx_rs::InsertChild::CommentEnd(comment) => self.comment_end(comment),
+                        }
+                    }
+                }
+                docx_rs::ParagraphChild::Delete(delete) => self.delete(delete, link),
+                // 移动的文字在原位置视为删除，在目标位置视为插入
+                docx_rs::ParagraphChild::MoveFrom(moved) => {
+                    let deleted = revision_of(RevisionKind::Deletion, &moved.author, &moved.date);
+                    for child in &moved.children {
+                        if let docx_rs::MoveFromChild::Run(run) = child {
+                            self.run(run, link, Some(&deleted));
+                        }
+                    }
+                }
+                docx_rs::ParagraphChild::MoveTo(moved) => {
+                    let inserted = revision_of(RevisionKind::Insertion, &moved.author, &moved.date);
+                    for child in &moved.children {
+                        match child {
+                            docx_rs::MoveToChild::Run(run) => self.run(run, link, Some(&inserted)),
+                            docx_rs::MoveToChild::Delete(delete) => self.delete(delete, link),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn delete(&mut self, delete: &docx_rs::Delete, link: Option<&Link>) {
+        let deleted = revision_of(RevisionKind::Deletion, &delete.author, &delete.date);
+        for child in &delete.children {
+            match child {
+                docx_rs::DeleteChild::Run(run) => self.run(run, link, Some(&deleted)),
+                docx_rs::DeleteChild::CommentStart(comment) => self.comment_mark(comment.id, true),
+                docx_rs::DeleteChild::CommentEnd(comment) => self.comment_end(comment),
+            }
+        }
+    }
+
+    fn run(&mut self, run: &docx_rs::Run, link: Option<&Link>, revision: Option<&Revision>) {
+        let mode = self.context.revisions;
+        let visible = match revision.map(|r| r.kind) {
+            None => true,
+            Some(RevisionKind::Insertion) => mode != RevisionMode::Reject,
+            Some(RevisionKind::Deletion) => mode != RevisionMode::Accept,
+        };
+        // 只有显示修订时才标记；接受或拒绝后的文字视为普通文字
+        let revision = revision.filter(|_| mode == RevisionMode::Show);
+        let run_style = run.run_property.style.as_ref().map(|style| style.val.as_str());
+        let mut properties = self.context.styles.run_properties(self.paragraph_style, run_style);
+        properties.merge(&RunProperties::from_docx(&run.run_property));
+        let formatting = properties.to_format_info();
+
+        for child in &run.children {
+            match child {
+                docx_rs::RunChild::FieldChar(field_char) => match field_char.field_char_type {
+                    docx_rs::FieldCharType::Begin => self.fields.push(Field {
+                        instruction: String::new(),
+                        link: None,
+                        in_result: false,
+                    }),
+                    docx_rs::FieldCharType::Separate => {
+                        if let Some(field) = self.fields.last_mut() {
+                            field.in_result = true;
+                            field.link = parse_hyperlink_instruction(&field.instruction);
+                        }
+                    }
+                    docx_rs::FieldCharType::End => {
+                        self.fields.pop();
+                    }
+                    _ => {}
+                },
+                docx_rs::RunChild::InstrTextString(instruction) => {
+                    if let Some(field) = self.fields.last_mut() {
+                        field.instruction.push_str(instruction);
+                    }
+                }
+                docx_rs::RunChild::Text(text) if visible => self.text(&text.text, &formatting, link, revision),
+                docx_rs::RunChild::DeleteText(text) if visible => {
+                    self.text(&built_text(text), &formatting, link, revision)
+                }
+                _ => {}
+            }
+        }
+        self.content.run_ends.push(self.length);
+    }
+
+    fn text(&mut self, text: &str, formatting: &FormatInfo, link: Option<&Link>, revision: Option<&Revision>) {
+        // 域的 begin 和 separate 之间是域代码，而非内容
+        if self.fields.iter().any(|field| !field.in_result) {
+            return;
+        }
+        let field_link = self.fields.iter().rev().find_map(|field| field.link.as_ref());
+        push_span(&mut self.content.spans, text, formatting.clone(), link.or(field_link), revision);
+        self.length += text.chars().count();
+    }
+
+    fn comment_mark(&mut self, id: usize, start: bool) {
+        self.content.comment_marks.push(CommentMark {
+            id,
+            offset: self.length,
+            start,
+        });
+    }
+
+    fn comment_end(&mut self, end: &docx_rs::CommentRangeEnd) {
+        if let Some(id) = comment_end_id(end) {
+            self.comment_mark(id, false);
+        }
+    }
+
+    fn resolve(&self, data: &docx_rs::HyperlinkData) -> Option<Link> {
+        match data {
+            docx_rs::HyperlinkData::External { rid, .. } => self.context.hyperlinks.get(rid).map(|url| Link {
+                url: Some(url.clone()),
+                anchor: None,
+            }),
+            docx_rs::HyperlinkData::Anchor { anchor } => Some(Link {
+                url: None,
+                anchor: Some(anchor.clone()),
+            }),
+        }
+    }
+}
+
+fn revision_of(kind: RevisionKind, author: &str, date: &str) -> Revision {
+    Revision {
+        kind,
+        author: author.to_string(),
+        date: Some(date).filter(|date| !date.is_empty() && *date != MISSING_REVISION_DATE).map(str::to_string),
+    }
+}
+
+/// 批注范围结束处的 ID
+fn comment_end_id(end: &docx_rs::CommentRangeEnd) -> Option<usize> {
+    built_elements(end)
+        .iter()
+        .find(|(_, element)| element.local_name().as_ref() == b"commentRangeEnd")
+        .and_then(|(_, element)| attr(element, "id"))
+        .and_then(|id| id.parse().ok())
+}
+
+/// 解析 `HYPERLINK "url" \l "anchor"` 域代码
+pub(crate) fn parse_hyperlink_instruction(instruction: &str) -> Option<Link> {
+    let tokens = split_instruction(instruction);
+    let mut tokens = tokens.iter();
+    if !tokens.next()?.eq_ignore_ascii_case("HYPERLINK") {
+        return None;
+    }
+
+    let mut link = Link { url: None, anchor: None };
+    while let Some(token) = tokens.next() {
+        match token.as_str() {
+            "\\l" => link.anchor = tokens.next().cloned(),
+            // 带参数但不影响目标的开关
+            "\\o" | "\\t" => {
+                tokens.next();
+            }
+            switch if switch.starts_with('\\') => {}
+            url if link.url.is_none() => link.url = Some(url.to_string()),
+            _ => {}
+        }
+    }
+    (link.url.is_some() || link.anchor.is_some()).then_some(link)
+}
+
+/// 将域代码拆分为单词，引号中的参数保持完整
+fn split_instruction(instruction: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in instruction.chars() {
+        match c {
+            '"' => {
+                if quoted {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                quoted = !quoted;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hyperlink_instruction() {
+        let link = parse_hyperlink_instruction(r#" HYPERLINK "https://example.com/a b" \o "tip" "#).unwrap();
+        assert_eq!(link.url.as_deref(), Some("https://example.com/a b"));
+        assert_eq!(link.anchor, None);
+
+        let link = parse_hyperlink_instruction(r#"HYPERLINK \l "_Toc123""#).unwrap();
+        assert_eq!(link.href(), "#_Toc123");
+
+        assert!(parse_hyperlink_instruction(r#"PAGEREF _Toc123 \h"#).is_none());
+    }
+
+    #[test]
+    fn test_paragraph_text_includes_hyperlinks() {
+        use docx_rs::{Hyperlink, HyperlinkType, Paragraph, Run};
+
+        let paragraph = Paragraph::new()
+            .add_run(Run::new().add_text("See "))
+            .add_hyperlink(Hyperlink::new("Intro", HyperlinkType::Anchor).add_run(Run::new().add_text("the intro")))
+            .add_run(Run::new().add_text("."));
+        assert_eq!(paragraph_text(&paragraph, RevisionMode::Accept), "See the intro.");
+    }
+}
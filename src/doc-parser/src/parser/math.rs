@@ -0,0 +1,890 @@
+//! Office Math（OMML）公式。
+//!
+//! docx-rs 完全丢弃 `m:oMath` 元素，因此公式通过直接扫描文档部件得到，
+//! 与注释引用一样按段落和 run 序号定位（见 [`super::package::scan_body_paragraphs`]）。
+//! 每个公式解析为一棵小的元素树，再转换为 LaTeX 和表示型 MathML，
+//! 同时保留原始 OMML。
+
+use super::package::{attr, ParagraphCollector, ScanPosition};
+use super::Equation;
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::Reader;
+
+/// 由同名 LaTeX 命令以正体书写的函数
+const FUNCTIONS: &[&str] = &[
+    "arccos", "arcsin", "arctan", "arg", "cos", "cosh", "cot", "coth", "csc", "deg", "det", "dim", "exp", "gcd", "inf",
+    "ker", "lg", "lim", "liminf", "limsup", "ln", "log", "max", "min", "Pr", "sec", "sin", "sinh", "sup", "tan", "tanh",
+];
+
+/// 扫描得到的公式
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawEquation {
+    /// 段落中公式之前的 run 数
+    pub run: usize,
+    /// 位于 `m:oMathPara` 中，即单独成行
+    pub display: bool,
+    /// 文档中原样的 `m:oMath` 元素
+    pub omml: String,
+}
+
+impl RawEquation {
+    pub fn to_equation(&self) -> Equation {
+        let root = parse(&self.omml).unwrap_or_default();
+        Equation {
+            latex: to_latex(&root),
+            mathml: to_mathml(&root, self.display),
+            omml: self.omml.clone(),
+            display: self.display,
+        }
+    }
+}
+
+/// 收集每个正文段落中的公式（见 [`super::package::scan_body_paragraphs`]）
+#[derive(Debug)]
+pub struct EquationCollector<'a> {
+    pub paragraphs: Vec<Vec<RawEquation>>,
+    document_xml: &'a str,
+    /// 未结束的 `m:oMathPara` 的深度
+    math_para: Option<usize>,
+    /// 未结束的 `m:oMath` 的字节偏移，及其之前已开始的 run 数
+    math_start: Option<(usize, usize)>,
+}
+
+impl<'a> EquationCollector<'a> {
+    /// `document_xml` 必须是正在扫描的部件；公式的 OMML 取自其中的切片
+    pub fn new(document_xml: &'a str) -> Self {
+        Self {
+            paragraphs: Vec::new(),
+            document_xml,
+            math_para: None,
+            math_start: None,
+        }
+    }
+}
+
+impl ParagraphCollector for EquationCollector<'_> {
+    fn paragraph(&mut self) {
+        self.paragraphs.push(Vec::new());
+    }
+
+    fn start(&mut self, element: &BytesStart, empty: bool, position: &ScanPosition) {
+        match element.local_name().as_ref() {
+            b"oMathPara" if !empty => self.math_para = Some(position.depth),
+            b"oMath" if !empty => self.math_start = Some((position.start, position.runs)),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, element: &BytesEnd, position: &ScanPosition) {
+        match element.local_name().as_ref() {
+            b"oMath" => {
+                if let (Some((start, run)), Some(current)) = (self.math_start.take(), self.paragraphs.last_mut()) {
+                    current.push(RawEquation {
+                        run,
+                        display: self.math_para.is_some(),
+                        omml: self.document_xml[start..position.end].to_string(),
+                    });
+                }
+            }
+            b"oMathPara" if self.math_para == Some(position.depth) => self.math_para = None,
+            _ => {}
+        }
+    }
+}
+
+/// OMML 元素：本地名、`m:val` 属性、`m:t` 文字和子元素
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    val: Option<String>,
+    text: String,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn from_start(element: &BytesStart) -> Self {
+        Self {
+            name: String::from_utf8_lossy(element.local_name().as_ref()).into_owned(),
+            val: attr(element, "val"),
+            ..Self::default()
+        }
+    }
+
+    fn child(&self, name: &str) -> Option<&Node> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Node> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// 结构自身属性中的元素，如分数 `m:fPr` 中的 `m:type`
+    fn property_element(&self, name: &str) -> Option<&Node> {
+        let properties = format!("{}Pr", self.name);
+        self.child(&properties)?.child(name)
+    }
+
+    fn property(&self, name: &str) -> Option<&str> {
+        self.property_element(name)?.val.as_deref()
+    }
+
+    /// 开关属性：存在且没有值，或值为 "1"、"on"、"true"
+    fn flag(&self, name: &str) -> bool {
+        self.property_element(name)
+            .is_some_and(|element| element.val.as_deref().is_none_or(|val| matches!(val, "1" | "on" | "true")))
+    }
+
+    /// run（`m:r`）的文字
+    fn run_text(&self) -> String {
+        self.children_named("t").map(|t| t.text.as_str()).collect()
+    }
+
+    /// run 是否为普通（非数学）文字，即属性中有 `m:nor`
+    fn is_normal_text(&self) -> bool {
+        self.child("rPr").and_then(|properties| properties.child("nor")).is_some()
+    }
+
+    /// 内容元素，不含属性元素（`m:fPr`、`w:rPr`、`m:ctrlPr` 等）
+    fn content(&self) -> impl Iterator<Item = &Node> {
+        self.children.iter().filter(|child| !child.name.ends_with("Pr"))
+    }
+
+    /// 元素只包含已知函数名的纯文本时，返回该函数名
+    fn function_name(&self) -> Option<String> {
+        let mut text = String::new();
+        for child in self.content() {
+            if child.name != "r" {
+                return None;
+            }
+            text.push_str(&child.run_text());
+        }
+        let text = text.trim();
+        FUNCTIONS.contains(&text).then(|| text.to_string())
+    }
+}
+
+fn parse(omml: &str) -> Result<Node, quick_xml::Error> {
+    let mut reader = Reader::from_str(omml);
+    let mut stack = vec![Node::default()];
+    loop {
+        match reader.read_event()? {
+            Event::Start(ref e) => stack.push(Node::from_start(e)),
+            Event::Empty(ref e) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Node::from_start(e));
+                }
+            }
+            Event::Text(ref t) => {
+                if let Some(node) = stack.last_mut().filter(|node| node.name == "t") {
+                    node.text.push_str(&t.unescape()?);
+                }
+            }
+            Event::End(_) if stack.len() > 1 => {
+                if let Some(node) = stack.pop() {
+                    stack.last_mut().expect("root stays on the stack").children.push(node);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    while stack.len() > 1 {
+        if let Some(node) = stack.pop() {
+            stack.last_mut().expect("root stays on the stack").children.push(node);
+        }
+    }
+    Ok(stack.pop().unwrap_or_default())
+}
+
+// ---- LaTeX ----
+
+fn to_latex(root: &Node) -> String {
+    latex_children(Some(root)).trim().to_string()
+}
+
+/// 元素内容拼接后的 LaTeX；元素不存在时为空
+fn latex_children(node: Option<&Node>) -> String {
+    let mut out = String::new();
+    for child in node.into_iter().flat_map(Node::content) {
+        append(&mut out, &latex(child));
+    }
+    out
+}
+
+/// 追加 LaTeX，控制词与其后的字母之间加空格
+fn append(out: &mut String, piece: &str) {
+    let letters = out.len() - out.trim_end_matches(|c: char| c.is_ascii_alphabetic()).len();
+    if letters > 0
+        && out[..out.len() - letters].ends_with('\\')
+        && piece.starts_with(|c: char| c.is_ascii_alphabetic())
+    {
+        out.push(' ');
+    }
+    out.push_str(piece);
+}
+
+/// 单个字符和控制词作为上下标或参数时不需要花括号
+fn group(latex: &str) -> String {
+    let single = latex.chars().count() == 1
+        || latex
+            .strip_prefix('\\')
+            .is_some_and(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_alphabetic()));
+    if single {
+        latex.to_string()
+    } else {
+        format!("{{{}}}", latex)
+    }
+}
+
+fn latex(node: &Node) -> String {
+    let arg = |name: &str| latex_children(node.child(name));
+    match node.name.as_str() {
+        "r" => latex_run(node),
+        "f" => {
+            let (num, den) = (arg("num"), arg("den"));
+            match node.property("type") {
+                Some("lin" | "skw") => format!("{}/{}", group(&num), group(&den)),
+                Some("noBar") => format!("\\genfrac{{}}{{}}{{0pt}}{{}}{{{}}}{{{}}}", num, den),
+                _ => format!("\\frac{{{}}}{{{}}}", num, den),
+            }
+        }
+        "rad" => {
+            let degree = arg("deg");
+            if node.flag("degHide") || degree.is_empty() {
+                format!("\\sqrt{{{}}}", arg("e"))
+            } else {
+                format!("\\sqrt[{}]{{{}}}", degree, arg("e"))
+            }
+        }
+        "sSup" => format!("{}^{{{}}}", group(&arg("e")), arg("sup")),
+        "sSub" => format!("{}_{{{}}}", group(&arg("e")), arg("sub")),
+        "sSubSup" => format!("{}_{{{}}}^{{{}}}", group(&arg("e")), arg("sub"), arg("sup")),
+        "sPre" => format!("{{}}_{{{}}}^{{{}}}{}", arg("sub"), arg("sup"), group(&arg("e"))),
+        "nary" => {
+            let operator = node.property("chr").and_then(|chr| chr.chars().next()).unwrap_or('∫');
+            let mut out = latex_symbol(operator);
+            let (sub, sup) = (arg("sub"), arg("sup"));
+            if !node.flag("subHide") && !sub.is_empty() {
+                out.push_str(&format!("_{{{}}}", sub));
+            }
+            if !node.flag("supHide") && !sup.is_empty() {
+                out.push_str(&format!("^{{{}}}", sup));
+            }
+            out.push(' ');
+            out.push_str(&arg("e"));
+            out
+        }
+        "d" => {
+            let separator = node.property("sepChr").unwrap_or("|");
+            let separator: String = separator.chars().map(latex_symbol).collect();
+            let items: Vec<String> = node.children_named("e").map(|e| latex_children(Some(e))).collect();
+            format!(
+                "\\left{}{}\\right{}",
+                latex_delimiter(node.property("begChr").unwrap_or("(")),
+                items.join(&separator),
+                latex_delimiter(node.property("endChr").unwrap_or(")")),
+            )
+        }
+        "m" => {
+            let rows: Vec<String> = node
+                .children_named("mr")
+                .map(|row| row.children_named("e").map(|e| latex_children(Some(e))).collect::<Vec<_>>().join(" & "))
+                .collect();
+            format!("\\begin{{matrix}}{}\\end{{matrix}}", rows.join(" \\\\ "))
+        }
+        "eqArr" => {
+            let rows: Vec<String> = node.children_named("e").map(|e| latex_children(Some(e))).collect();
+            format!("\\begin{{gathered}}{}\\end{{gathered}}", rows.join(" \\\\ "))
+        }
+        "func" => {
+            let name = node.child("fName");
+            let mut out = match name.and_then(Node::function_name) {
+                Some(function) => format!("\\{}", function),
+                None if name.is_some_and(|name| name.content().all(|child| child.name == "r")) => {
+                    format!("\\operatorname{{{}}}", arg("fName"))
+                }
+                None => arg("fName"),
+            };
+            let argument = arg("e");
+            if argument.starts_with(char::is_alphanumeric) {
+                out.push(' ');
+            }
+            out.push_str(&argument);
+            out
+        }
+        "limLow" | "limUpp" => {
+            let (script, stack) = if node.name == "limLow" { ("_", "underset") } else { ("^", "overset") };
+            match node.child("e").and_then(Node::function_name) {
+                Some(function) => format!("\\{}{}{{{}}}", function, script, arg("lim")),
+                None => format!("\\{}{{{}}}{{{}}}", stack, arg("lim"), arg("e")),
+            }
+        }
+        "acc" => {
+            let accent = node.property("chr").and_then(|chr| chr.chars().next()).unwrap_or('\u{302}');
+            match latex_accent(accent) {
+                Some(command) => format!("\\{}{{{}}}", command, arg("e")),
+                None => format!("\\overset{{{}}}{{{}}}", latex_symbol(accent), arg("e")),
+            }
+        }
+        "bar" => match node.property("pos") {
+            Some("top") => format!("\\overline{{{}}}", arg("e")),
+            _ => format!("\\underline{{{}}}", arg("e")),
+        },
+        "groupChr" => {
+            let character = node.property("chr").and_then(|chr| chr.chars().next()).unwrap_or('\u{23DF}');
+            let top = node.property("pos") == Some("top");
+            match character {
+                '\u{23DF}' => format!("\\underbrace{{{}}}", arg("e")),
+                '\u{23DE}' => format!("\\overbrace{{{}}}", arg("e")),
+                _ => {
+                    let stack = if top { "overset" } else { "underset" };
+                    format!("\\{}{{{}}}{{{}}}", stack, latex_symbol(character), arg("e"))
+                }
+            }
+        }
+        "borderBox" => format!("\\boxed{{{}}}", arg("e")),
+        _ => latex_children(Some(node)),
+    }
+}
+
+fn latex_run(node: &Node) -> String {
+    let text = node.run_text();
+    if node.is_normal_text() {
+        let escaped: String = text
+            .chars()
+            .map(|c| match c {
+                '{' | '}' | '#' | '$' | '%' | '&' | '_' => format!("\\{}", c),
+                '\\' => "\\textbackslash{}".to_string(),
+                _ => c.to_string(),
+            })
+            .collect();
+        return format!("\\text{{{}}}", escaped);
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        append(&mut out, &latex_symbol(c));
+    }
+    out
+}
+
+fn latex_delimiter(delimiter: &str) -> String {
+    match delimiter.chars().next() {
+        None => ".".to_string(),
+        Some('{') => "\\{".to_string(),
+        Some('}') => "\\}".to_string(),
+        Some('⟨' | '〈') => "\\langle".to_string(),
+        Some('⟩' | '〉') => "\\rangle".to_string(),
+        Some('⌊') => "\\lfloor".to_string(),
+        Some('⌋') => "\\rfloor".to_string(),
+        Some('⌈') => "\\lceil".to_string(),
+        Some('⌉') => "\\rceil".to_string(),
+        Some('‖') => "\\|".to_string(),
+        Some(c) => c.to_string(),
+    }
+}
+
+fn latex_accent(accent: char) -> Option<&'static str> {
+    Some(match accent {
+        '\u{302}' | '^' => "hat",
+        '\u{303}' | '~' => "tilde",
+        '\u{304}' | '\u{305}' | '¯' => "bar",
+        '\u{307}' | '˙' => "dot",
+        '\u{308}' | '¨' => "ddot",
+        '\u{20DB}' => "dddot",
+        '\u{20D7}' | '→' => "vec",
+        '\u{301}' | '´' => "acute",
+        '\u{300}' | '`' => "grave",
+        '\u{306}' | '˘' => "breve",
+        '\u{30C}' | 'ˇ' => "check",
+        _ => return None,
+    })
+}
+
+/// 数学文字中单个字符的 LaTeX
+fn latex_symbol(c: char) -> String {
+    let command = match c {
+        '{' | '}' | '#' | '$' | '%' | '&' | '_' => return format!("\\{}", c),
+        '\\' => "\\backslash",
+        '~' => "\\sim",
+        '^' => "\\wedge",
+        '\u{2212}' => "-",
+        '∗' => "*",
+        '′' => "'",
+        '″' => "''",
+        '°' => "^{\\circ}",
+        'α' => "\\alpha",
+        'β' => "\\beta",
+        'γ' => "\\gamma",
+        'δ' => "\\delta",
+        'ε' => "\\varepsilon",
+        'ϵ' => "\\epsilon",
+        'ζ' => "\\zeta",
+        'η' => "\\eta",
+        'θ' => "\\theta",
+        'ϑ' => "\\vartheta",
+        'ι' => "\\iota",
+        'κ' => "\\kappa",
+        'λ' => "\\lambda",
+        'μ' => "\\mu",
+        'ν' => "\\nu",
+        'ξ' => "\\xi",
+        'π' => "\\pi",
+        'ρ' => "\\rho",
+        'σ' => "\\sigma",
+        'ς' => "\\varsigma",
+        'τ' => "\\tau",
+        'υ' => "\\upsilon",
+        'φ' => "\\varphi",
+        'ϕ' => "\\phi",
+        'χ' => "\\chi",
+        'ψ' => "\\psi",
+        'ω' => "\\omega",
+        'Γ' => "\\Gamma",
+        'Δ' => "\\Delta",
+        'Θ' => "\\Theta",
+        'Λ' => "\\Lambda",
+        'Ξ' => "\\Xi",
+        'Π' => "\\Pi",
+        'Σ' => "\\Sigma",
+        'Υ' => "\\Upsilon",
+        'Φ' => "\\Phi",
+        'Ψ' => "\\Psi",
+        'Ω' => "\\Omega",
+        '×' => "\\times",
+        '÷' => "\\div",
+        '·' | '⋅' => "\\cdot",
+        '±' => "\\pm",
+        '∓' => "\\mp",
+        '≤' => "\\leq",
+        '≥' => "\\geq",
+        '≠' => "\\neq",
+        '≈' => "\\approx",
+        '≡' => "\\equiv",
+        '∼' => "\\sim",
+        '≅' => "\\cong",
+        '∝' => "\\propto",
+        '≪' => "\\ll",
+        '≫' => "\\gg",
+        '∞' => "\\infty",
+        '∂' => "\\partial",
+        '∇' => "\\nabla",
+        '∈' => "\\in",
+        '∉' => "\\notin",
+        '∋' => "\\ni",
+        '⊂' => "\\subset",
+        '⊃' => "\\supset",
+        '⊆' => "\\subseteq",
+        '⊇' => "\\supseteq",
+        '∪' => "\\cup",
+        '∩' => "\\cap",
+        '∅' => "\\emptyset",
+        '∀' => "\\forall",
+        '∃' => "\\exists",
+        '¬' => "\\neg",
+        '∧' => "\\wedge",
+        '∨' => "\\vee",
+        '⊕' => "\\oplus",
+        '⊗' => "\\otimes",
+        '∘' => "\\circ",
+        '⊥' => "\\perp",
+        '∥' => "\\parallel",
+        '∠' => "\\angle",
+        '△' => "\\triangle",
+        '→' => "\\rightarrow",
+        '←' => "\\leftarrow",
+        '↔' => "\\leftrightarrow",
+        '⇒' => "\\Rightarrow",
+        '⇐' => "\\Leftarrow",
+        '⇔' => "\\Leftrightarrow",
+        '↦' => "\\mapsto",
+        '…' => "\\ldots",
+        '⋯' => "\\cdots",
+        '⋮' => "\\vdots",
+        '⋱' => "\\ddots",
+        '∑' => "\\sum",
+        '∏' => "\\prod",
+        '∐' => "\\coprod",
+        '∫' => "\\int",
+        '∬' => "\\iint",
+        '∭' => "\\iiint",
+        '∮' => "\\oint",
+        '⋃' => "\\bigcup",
+        '⋂' => "\\bigcap",
+        '⋁' => "\\bigvee",
+        '⋀' => "\\bigwedge",
+        '√' => "\\surd",
+        'ℏ' => "\\hbar",
+        'ℓ' => "\\ell",
+        'ℝ' => "\\mathbb{R}",
+        'ℕ' => "\\mathbb{N}",
+        'ℤ' => "\\mathbb{Z}",
+        'ℚ' => "\\mathbb{Q}",
+        'ℂ' => "\\mathbb{C}",
+        '⟨' | '〈' => "\\langle",
+        '⟩' | '〉' => "\\rangle",
+        '‖' => "\\|",
+        _ => return c.to_string(),
+    };
+    command.to_string()
+}
+
+// ---- MathML ----
+
+fn to_mathml(root: &Node, display: bool) -> String {
+    let mut out = String::from("<math xmlns=\"http://www.w3.org/1998/Math/MathML\"");
+    if display {
+        out.push_str(" display=\"block\"");
+    }
+    out.push('>');
+    mathml_children(Some(root), &mut out);
+    out.push_str("</math>");
+    out
+}
+
+fn mathml_children(node: Option<&Node>, out: &mut String) {
+    for child in node.into_iter().flat_map(Node::content) {
+        mathml(child, out);
+    }
+}
+
+/// 将元素内容作为单个 `mrow`，MathML 布局元素的参数必须如此
+fn mathml_row(node: Option<&Node>, out: &mut String) {
+    match node.and_then(Node::function_name) {
+        Some(function) => out.push_str(&format!("<mi>{}</mi>", function)),
+        None => {
+            out.push_str("<mrow>");
+            mathml_children(node, out);
+            out.push_str("</mrow>");
+        }
+    }
+}
+
+fn mathml(node: &Node, out: &mut String) {
+    let arg = |name: &str, out: &mut String| mathml_row(node.child(name), out);
+    match node.name.as_str() {
+        "r" => mathml_run(node, out),
+        "f" => match node.property("type") {
+            Some("lin" | "skw") => {
+                out.push_str("<mrow>");
+                arg("num", out);
+                out.push_str("<mo>/</mo>");
+                arg("den", out);
+                out.push_str("</mrow>");
+            }
+            fraction_type => {
+                out.push_str(if fraction_type == Some("noBar") {
+                    "<mfrac linethickness=\"0\">"
+                } else {
+                    "<mfrac>"
+                });
+                arg("num", out);
+                arg("den", out);
+                out.push_str("</mfrac>");
+            }
+        },
+        "rad" => {
+            let hidden = node.flag("degHide") || node.child("deg").is_none_or(|deg| deg.content().next().is_none());
+            if hidden {
+                out.push_str("<msqrt>");
+                arg("e", out);
+                out.push_str("</msqrt>");
+            } else {
+                out.push_str("<mroot>");
+                arg("e", out);
+                arg("deg", out);
+                out.push_str("</mroot>");
+            }
+        }
+        "sSup" | "sSub" | "sSubSup" => {
+            let tag = &node.name[1..].to_lowercase();
+            let tag = format!("m{}", tag);
+            out.push_str(&format!("<{}>", tag));
+            arg("e", out);
+            if node.name != "sSup" {
+                arg("sub", out);
+            }
+            if node.name != "sSub" {
+                arg("sup", out);
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+        "sPre" => {
+            out.push_str("<mmultiscripts>");
+            arg("e", out);
+            out.push_str("<mprescripts/>");
+            arg("sub", out);
+            arg("sup", out);
+            out.push_str("</mmultiscripts>");
+        }
+        "nary" => {
+            let operator = node.property("chr").unwrap_or("∫");
+            let has = |name: &str, hide: &str| {
+                !node.flag(hide) && node.child(name).is_some_and(|script| script.content().next().is_some())
+            };
+            let (sub, sup) = (has("sub", "subHide"), has("sup", "supHide"));
+            let tag = match (node.property("limLoc") == Some("undOvr"), sub, sup) {
+                (true, true, true) => Some("munderover"),
+                (true, true, false) => Some("munder"),
+                (true, false, true) => Some("mover"),
+                (false, true, true) => Some("msubsup"),
+                (false, true, false) => Some("msub"),
+                (false, false, true) => Some("msup"),
+                _ => None,
+            };
+            out.push_str("<mrow>");
+            if let Some(tag) = tag {
+                out.push_str(&format!("<{}>", tag));
+            }
+            out.push_str(&format!("<mo>{}</mo>", escape_xml(operator)));
+            if sub {
+                arg("sub", out);
+            }
+            if sup {
+                arg("sup", out);
+            }
+            if let Some(tag) = tag {
+                out.push_str(&format!("</{}>", tag));
+            }
+            arg("e", out);
+            out.push_str("</mrow>");
+        }
+        "d" => {
+            let fence = |chr: &str, out: &mut String| {
+                if !chr.is_empty() {
+                    out.push_str(&format!("<mo>{}</mo>", escape_xml(chr)));
+                }
+            };
+            out.push_str("<mrow>");
+            fence(node.property("begChr").unwrap_or("("), out);
+            for (index, item) in node.children_named("e").enumerate() {
+                if index > 0 {
+                    fence(node.property("sepChr").unwrap_or("|"), out);
+                }
+                mathml_row(Some(item), out);
+            }
+            fence(node.property("endChr").unwrap_or(")"), out);
+            out.push_str("</mrow>");
+        }
+        "m" | "eqArr" => {
+            out.push_str("<mtable>");
+            let rows: Vec<Vec<&Node>> = if node.name == "m" {
+                node.children_named("mr").map(|row| row.children_named("e").collect()).collect()
+            } else {
+                node.children_named("e").map(|e| vec![e]).collect()
+            };
+            for row in rows {
+                out.push_str("<mtr>");
+                for cell in row {
+                    out.push_str("<mtd>");
+                    mathml_children(Some(cell), out);
+                    out.push_str("</mtd>");
+                }
+                out.push_str("</mtr>");
+            }
+            out.push_str("</mtable>");
+        }
+        "func" => {
+            out.push_str("<mrow>");
+            arg("fName", out);
+            out.push_str("<mo>&#x2061;</mo>");
+            arg("e", out);
+            out.push_str("</mrow>");
+        }
+        "limLow" | "limUpp" => {
+            let tag = if node.name == "limLow" { "munder" } else { "mover" };
+            out.push_str(&format!("<{}>", tag));
+            arg("e", out);
+            arg("lim", out);
+            out.push_str(&format!("</{}>", tag));
+        }
+        "acc" => {
+            let accent = node.property("chr").and_then(|chr| chr.chars().next()).unwrap_or('\u{302}');
+            out.push_str("<mover accent=\"true\">");
+            arg("e", out);
+            out.push_str(&format!("<mo>{}</mo>", escape_xml(&spacing_accent(accent).to_string())));
+            out.push_str("</mover>");
+        }
+        "bar" => {
+            let (tag, mark) = match node.property("pos") {
+                Some("top") => ("mover", "&#xAF;"),
+                _ => ("munder", "_"),
+            };
+            out.push_str(&format!("<{}>", tag));
+            arg("e", out);
+            out.push_str(&format!("<mo>{}</mo></{}>", mark, tag));
+        }
+        "groupChr" => {
+            let character = node.property("chr").unwrap_or("\u{23DF}");
+            let tag = if node.property("pos") == Some("top") { "mover" } else { "munder" };
+            out.push_str(&format!("<{}>", tag));
+            arg("e", out);
+            out.push_str(&format!("<mo>{}</mo></{}>", escape_xml(character), tag));
+        }
+        "borderBox" => {
+            out.push_str("<menclose notation=\"box\">");
+            arg("e", out);
+            out.push_str("</menclose>");
+        }
+        _ => mathml_children(Some(node), out),
+    }
+}
+
+/// run 的 MathML 记号：数字为 `mn`，字母为 `mi`，其余为 `mo`
+fn mathml_run(node: &Node, out: &mut String) {
+    let text = node.run_text();
+    if node.is_normal_text() {
+        out.push_str(&format!("<mtext>{}</mtext>", escape_xml(&text)));
+        return;
+    }
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            let mut number = c.to_string();
+            while let Some(next) = chars.next_if(|next| next.is_ascii_digit() || *next == '.') {
+                number.push(next);
+            }
+            out.push_str(&format!("<mn>{}</mn>", number));
+        } else if c.is_alphabetic() {
+            out.push_str(&format!("<mi>{}</mi>", c));
+        } else if !c.is_whitespace() {
+            out.push_str(&format!("<mo>{}</mo>", escape_xml(&c.to_string())));
+        }
+    }
+}
+
+/// 组合重音符的非组合形式，`mover` 中需要此形式
+fn spacing_accent(accent: char) -> char {
+    match accent {
+        '\u{302}' => '^',
+        '\u{303}' => '~',
+        '\u{304}' | '\u{305}' => '¯',
+        '\u{307}' => '˙',
+        '\u{308}' => '¨',
+        '\u{20DB}' => '⋯',
+        '\u{20D7}' => '→',
+        '\u{301}' => '´',
+        '\u{300}' => '`',
+        '\u{306}' => '˘',
+        '\u{30C}' => 'ˇ',
+        other => other,
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::package::scan_body_paragraphs;
+
+    fn equation(body: &str) -> Equation {
+        RawEquation {
+            run: 0,
+            display: false,
+            omml: format!("<m:oMath>{}</m:oMath>", body),
+        }
+        .to_equation()
+    }
+
+    fn run(text: &str) -> String {
+        format!("<m:r><m:t>{}</m:t></m:r>", text)
+    }
+
+    #[test]
+    fn test_scan_positions_equations_by_run() {
+        let xml = format!(
+            r#"<w:document><w:body>
+            <w:p><w:r><w:t>Area is </w:t></w:r><m:oMath>{}</m:oMath><w:r><w:t> here.</w:t></w:r></w:p>
+            <w:tbl><w:tr><w:tc><w:p><m:oMath>{}</m:oMath></w:p></w:tc></w:tr></w:tbl>
+            <w:p><m:oMathPara><m:oMath>{}</m:oMath></m:oMathPara></w:p>
+            </w:body></w:document>"#,
+            run("x"),
+            run("y"),
+            run("z")
+        );
+        let mut collector = EquationCollector::new(&xml);
+        scan_body_paragraphs(&xml, &mut [&mut collector]).unwrap();
+        let paragraphs = collector.paragraphs;
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(paragraphs[0].len(), 1);
+        assert_eq!(paragraphs[0][0].run, 1);
+        assert!(!paragraphs[0][0].display);
+        assert_eq!(paragraphs[0][0].omml, format!("<m:oMath>{}</m:oMath>", run("x")));
+        assert_eq!(paragraphs[1][0].run, 0);
+        assert!(paragraphs[1][0].display);
+    }
+
+    #[test]
+    fn test_fraction_radical_and_scripts() {
+        let body = format!(
+            "<m:f><m:num>{}</m:num><m:den>{}</m:den></m:f>{}<m:rad><m:radPr><m:degHide m:val=\"1\"/></m:radPr><m:deg/><m:e><m:sSup><m:e>{}</m:e><m:sup>{}</m:sup></m:sSup></m:e></m:rad>",
+            run("a+b"),
+            run("2"),
+            run("="),
+            run("α"),
+            run("2")
+        );
+        let eq = equation(&body);
+        assert_eq!(eq.latex, "\\frac{a+b}{2}=\\sqrt{\\alpha^{2}}");
+        assert_eq!(
+            eq.mathml,
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow>\
+             <mrow><mn>2</mn></mrow></mfrac><mo>=</mo><msqrt><mrow><msup><mrow><mi>α</mi></mrow><mrow><mn>2</mn></mrow>\
+             </msup></mrow></msqrt></math>"
+        );
+    }
+
+    #[test]
+    fn test_nary_delimiters_and_functions() {
+        let sum = format!(
+            "<m:nary><m:naryPr><m:chr m:val=\"∑\"/><m:limLoc m:val=\"undOvr\"/></m:naryPr><m:sub>{}</m:sub><m:sup>{}</m:sup><m:e><m:sSub><m:e>{}</m:e><m:sub>{}</m:sub></m:sSub></m:e></m:nary>",
+            run("i=1"),
+            run("n"),
+            run("x"),
+            run("i")
+        );
+        assert_eq!(equation(&sum).latex, "\\sum_{i=1}^{n} x_{i}");
+        assert!(equation(&sum).mathml.contains("<munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow>"));
+
+        let interval = format!(
+            "<m:d><m:dPr><m:begChr m:val=\"[\"/><m:sepChr m:val=\",\"/></m:dPr><m:e>{}</m:e><m:e>{}</m:e></m:d>",
+            run("0"),
+            run("π")
+        );
+        assert_eq!(equation(&interval).latex, "\\left[0,\\pi\\right)");
+
+        let sine = format!("<m:func><m:fName>{}</m:fName><m:e>{}</m:e></m:func>", run("sin"), run("θ"));
+        assert_eq!(equation(&sine).latex, "\\sin\\theta");
+        assert!(equation(&sine).mathml.contains("<mi>sin</mi><mo>&#x2061;</mo>"));
+
+        let limit = format!(
+            "<m:func><m:fName><m:limLow><m:e>{}</m:e><m:lim>{}</m:lim></m:limLow></m:fName><m:e>{}</m:e></m:func>",
+            run("lim"),
+            run("n→∞"),
+            run("a")
+        );
+        assert_eq!(equation(&limit).latex, "\\lim_{n\\rightarrow\\infty} a");
+    }
+
+    #[test]
+    fn test_matrix_accent_and_text() {
+        let matrix = format!(
+            "<m:m><m:mr><m:e>{}</m:e><m:e>{}</m:e></m:mr><m:mr><m:e>{}</m:e><m:e>{}</m:e></m:mr></m:m>",
+            run("1"),
+            run("0"),
+            run("0"),
+            run("1")
+        );
+        assert_eq!(equation(&matrix).latex, "\\begin{matrix}1 & 0 \\\\ 0 & 1\\end{matrix}");
+        assert!(equation(&matrix).mathml.contains("<mtable><mtr><mtd><mn>1</mn></mtd>"));
+
+        let accent = format!("<m:acc><m:accPr><m:chr m:val=\"\u{20D7}\"/></m:accPr><m:e>{}</m:e></m:acc>", run("v"));
+        assert_eq!(equation(&accent).latex, "\\vec{v}");
+
+        let text = "<m:r><m:rPr><m:nor/></m:rPr><m:t>rate &amp; time</m:t></m:r>";
+        assert_eq!(equation(text).latex, "\\text{rate \\& time}");
+        assert!(equation(text).mathml.contains("<mtext>rate &amp; time</mtext>"));
+    }
+}
@@ -1,11 +1,28 @@
 use crate::error::{DocParserError, Result};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 pub mod docx;
 pub mod doc;
+pub mod formatting;
+pub mod images;
+pub mod inline;
+pub mod math;
+pub mod notes;
+pub mod numbering;
+pub mod outline;
+pub mod package;
+pub mod properties;
+pub mod shapes;
+pub mod styles;
+#[cfg(feature = "legacy-doc")]
+pub mod summary_info;
 pub mod text_extractor;
 pub mod table;
+#[cfg(feature = "legacy-doc")]
+pub mod word97;
 
+pub use outline::{Outline, OutlineEntry};
 pub use text_extractor::TextExtractor;
 pub use table::{TableData, TableExtractor, TableExtractionConfig, TableRow, TableCell, CellFormatting, TableExtractionMode, TableOutputFormat};
 
@@ -15,6 +32,12 @@ pub struct DocData {
     pub metadata: DocMetadata,
     pub sections: Vec<DocSection>,
     pub raw_text: String,  // 纯文本内容
+    /// 脚注正文（按文档中的顺序）
+    pub footnotes: Vec<Note>,
+    /// 尾注正文
+    pub endnotes: Vec<Note>,
+    /// 审阅批注
+    pub comments: Vec<Comment>,
 }
 
 #[derive(Debug, Clone)]
@@ -28,18 +51,201 @@ pub struct DocMetadata {
     pub paragraph_count: usize,
     pub page_count: Option<usize>,
     pub character_count: usize,
+    /// 关键词原文（通常以逗号或分号分隔）
+    pub keywords: Option<String>,
+    /// 摘要/备注（`dc:description`）
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub last_modified_by: Option<String>,
+    /// 修订次数（`cp:revision`）
+    pub revision: Option<u32>,
+    /// 基于的模板，如 "Normal.dotm"
+    pub template: Option<String>,
+    /// 生成文档的应用程序
+    pub application: Option<String>,
+    /// Word 保存时记录的字数；`word_count` 为解析时统计的值
+    pub recorded_word_count: Option<usize>,
+    /// 自定义属性（custom.xml），值统一为文本
+    pub custom_properties: BTreeMap<String, String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct DocSection {
     pub section_type: SectionType,
     pub content: String,
     pub level: Option<u8>,  // 用于标题级别
+    /// 所有文本片段共有的格式
     pub formatting: Option<FormatInfo>,
+    /// 按格式切分的文本片段，拼接后等于 `content`（表格等非段落内容为空）
+    pub spans: Vec<TextSpan>,
+    /// 列表项的编号信息（编号标题也会带有此信息）
+    pub list: Option<ListInfo>,
+    /// 在本段开始的书签名（内部链接的目标）
+    pub bookmarks: Vec<String>,
+    /// 本段中的脚注、尾注和批注引用
+    pub notes: Vec<NoteReference>,
+    /// 图片段落（`SectionType::Image`）的图片
+    pub image: Option<ImageInfo>,
+    /// 表格（`SectionType::Table`）的结构化数据；`content` 为其纯文本形式
+    pub table: Option<TableData>,
+    /// 图形（`SectionType::Shape`）的种类和名称；`content` 为其文字，每段一行
+    pub shape: Option<ShapeInfo>,
 }
 
-#[derive(Debug, Clone)]
+impl DocData {
+    /// 各段在 `content` 中的起始字符偏移（含段首空白）；页眉页脚、图片等不在正文中的段为 None
+    pub fn section_offsets(&self) -> Vec<Option<usize>> {
+        let mut cursor = 0;
+        let mut char_cursor = 0;
+        self.sections
+            .iter()
+            .map(|section| {
+                let in_body = !matches!(section.section_type, SectionType::Header | SectionType::Footer | SectionType::Image);
+                let text = section.content.trim();
+                if !in_body || text.is_empty() {
+                    return None;
+                }
+                // 正文各段按顺序写入 content，从上一段末尾继续查找；
+                // 段首空白可能已被裁掉，此时按去掉空白后的位置回推
+                let rest = &self.content[cursor..];
+                let (found, len, leading) = match rest.find(section.content.as_str()) {
+                    Some(found) => (found, section.content.len(), 0),
+                    None => (rest.find(text)?, text.len(), section.leading_whitespace()),
+                };
+                let start = char_cursor + rest[..found].chars().count();
+                char_cursor = start + rest[found..found + len].chars().count();
+                cursor += found + len;
+                Some(start.saturating_sub(leading))
+            })
+            .collect()
+    }
+}
+
+impl DocSection {
+    /// 只含类型和文字的段，其余字段为空
+    pub fn new(section_type: SectionType, content: impl Into<String>) -> Self {
+        DocSection {
+            section_type,
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 段首空白的字符数：`content.trim()` 从段偏移加上此值处开始
+    pub fn leading_whitespace(&self) -> usize {
+        self.content.chars().take_while(|c| c.is_whitespace()).count()
+    }
+
+    /// 段落中的链接及其文字，相邻且指向同一目标的片段合并为一个链接
+    pub fn links(&self) -> Vec<(String, &Link)> {
+        let mut links: Vec<(String, &Link)> = Vec::new();
+        let mut continues = false;
+        for span in &self.spans {
+            if let Some(link) = &span.link {
+                match links.last_mut() {
+                    Some((text, last)) if continues && *last == link => text.push_str(&span.text),
+                    _ => links.push((span.text.clone(), link)),
+                }
+            }
+            continues = span.link.is_some();
+        }
+        links
+    }
+
+    /// 段落中的公式及其在 `content` 中的字符位置
+    pub fn equations(&self) -> Vec<(usize, &Equation)> {
+        let mut offset = 0;
+        let mut equations = Vec::new();
+        for span in &self.spans {
+            if let Some(equation) = &span.equation {
+                equations.push((offset, equation));
+            }
+            offset += span.text.chars().count();
+        }
+        equations
+    }
+}
+
+/// 列表项信息，来自 `w:numPr` 与 numbering.xml
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListInfo {
+    /// 嵌套层级，从 0 开始
+    pub level: u8,
+    /// 有序列表（项目符号列表为 false）
+    pub ordered: bool,
+    /// 计算出的编号文本，如 "1."、"a)"、"iv."，项目符号列表为符号本身
+    pub label: String,
+    /// 当前层级的计数值
+    pub number: u32,
+}
+
+/// 文档中的图片（`w:drawing` 或 VML `w:pict`）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageInfo {
+    /// 包内部件名（如 "word/media/image1.png"）；链接的外部图片为其地址
+    pub target: String,
+    /// 图片链接到包外的文件而非嵌入
+    pub external: bool,
+    /// 替代文字（`wp:docPr` 的 descr）
+    pub alt_text: Option<String>,
+    /// 标题（`wp:docPr` 的 title）
+    pub title: Option<String>,
+    /// 绘图对象名称，如 "Picture 1"
+    pub name: Option<String>,
+    /// 显示尺寸（像素，按 96 DPI 换算）
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// 浮动图片（`wp:anchor`），而非嵌入行内
+    pub anchored: bool,
+    /// 导出后的文件路径，见 [`DocParser::with_image_dir`]
+    pub path: Option<String>,
+}
+
+/// 含文字的图形，紧随其所在段落
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeInfo {
+    pub kind: ShapeKind,
+    /// 绘图对象名称，如 "Text Box 2"
+    pub name: Option<String>,
+    /// 浮动图形（`wp:anchor`），而非嵌入行内
+    pub anchored: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeKind {
+    /// 文本框（`wps:txbx` 或 VML `v:textbox`）
+    TextBox,
+    /// 绘图画布或组合图形，可包含多个文本框
+    Canvas,
+    /// SmartArt 图形，文字取自其数据部件
+    SmartArt,
+}
+
+/// 文本框、形状和 SmartArt 中文字的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShapeMode {
+    /// 作为独立的段输出，紧随所在段落
+    #[default]
+    Include,
+    /// 忽略图形中的文字
+    Skip,
+}
+
+impl ImageInfo {
+    /// 输出中引用的位置：优先使用导出的文件，否则为包内路径或外部地址
+    pub fn source(&self) -> &str {
+        self.path.as_deref().unwrap_or(&self.target)
+    }
+
+    /// 图片的文字说明：替代文字、标题或对象名称
+    pub fn description(&self) -> Option<&str> {
+        self.alt_text.as_deref().or(self.title.as_deref()).or(self.name.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub enum SectionType {
+    #[default]
     Paragraph,
     Heading(u8),      // 1-6 标题级别
     Table,
@@ -48,22 +254,200 @@ pub enum SectionType {
     Footer,
     Header,
     Hyperlink,
+    Shape,            // 文本框、绘图画布、SmartArt 中的文字
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct FormatInfo {
     pub bold: bool,
     pub italic: bool,
     pub underline: bool,
-    pub font_size: Option<u32>,
+    pub strike: bool,
+    /// 字号（磅）
+    pub font_size: Option<f32>,
     pub font_family: Option<String>,
+    /// 文字颜色，`#RRGGBB`
+    pub color: Option<String>,
+    /// 突出显示颜色，`#RRGGBB`
+    pub highlight: Option<String>,
+    pub vertical_align: VerticalAlign,
+}
+
+impl FormatInfo {
+    /// 没有任何可见格式
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerticalAlign {
+    #[default]
+    Baseline,
+    Superscript,
+    Subscript,
+}
+
+/// 引用的对象种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteKind {
+    Footnote,
+    Endnote,
+    Comment,
+}
+
+/// 段落中对脚注、尾注或批注的引用
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteReference {
+    pub kind: NoteKind,
+    pub id: usize,
+    /// 引用在 `content` 中的字符偏移；批注取其标注范围的结束位置
+    pub offset: usize,
+}
+
+/// 脚注或尾注
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub id: usize,
+    pub content: String,
+}
+
+/// 审阅批注
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub id: usize,
+    pub author: String,
+    pub date: Option<String>,
+    pub content: String,
+    /// 批注标注的正文
+    pub anchor_text: Option<String>,
+    /// 回复所针对的批注
+    pub parent_id: Option<usize>,
+}
+
+/// 一段格式一致的文本（对应一个或多个相邻的 run）
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub formatting: FormatInfo,
+    /// 所在超链接
+    pub link: Option<Link>,
+    /// 所属修订；仅在 [`RevisionMode::Show`] 下标注
+    pub revision: Option<Revision>,
+    /// 公式；此时 `text` 为其 LaTeX 表示
+    pub equation: Option<Equation>,
+}
+
+/// 公式（Office Math），同时给出 LaTeX 和 MathML 表示并保留原始 OMML
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equation {
+    /// LaTeX 表示，不含 `$` 定界符
+    pub latex: String,
+    /// MathML `<math>` 元素
+    pub mathml: String,
+    /// 原始 `m:oMath` 元素
+    pub omml: String,
+    /// 独立成行的公式（位于 `m:oMathPara` 中）
+    pub display: bool,
+}
+
+/// 修订（修订模式下的插入或删除）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub kind: RevisionKind,
+    pub author: String,
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionKind {
+    Insertion,
+    Deletion,
+}
+
+/// 修订的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RevisionMode {
+    /// 接受所有修订：保留插入、丢弃删除，得到最终文本
+    #[default]
+    Accept,
+    /// 拒绝所有修订：丢弃插入、保留删除，得到原始文本
+    Reject,
+    /// 同时保留插入和删除，并标注作者和时间
+    Show,
+}
+
+/// 超链接目标：外部地址、文档内书签，或两者（带片段的外部地址）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    /// 外部地址，已由关系 ID 解析
+    pub url: Option<String>,
+    /// 书签名（`w:anchor`）
+    pub anchor: Option<String>,
+}
+
+impl Link {
+    /// 链接地址；内部书签表示为 `#name`
+    pub fn href(&self) -> String {
+        match (&self.url, &self.anchor) {
+            (Some(url), Some(anchor)) => format!("{}#{}", url, anchor),
+            (Some(url), None) => url.clone(),
+            (None, Some(anchor)) => format!("#{}", anchor),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// 文档解析选项
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// 文档完全没有使用标题样式时，按段落长度和关键词猜测标题
+    pub heading_heuristic: bool,
+    /// 修订（插入和删除）的处理方式
+    pub revisions: RevisionMode,
+    /// 将嵌入图片写入此目录，并按路径引用
+    pub image_dir: Option<PathBuf>,
+    /// 文本框、绘图画布和 SmartArt 中的文字是否作为图形段
+    pub shapes: ShapeMode,
 }
 
-pub struct DocParser;
+pub struct DocParser {
+    options: ParseOptions,
+}
 
 impl DocParser {
     pub fn new() -> Self {
-        Self
+        Self {
+            options: ParseOptions::default(),
+        }
+    }
+
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self { options }
+    }
+
+    /// Enable the length/keyword heading heuristic as a fallback for unstyled documents
+    pub fn with_heading_heuristic(mut self, enabled: bool) -> Self {
+        self.options.heading_heuristic = enabled;
+        self
+    }
+
+    /// Choose whether tracked changes are accepted, rejected or shown with attribution
+    pub fn with_revisions(mut self, mode: RevisionMode) -> Self {
+        self.options.revisions = mode;
+        self
+    }
+
+    /// Include or skip the text of text boxes, drawing canvases and SmartArt diagrams
+    pub fn with_shapes(mut self, mode: ShapeMode) -> Self {
+        self.options.shapes = mode;
+        self
+    }
+
+    /// Extract embedded images into `dir`; image sections then reference the written files
+    pub fn with_image_dir<P: Into<PathBuf>>(mut self, dir: Option<P>) -> Self {
+        self.options.image_dir = dir.map(Into::into);
+        self
     }
 
     /// 解析DOC文件并返回结构化数据
@@ -83,8 +467,8 @@ impl DocParser {
             .to_lowercase();
 
         match extension.as_str() {
-            "docx" => docx::parse_docx(file_path),
-            "doc" => doc::parse_doc(file_path),
+            "docx" => docx::parse_docx(file_path, &self.options),
+            "doc" => doc::parse_doc(file_path, &self.options),
             _ => Err(DocParserError::UnsupportedFormat {
                 format: extension,
                 file: file_path.display().to_string(),
@@ -164,6 +548,74 @@ impl DocParser {
         Ok(doc_data.sections)
     }
 
+    /// Extracts every body table as structured [`TableData`], in document order.
+    ///
+    /// Tables are titled from their "Caption" style paragraphs and numbered
+    /// `table-1`, `table-2`, ... in [`TableData::table_id`]. Only DOCX files
+    /// are supported.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use doc_parser::parser::DocParser;
+    /// use doc_parser::parser::table::TableOutputFormat;
+    /// use doc_parser::output::TableWriter;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let parser = DocParser::new();
+    /// let writer = TableWriter::new(TableOutputFormat::CSV);
+    /// for table in parser.extract_tables("document.docx")? {
+    ///     print!("{}", writer.table_to_string(&table)?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extract_tables<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<TableData>> {
+        let file_path = file_path.as_ref();
+        if !file_path.exists() {
+            return Err(DocParserError::FileNotFound {
+                file: file_path.display().to_string(),
+            });
+        }
+
+        let extension = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match extension.as_str() {
+            "docx" => docx::extract_docx_tables(file_path, &self.options),
+            _ => Err(DocParserError::UnsupportedFormat {
+                format: extension,
+                file: file_path.display().to_string(),
+            }),
+        }
+    }
+
+    /// Builds the heading tree of a document.
+    ///
+    /// Headings are numbered by their position in the tree ("2.3.1") and carry
+    /// their character offset in [`DocData::content`] and a unique slug anchor,
+    /// the same anchor `--toc` links to in Markdown and HTML output.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use doc_parser::parser::DocParser;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let parser = DocParser::new();
+    /// for (depth, entry) in parser.outline("spec.docx")?.flatten() {
+    ///     println!("{}{} {} (offset {})", "  ".repeat(depth), entry.number, entry.title, entry.offset);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn outline<P: AsRef<Path>>(&self, file_path: P) -> Result<Outline> {
+        let doc_data = self.parse(file_path)?;
+        Ok(Outline::from_doc(&doc_data))
+    }
+
     /// Extracts document metadata without parsing the full content.
     ///
     /// This method extracts only the metadata (title, author, word count, etc.)
@@ -0,0 +1,175 @@
+//! 脚注、尾注和批注。
+//!
+//! docx-rs 能读取批注，但会丢弃 `w:footnoteReference`/`w:endnoteReference` run，
+//! 也不加载脚注/尾注部件。因此引用位置通过直接扫描文档部件得到：
+//! 每个引用按其所在段落和 run 序号定位，再由行内收集器换算为字符偏移
+//! （见 [`ParagraphContent::run_ends`]）。
+//!
+//! [`ParagraphContent::run_ends`]: super::inline::ParagraphContent::run_ends
+
+use super::inline::CommentMark;
+use super::package::{attr, ParagraphCollector, ScanPosition};
+use super::{NoteKind, NoteReference};
+use docx_rs::{ElementReader, XmlEvent};
+use quick_xml::events::BytesStart;
+use std::collections::HashMap;
+
+/// 扫描得到的注释引用：所在段落中的 run 序号、种类和注释 ID
+pub type RawReference = (usize, NoteKind, usize);
+
+/// 收集每个正文段落中的脚注和尾注引用
+/// （见 [`super::package::scan_body_paragraphs`]）
+#[derive(Debug, Default)]
+pub struct NoteCollector {
+    pub paragraphs: Vec<Vec<RawReference>>,
+}
+
+impl ParagraphCollector for NoteCollector {
+    fn paragraph(&mut self) {
+        self.paragraphs.push(Vec::new());
+    }
+
+    fn start(&mut self, element: &BytesStart, _empty: bool, position: &ScanPosition) {
+        let kind = match element.local_name().as_ref() {
+            b"footnoteReference" => NoteKind::Footnote,
+            b"endnoteReference" => NoteKind::Endnote,
+            _ => return,
+        };
+        if let (true, Some(id), Some(current)) = (
+            position.in_run,
+            attr(element, "id").and_then(|id| id.parse().ok()),
+            self.paragraphs.last_mut(),
+        ) {
+            current.push((position.runs - 1, kind, id));
+        }
+    }
+}
+
+/// 读取脚注或尾注部件中的注释（`element` 为 "footnote" 或 "endnote"），
+/// 跳过分隔符注释
+pub fn read_notes(xml: &str, element: &str) -> Result<Vec<(usize, Vec<docx_rs::Paragraph>)>, docx_rs::ReaderError> {
+    let mut parser = docx_rs::EventReader::new(xml.as_bytes());
+    let mut notes = Vec::new();
+    let mut current: Option<(usize, Vec<docx_rs::Paragraph>)> = None;
+
+    loop {
+        let event = parser.next_event().map_err(|_| docx_rs::ReaderError::XMLReadError)?;
+        match event {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                let value = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|a| a.name.local_name == key)
+                        .map(|a| a.value.clone())
+                };
+                if name.local_name == element {
+                    let regular = value("type").is_none_or(|t| t == "normal");
+                    current = value("id")
+                        .and_then(|id| id.parse().ok())
+                        .filter(|_| regular)
+                        .map(|id| (id, Vec::new()));
+                } else if name.local_name == "p" {
+                    let paragraph = docx_rs::Paragraph::read(&mut parser, &attributes)?;
+                    if let Some((_, paragraphs)) = current.as_mut() {
+                        paragraphs.push(paragraph);
+                    }
+                }
+            }
+            XmlEvent::EndElement { name } if name.local_name == element => {
+                notes.extend(current.take());
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(notes)
+}
+
+/// 收集每条批注所锚定的文字；范围可以跨段落
+#[derive(Debug, Default)]
+pub struct CommentAnchors {
+    open: HashMap<usize, String>,
+    closed: HashMap<usize, String>,
+}
+
+impl CommentAnchors {
+    /// 处理一个段落，返回范围在此段结束的批注引用
+    pub fn paragraph(&mut self, text: &str, marks: &[CommentMark]) -> Vec<NoteReference> {
+        let chars: Vec<char> = text.chars().collect();
+        let slice = |from: usize, to: usize| -> String { chars[from.min(chars.len())..to.min(chars.len())].iter().collect() };
+
+        // 之前段落中尚未结束的范围从段首继续
+        let mut starts: HashMap<usize, usize> = self.open.keys().map(|id| (*id, 0)).collect();
+        let mut references = Vec::new();
+        for mark in marks {
+            if mark.start {
+                starts.insert(mark.id, mark.offset);
+                self.open.entry(mark.id).or_default();
+            } else if let Some(start) = starts.remove(&mark.id) {
+                let mut anchor = self.open.remove(&mark.id).unwrap_or_default();
+                anchor.push_str(&slice(start, mark.offset));
+                self.closed.insert(mark.id, anchor.trim().to_string());
+                references.push(NoteReference {
+                    kind: NoteKind::Comment,
+                    id: mark.id,
+                    offset: mark.offset,
+                });
+            }
+        }
+        for (id, start) in starts {
+            if let Some(anchor) = self.open.get_mut(&id) {
+                anchor.push_str(&slice(start, chars.len()));
+                anchor.push('\n');
+            }
+        }
+        references
+    }
+
+    /// 批注范围结束后其锚定的文字
+    pub fn anchor_text(&self, id: usize) -> Option<&str> {
+        self.closed.get(&id).map(String::as_str).filter(|text| !text.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::package::scan_body_paragraphs;
+
+    #[test]
+    fn test_note_references() {
+        let xml = r#"<w:document xmlns:w="w"><w:body><w:p><w:r><w:t>One</w:t></w:r><w:r><w:footnoteReference w:id="1"/></w:r></w:p><w:tbl><w:tr><w:tc><w:p><w:r><w:footnoteReference w:id="9"/></w:r></w:p></w:tc></w:tr></w:tbl><w:p/><w:p><w:hyperlink><w:r><w:t>a</w:t></w:r></w:hyperlink><w:r/><w:r><w:endnoteReference w:id="2"/></w:r></w:p></w:body></w:document>"#;
+
+        let mut notes = NoteCollector::default();
+        scan_body_paragraphs(xml, &mut [&mut notes]).unwrap();
+        let references = notes.paragraphs;
+        assert_eq!(references.len(), 3);
+        assert_eq!(references[0], vec![(1, NoteKind::Footnote, 1)]);
+        assert!(references[1].is_empty());
+        assert_eq!(references[2], vec![(2, NoteKind::Endnote, 2)]);
+    }
+
+    #[test]
+    fn test_read_notes_skips_separators() {
+        let xml = r#"<w:footnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator/></w:r></w:p></w:footnote><w:footnote w:id="1"><w:p><w:r><w:t>First note</w:t></w:r></w:p></w:footnote></w:footnotes>"#;
+
+        let notes = read_notes(xml, "footnote").unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].0, 1);
+        assert_eq!(notes[0].1.len(), 1);
+    }
+
+    #[test]
+    fn test_comment_anchors_across_paragraphs() {
+        let mark = |id, offset, start| CommentMark { id, offset, start };
+        let mut anchors = CommentAnchors::default();
+
+        let references = anchors.paragraph("Hello world", &[mark(1, 6, true), mark(0, 0, true), mark(0, 5, false)]);
+        assert_eq!(references.iter().map(|r| (r.id, r.offset)).collect::<Vec<_>>(), vec![(0, 5)]);
+        assert_eq!(anchors.anchor_text(0), Some("Hello"));
+        assert_eq!(anchors.anchor_text(1), None);
+
+        anchors.paragraph("Second line", &[mark(1, 6, false)]);
+        assert_eq!(anchors.anchor_text(1), Some("world\nSecond"));
+    }
+}
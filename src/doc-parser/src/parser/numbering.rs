@@ -0,0 +1,400 @@
+//! 来自 `word/numbering.xml` 的列表编号。
+//!
+//! 段落通过 `w:numPr`（`numId` + `ilvl`）直接或经由样式加入列表。`numId` 指向
+//! 一个 `w:num`，后者引用共享的 `w:abstractNum`（其中定义各级别），并可覆盖个别级别。
+//! 计数器属于抽象定义，因此共用同一定义的列表会连续计数，
+//! 除非 `w:startOverride` 重新开始。
+
+use super::package::attr;
+use super::ListInfo;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::{HashMap, HashSet};
+
+/// Word 支持九级列表（`ilvl` 0-8）
+const MAX_LEVELS: usize = 9;
+
+/// 一个 `w:lvl` 定义
+#[derive(Debug, Clone)]
+pub struct NumberingLevel {
+    pub start: u32,
+    /// `w:numFmt`，如 "decimal"、"lowerLetter"、"bullet"
+    pub format: String,
+    /// `w:lvlText`，如 "%1." 或 "%1.%2)"；项目符号列表为符号字符
+    pub text: String,
+    /// `w:lvlRestart`：在此级别（从 1 开始）之后重新计数；0 表示从不重新计数
+    pub restart: Option<u32>,
+    /// `w:isLgl`：所有引用的级别都显示为十进制数字
+    pub legal: bool,
+}
+
+impl Default for NumberingLevel {
+    fn default() -> Self {
+        Self {
+            start: 1,
+            format: "decimal".to_string(),
+            text: String::new(),
+            restart: None,
+            legal: false,
+        }
+    }
+}
+
+/// 一个 `w:num` 实例
+#[derive(Debug, Clone, Default)]
+struct NumberingInstance {
+    abstract_id: String,
+    /// 通过 `w:lvlOverride/w:lvl` 替换的级别
+    level_overrides: HashMap<usize, NumberingLevel>,
+    /// `w:lvlOverride/w:startOverride`
+    start_overrides: HashMap<usize, u32>,
+}
+
+/// 文档中解析出的编号定义
+#[derive(Debug, Clone, Default)]
+pub struct Numbering {
+    abstract_levels: HashMap<String, HashMap<usize, NumberingLevel>>,
+    instances: HashMap<usize, NumberingInstance>,
+}
+
+impl Numbering {
+    /// 解析 `word/numbering.xml` 的内容
+    pub fn parse(xml: &str) -> std::result::Result<Self, quick_xml::Error> {
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::new();
+        let mut numbering = Numbering::default();
+
+        let mut current_abstract: Option<(String, HashMap<usize, NumberingLevel>)> = None;
+        let mut current_num: Option<(usize, NumberingInstance)> = None;
+        let mut override_level: Option<usize> = None;
+        let mut current_level: Option<(usize, NumberingLevel)> = None;
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(ref e) | Event::Empty(ref e) => {
+                    let val = attr(e, "val");
+                    match e.local_name().as_ref() {
+                        b"abstractNum" => {
+                            current_abstract = Some((attr(e, "abstractNumId").unwrap_or_default(), HashMap::new()));
+                        }
+                        b"num" => {
+                            let id = attr(e, "numId").and_then(|v| v.parse().ok()).unwrap_or(0);
+                            current_num = Some((id, NumberingInstance::default()));
+                        }
+                        b"abstractNumId" => {
+                            if let Some((_, instance)) = current_num.as_mut() {
+                                instance.abstract_id = val.unwrap_or_default();
+                            }
+                        }
+                        b"lvlOverride" => {
+                            override_level = attr(e, "ilvl").and_then(|v| v.parse().ok());
+                        }
+                        b"startOverride" => {
+                            if let (Some((_, instance)), Some(level), Some(start)) =
+                                (current_num.as_mut(), override_level, val.and_then(|v| v.parse().ok()))
+                            {
+                                instance.start_overrides.insert(level, start);
+                            }
+                        }
+                        b"lvl" => {
+                            let level = attr(e, "ilvl").and_then(|v| v.parse().ok()).or(override_level).unwrap_or(0);
+                            current_level = Some((level, NumberingLevel::default()));
+                        }
+                        name => {
+                            if let Some((_, level)) = current_level.as_mut() {
+                                match name {
+                                    b"start" => level.start = val.and_then(|v| v.parse().ok()).unwrap_or(1),
+                                    b"numFmt" => level.format = val.unwrap_or_default(),
+                                    b"lvlText" => level.text = val.unwrap_or_default(),
+                                    b"lvlRestart" => level.restart = val.and_then(|v| v.parse().ok()),
+                                    b"isLgl" => level.legal = !matches!(val.as_deref(), Some("0" | "false" | "off")),
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                }
+                Event::End(ref e) => match e.local_name().as_ref() {
+                    b"lvl" => {
+                        if let Some((index, level)) = current_level.take() {
+                            if let Some((_, instance)) = current_num.as_mut() {
+                                instance.level_overrides.insert(index, level);
+                            } else if let Some((_, levels)) = current_abstract.as_mut() {
+                                levels.insert(index, level);
+                            }
+                        }
+                    }
+                    b"lvlOverride" => override_level = None,
+                    b"abstractNum" => {
+                        if let Some((id, levels)) = current_abstract.take() {
+                            numbering.abstract_levels.insert(id, levels);
+                        }
+                    }
+                    b"num" => {
+                        if let Some((id, instance)) = current_num.take() {
+                            numbering.instances.insert(id, instance);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(numbering)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// 列表实例实际使用的级别定义
+    fn level(&self, num_id: usize, level: usize) -> Option<&NumberingLevel> {
+        let instance = self.instances.get(&num_id)?;
+        instance
+            .level_overrides
+            .get(&level)
+            .or_else(|| self.abstract_levels.get(&instance.abstract_id)?.get(&level))
+    }
+}
+
+/// 按顺序遍历文档时的列表计数器
+#[derive(Debug, Default)]
+pub struct ListCounter {
+    /// 按抽象编号 ID 索引的各级当前值
+    counters: HashMap<String, [Option<u32>; MAX_LEVELS]>,
+    /// 已出现的列表实例（起始值覆盖在首次使用时生效）
+    started: HashSet<usize>,
+}
+
+impl ListCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为列表段落推进计数器并计算其编号文本。
+    ///
+    /// `numId` 为 0（取消编号）或列表未知时返回 None。
+    pub fn next(&mut self, numbering: &Numbering, num_id: usize, level: usize) -> Option<ListInfo> {
+        let level = level.min(MAX_LEVELS - 1);
+        let instance = numbering.instances.get(&num_id)?;
+        let definition = numbering.level(num_id, level)?;
+        let counters = self.counters.entry(instance.abstract_id.clone()).or_default();
+
+        // 起始值覆盖在该实例首次使用时重新开始共享的计数器
+        if self.started.insert(num_id) {
+            for &overridden in instance.start_overrides.keys() {
+                if overridden < MAX_LEVELS {
+                    counters[overridden] = None;
+                }
+            }
+        }
+
+        let start_of = |index: usize| {
+            instance
+                .start_overrides
+                .get(&index)
+                .copied()
+                .or_else(|| numbering.level(num_id, index).map(|d| d.start))
+                .unwrap_or(1)
+        };
+
+        let value = match counters[level] {
+            Some(current) => current + 1,
+            None => start_of(level),
+        };
+        counters[level] = Some(value);
+
+        // 更深的级别重新计数，除非其 lvlRestart 另有规定
+        for (deeper, counter) in counters.iter_mut().enumerate().skip(level + 1) {
+            match numbering.level(num_id, deeper).and_then(|d| d.restart) {
+                Some(0) => {}
+                Some(after) if (after as usize) <= level => {}
+                _ => *counter = None,
+            }
+        }
+        let counters = *counters;
+
+        let ordered = !matches!(definition.format.as_str(), "bullet" | "none");
+        let label = if definition.format == "bullet" {
+            bullet_symbol(&definition.text)
+        } else {
+            let mut label = definition.text.clone();
+            for referenced in (0..MAX_LEVELS).rev() {
+                let placeholder = format!("%{}", referenced + 1);
+                if !label.contains(&placeholder) {
+                    continue;
+                }
+                let number = counters[referenced].unwrap_or_else(|| start_of(referenced));
+                let format = match numbering.level(num_id, referenced) {
+                    Some(d) if !(definition.legal && referenced != level) => d.format.as_str(),
+                    _ => "decimal",
+                };
+                label = label.replace(&placeholder, &format_number(number, format));
+            }
+            label
+        };
+
+        Some(ListInfo {
+            level: level as u8,
+            ordered,
+            label,
+            number: value,
+        })
+    }
+}
+
+/// 项目符号的可打印形式；符号字体字形（Symbol/Wingdings 私用区字符、
+/// Courier 的 "o"、Wingdings 的 "§"）映射为外观相近的 Unicode 字符
+fn bullet_symbol(text: &str) -> String {
+    match text.trim() {
+        "" => "•".to_string(),
+        "o" => "◦".to_string(),
+        "§" => "▪".to_string(),
+        symbol if symbol.chars().all(|c| ('\u{E000}'..='\u{F8FF}').contains(&c)) => "•".to_string(),
+        symbol => symbol.to_string(),
+    }
+}
+
+/// 按 `w:numFmt` 样式格式化计数值
+pub fn format_number(value: u32, format: &str) -> String {
+    match format {
+        "lowerLetter" => letters(value).to_lowercase(),
+        "upperLetter" => letters(value),
+        "lowerRoman" => roman(value).to_lowercase(),
+        "upperRoman" => roman(value),
+        "decimalZero" => format!("{:02}", value),
+        "none" => String::new(),
+        "chineseCounting" | "chineseCountingThousand" | "ideographTraditional" | "taiwaneseCounting" => {
+            chinese(value)
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// a, b, ..., z, aa, bb, ...（Word 重复字母）
+fn letters(value: u32) -> String {
+    if value == 0 {
+        return String::new();
+    }
+    let letter = char::from(b'A' + ((value - 1) % 26) as u8);
+    letter.to_string().repeat(((value - 1) / 26 + 1) as usize)
+}
+
+fn roman(mut value: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut result = String::new();
+    for (amount, numeral) in NUMERALS {
+        while value >= amount {
+            result.push_str(numeral);
+            value -= amount;
+        }
+    }
+    result
+}
+
+/// 一、二、……、十、十一、二十 (values up to 99)
+fn chinese(value: u32) -> String {
+    const DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+    match value {
+        0..=9 => DIGITS[value as usize].to_string(),
+        10..=99 => {
+            let tens = if value / 10 == 1 { String::new() } else { DIGITS[(value / 10) as usize].to_string() };
+            let ones = match value % 10 {
+                0 => "",
+                digit => DIGITS[digit as usize],
+            };
+            format!("{}十{}", tens, ones)
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUMBERING: &str = r#"<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+        <w:abstractNum w:abstractNumId="0">
+            <w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/></w:lvl>
+            <w:lvl w:ilvl="1"><w:start w:val="1"/><w:numFmt w:val="lowerLetter"/><w:lvlText w:val="%2)"/></w:lvl>
+            <w:lvl w:ilvl="2"><w:start w:val="1"/><w:numFmt w:val="lowerRoman"/><w:lvlText w:val="%3."/></w:lvl>
+        </w:abstractNum>
+        <w:abstractNum w:abstractNumId="1">
+            <w:lvl w:ilvl="0"><w:numFmt w:val="bullet"/><w:lvlText w:val="•"/></w:lvl>
+        </w:abstractNum>
+        <w:abstractNum w:abstractNumId="2">
+            <w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/></w:lvl>
+            <w:lvl w:ilvl="1"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1.%2"/></w:lvl>
+        </w:abstractNum>
+        <w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+        <w:num w:numId="2"><w:abstractNumId w:val="1"/></w:num>
+        <w:num w:numId="3"><w:abstractNumId w:val="0"/><w:lvlOverride w:ilvl="0"><w:startOverride w:val="1"/></w:lvlOverride></w:num>
+        <w:num w:numId="4"><w:abstractNumId w:val="2"/></w:num>
+    </w:numbering>"#;
+
+    fn labels(items: &[(usize, usize)]) -> Vec<String> {
+        let numbering = Numbering::parse(NUMBERING).unwrap();
+        let mut counter = ListCounter::new();
+        items
+            .iter()
+            .map(|&(num_id, level)| counter.next(&numbering, num_id, level).map(|info| info.label).unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_nested_labels() {
+        assert_eq!(
+            labels(&[(1, 0), (1, 1), (1, 1), (1, 2), (1, 2), (1, 2), (1, 2), (1, 0), (1, 1)]),
+            vec!["1.", "a)", "b)", "i.", "ii.", "iii.", "iv.", "2.", "a)"]
+        );
+        assert_eq!(labels(&[(4, 0), (4, 1), (4, 1), (4, 0), (4, 1)]), vec!["1.", "1.1", "1.2", "2.", "2.1"]);
+    }
+
+    #[test]
+    fn test_restart_and_continuation() {
+        // numId 3 与 numId 1 共用 abstractNum 0 但重新计数；再次使用 numId 1 时继续共享的计数器
+        assert_eq!(labels(&[(1, 0), (1, 0), (3, 0), (3, 0), (1, 0)]), vec!["1.", "2.", "1.", "2.", "3."]);
+    }
+
+    #[test]
+    fn test_bullets_and_unknown_lists() {
+        let numbering = Numbering::parse(NUMBERING).unwrap();
+        let mut counter = ListCounter::new();
+
+        let bullet = counter.next(&numbering, 2, 0).unwrap();
+        assert!(!bullet.ordered);
+        assert_eq!(bullet.label, "•");
+        assert_eq!(bullet_symbol("\u{F0B7}"), "•");
+        assert_eq!(bullet_symbol("o"), "◦");
+
+        assert!(counter.next(&numbering, 0, 0).is_none());
+        assert!(counter.next(&numbering, 99, 0).is_none());
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(4, "lowerRoman"), "iv");
+        assert_eq!(format_number(1994, "upperRoman"), "MCMXCIV");
+        assert_eq!(format_number(28, "lowerLetter"), "bb");
+        assert_eq!(format_number(7, "decimalZero"), "07");
+        assert_eq!(format_number(12, "chineseCounting"), "十二");
+        assert_eq!(format_number(30, "chineseCountingThousand"), "三十");
+    }
+}
@@ -0,0 +1,221 @@
+//! 文档的标题大纲。
+//!
+//! 标题嵌套在其前面最近的更低级别标题之下，跳级（标题 1 之后直接是标题 3）
+//! 也只嵌套一层。章节编号（"2.3.1"）按其在树中的位置计算，而非文档自身的编号。
+//! 锚点为标题文字的 slug，重复时加 "-2"、"-3" 等后缀，
+//! 因此只要标题文字不变，锚点就保持不变。
+
+use super::{DocData, SectionType};
+use crate::output::slugify;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::iter::Peekable;
+
+/// 一个标题及其下嵌套的标题
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutlineEntry {
+    /// 标题级别（1-9）
+    pub level: u8,
+    /// 在大纲中的章节编号，如 "2.3.1"
+    pub number: String,
+    pub title: String,
+    /// 标题的唯一 slug，用作 Markdown 和 HTML 中的链接目标
+    pub anchor: String,
+    /// 标题在 [`DocData::content`] 中的字符偏移
+    pub offset: usize,
+    /// 标题在 [`DocData::sections`] 中的下标
+    pub section_index: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineEntry>,
+}
+
+/// 分层的标题树；序列化为顶层条目的列表
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Outline {
+    pub entries: Vec<OutlineEntry>,
+}
+
+impl Outline {
+    /// 由 `data` 中的标题段构建大纲
+    pub fn from_doc(data: &DocData) -> Self {
+        let mut anchors = HashSet::new();
+        let mut headings = Vec::new();
+        let mut last_offset = 0;
+
+        for ((section_index, section), offset) in data.sections.iter().enumerate().zip(data.section_offsets()) {
+            let SectionType::Heading(level) = section.section_type else {
+                continue;
+            };
+            let title = section.content.trim().to_string();
+            last_offset = offset.map_or(last_offset, |offset| offset + section.leading_whitespace());
+
+            headings.push(OutlineEntry {
+                level,
+                number: String::new(),
+                anchor: unique_anchor(&title, &mut anchors),
+                title,
+                offset: last_offset,
+                section_index,
+                children: Vec::new(),
+            });
+        }
+
+        let mut entries = nest(&mut headings.into_iter().peekable(), 0);
+        assign_numbers(&mut entries, "");
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 按文档顺序列出所有条目及其在树中的深度（顶层为 0）
+    pub fn flatten(&self) -> Vec<(usize, &OutlineEntry)> {
+        fn walk<'a>(entries: &'a [OutlineEntry], depth: usize, out: &mut Vec<(usize, &'a OutlineEntry)>) {
+            for entry in entries {
+                out.push((depth, entry));
+                walk(&entry.children, depth + 1, out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.entries, 0, &mut out);
+        out
+    }
+
+    /// 按段下标索引的标题锚点
+    pub fn anchors(&self) -> HashMap<usize, &str> {
+        self.flatten()
+            .into_iter()
+            .map(|(_, entry)| (entry.section_index, entry.anchor.as_str()))
+            .collect()
+    }
+}
+
+/// 缩进的“编号 标题”行，每级两个空格
+impl fmt::Display for Outline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (depth, entry) in self.flatten() {
+            writeln!(f, "{}{} {}", "  ".repeat(depth), entry.number, entry.title)?;
+        }
+        Ok(())
+    }
+}
+
+/// 取出比 `parent_level` 更深的标题，每个标题带上其后更深的标题
+fn nest<I: Iterator<Item = OutlineEntry>>(headings: &mut Peekable<I>, parent_level: u8) -> Vec<OutlineEntry> {
+    let mut entries = Vec::new();
+    while let Some(mut entry) = headings.next_if(|heading| heading.level > parent_level) {
+        entry.children = nest(headings, entry.level);
+        entries.push(entry);
+    }
+    entries
+}
+
+fn assign_numbers(entries: &mut [OutlineEntry], prefix: &str) {
+    for (index, entry) in entries.iter_mut().enumerate() {
+        entry.number = format!("{}{}", prefix, index + 1);
+        let prefix = format!("{}.", entry.number);
+        assign_numbers(&mut entry.children, &prefix);
+    }
+}
+
+fn unique_anchor(title: &str, used: &mut HashSet<String>) -> String {
+    let slug = slugify(title);
+    let base = if slug.is_empty() { "section".to_string() } else { slug };
+    let mut anchor = base.clone();
+    let mut suffix = 2;
+    while !used.insert(anchor.clone()) {
+        anchor = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    anchor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{DocMetadata, DocSection};
+
+    fn doc(sections: Vec<DocSection>) -> DocData {
+        let content = sections.iter().map(|section| section.content.as_str()).collect::<Vec<_>>().join("\n");
+        DocData {
+            raw_text: content.clone(),
+            content,
+            metadata: DocMetadata {
+                title: None,
+                author: None,
+                subject: None,
+                created: None,
+                modified: None,
+                word_count: 0,
+                paragraph_count: 0,
+                page_count: None,
+                character_count: 0,
+                keywords: None,
+                description: None,
+                category: None,
+                last_modified_by: None,
+                revision: None,
+                template: None,
+                application: None,
+                recorded_word_count: None,
+                custom_properties: Default::default(),
+            },
+            sections,
+            footnotes: Vec::new(),
+            endnotes: Vec::new(),
+            comments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_nesting_numbers_and_offsets() {
+        let data = doc(vec![
+            DocSection::new(SectionType::Heading(1), "Intro"),
+            DocSection::new(SectionType::Paragraph, "Überblick."),
+            DocSection::new(SectionType::Heading(2), "Scope"),
+            DocSection::new(SectionType::Heading(1), "Design"),
+            DocSection::new(SectionType::Heading(3), "Storage"),
+            DocSection::new(SectionType::Heading(2), "Network"),
+        ]);
+        let outline = Outline::from_doc(&data);
+
+        let flat: Vec<_> = outline
+            .flatten()
+            .into_iter()
+            .map(|(depth, entry)| (depth, entry.number.as_str(), entry.offset, entry.section_index))
+            .collect();
+        assert_eq!(flat, vec![(0, "1", 0, 0), (1, "1.1", 17, 2), (0, "2", 23, 3), (1, "2.1", 30, 4), (1, "2.2", 38, 5)]);
+        assert_eq!(outline.to_string(), "1 Intro\n  1.1 Scope\n2 Design\n  2.1 Storage\n  2.2 Network\n");
+        let anchors = outline.anchors();
+        assert_eq!(anchors.get(&4), Some(&"storage"));
+        assert_eq!(anchors.get(&1), None);
+    }
+
+    #[test]
+    fn test_offsets_of_repeated_and_substring_sections() {
+        let data = doc(vec![
+            DocSection::new(SectionType::Paragraph, "Terms and Scope"),
+            DocSection::new(SectionType::Heading(1), "Scope"),
+            DocSection::new(SectionType::Paragraph, "Scope"),
+            DocSection::new(SectionType::Heading(2), "  Scope"),
+        ]);
+        assert_eq!(data.section_offsets(), vec![Some(0), Some(16), Some(22), Some(28)]);
+
+        let offsets: Vec<usize> = Outline::from_doc(&data).flatten().into_iter().map(|(_, entry)| entry.offset).collect();
+        assert_eq!(offsets, vec![16, 30]);
+    }
+
+    #[test]
+    fn test_unique_anchors() {
+        let data = doc(vec![
+            DocSection::new(SectionType::Heading(1), "Overview"),
+            DocSection::new(SectionType::Heading(1), "Overview"),
+            DocSection::new(SectionType::Heading(1), "***"),
+        ]);
+        let anchors: Vec<_> = Outline::from_doc(&data).entries.into_iter().map(|entry| entry.anchor).collect();
+        assert_eq!(anchors, vec!["overview", "overview-2", "section"]);
+    }
+}
@@ -0,0 +1,413 @@
+use crate::error::{DocParserError, Result};
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::Reader;
+use std::io::{Cursor, Read};
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+/// 直接读取 DOCX 包中的 XML 部件。
+///
+/// docx-rs 能解析文档正文，但多数属性值为私有字段，且完全跳过了若干部件，
+/// 这些内容在此直接从包中读取。
+pub struct DocxPackage {
+    archive: ZipArchive<Cursor<Vec<u8>>>,
+    file: String,
+}
+
+/// `_rels/*.rels` 部件中的一条关系
+#[derive(Debug, Clone)]
+pub struct Relationship {
+    pub id: String,
+    pub rel_type: String,
+    /// 内部目标为绝对部件名，外部目标为原始地址
+    pub target: String,
+    pub external: bool,
+}
+
+impl Relationship {
+    /// 按关系类型的最后一段路径判断类型（如 "styles"）
+    pub fn is_type(&self, kind: &str) -> bool {
+        self.rel_type.rsplit('/').next() == Some(kind)
+    }
+}
+
+impl DocxPackage {
+    /// 从 .docx 文件的字节打开包
+    pub fn from_bytes(data: Vec<u8>, file: impl Into<String>) -> Result<Self> {
+        let file = file.into();
+        let archive = ZipArchive::new(Cursor::new(data)).map_err(|e| DocParserError::DocxParsing {
+            file: file.clone(),
+            details: format!("Failed to open DOCX package: {}", e),
+        })?;
+        Ok(Self { archive, file })
+    }
+
+    /// 以 UTF-8 文本读取部件，部件不存在时为 None
+    pub fn read_part(&mut self, part_name: &str) -> Result<Option<String>> {
+        Ok(self
+            .read_part_bytes(part_name)?
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+    }
+
+    /// 读取部件的原始字节，部件不存在时为 None
+    pub fn read_part_bytes(&mut self, part_name: &str) -> Result<Option<Vec<u8>>> {
+        let part_name = part_name.trim_start_matches('/');
+        let mut file = match self.archive.by_name(part_name) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => {
+                return Err(DocParserError::DocxParsing {
+                    file: self.file.clone(),
+                    details: format!("Failed to read part '{}': {}", part_name, e),
+                })
+            }
+        };
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        Ok(Some(content))
+    }
+
+    /// 包文件路径，用于错误信息
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// 包中所有部件的名称
+    pub fn part_names(&self) -> Vec<String> {
+        self.archive.file_names().map(|name| name.to_string()).collect()
+    }
+
+    /// 包关系中声明的主文档部件名
+    pub fn main_document_part(&mut self) -> Result<String> {
+        Ok(self
+            .relationships("")?
+            .into_iter()
+            .find(|rel| rel.is_type("officeDocument"))
+            .map(|rel| rel.target)
+            .unwrap_or_else(|| "word/document.xml".to_string()))
+    }
+
+    /// 部件声明的关系（读取同级的 `_rels/<name>.rels`）；
+    /// 部件名为空时读取包关系
+    pub fn relationships(&mut self, part_name: &str) -> Result<Vec<Relationship>> {
+        let part_name = part_name.trim_start_matches('/');
+        let (dir, file_name) = match part_name.rfind('/') {
+            Some(pos) => (&part_name[..pos], &part_name[pos + 1..]),
+            None => ("", part_name),
+        };
+        let rels_name = if dir.is_empty() {
+            format!("_rels/{}.rels", file_name)
+        } else {
+            format!("{}/_rels/{}.rels", dir, file_name)
+        };
+
+        let content = match self.read_part(&rels_name)? {
+            Some(content) => content,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut relationships = Vec::new();
+        let mut reader = Reader::from_str(&content);
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf).map_err(|e| self.xml_error(&rels_name, e))? {
+                Event::Start(ref e) | Event::Empty(ref e)
+                    if e.local_name().as_ref() == b"Relationship" =>
+                {
+                    let external = attr(e, "TargetMode").as_deref() == Some("External");
+                    let raw_target = attr(e, "Target").unwrap_or_default();
+                    let target = if external {
+                        raw_target
+                    } else {
+                        resolve_target(part_name, &raw_target)
+                    };
+                    relationships.push(Relationship {
+                        id: attr(e, "Id").unwrap_or_default(),
+                        rel_type: attr(e, "Type").unwrap_or_default(),
+                        target,
+                        external,
+                    });
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(relationships)
+    }
+
+    /// 为 XML 错误附上所在文件和部件
+    pub fn xml_error(&self, part_name: &str, error: quick_xml::Error) -> DocParserError {
+        DocParserError::DocxParsing {
+            file: self.file.clone(),
+            details: format!("Malformed XML in '{}': {}", part_name, error),
+        }
+    }
+}
+
+/// 正文段落中元素或文字事件的位置，供 [`ParagraphCollector`] 使用
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScanPosition {
+    /// 元素在段落中的深度（`w:p` 本身为 1）
+    pub depth: usize,
+    /// 段落中已开始的 run 数，包括当前未结束的和 `m:oMath` 中的
+    pub runs: usize,
+    /// 位于段落级 `w:r` 之内
+    pub in_run: bool,
+    /// 事件在文档部件中的字节范围
+    pub start: usize,
+    pub end: usize,
+}
+
+/// 接收 [`scan_body_paragraphs`] 扫描到的正文段落内容。
+///
+/// 每个收集器为每个段落保留一项（在 [`paragraph`] 中加入），
+/// 因此其结果与 docx-rs 为 `w:body` 生成的段落一一对应。
+///
+/// [`paragraph`]: ParagraphCollector::paragraph
+pub trait ParagraphCollector {
+    /// 新的正文段落开始
+    fn paragraph(&mut self);
+
+    /// 段落内的元素开始；自闭合元素的 `empty` 为 true，且没有对应的结束
+    fn start(&mut self, _element: &BytesStart, _empty: bool, _position: &ScanPosition) {}
+
+    /// 段落内的文字（已反转义）
+    fn text(&mut self, _text: &str, _position: &ScanPosition) {}
+
+    /// 段落内的元素结束
+    fn end(&mut self, _element: &BytesEnd, _position: &ScanPosition) {}
+}
+
+/// 遍历一次文档部件中的正文段落，交给所有收集器。
+///
+/// 表格和块级内容控件由 docx-rs 整体读取，其中的段落在此跳过。
+/// run 的计数方式与 docx-rs 一致：嵌套的 run（文本框）不计入；docx-rs 把 `m:oMath`
+/// 中的 `m:r` 也读作（空的）run，因此这些 run 同样计入，但只传递 `m:oMath` 元素本身。
+/// `mc:Fallback` 重复了 `mc:Choice` 的内容，整体跳过。
+pub fn scan_body_paragraphs(
+    document_xml: &str,
+    collectors: &mut [&mut dyn ParagraphCollector],
+) -> std::result::Result<(), quick_xml::Error> {
+    let mut reader = Reader::from_str(document_xml);
+    // 正在扫描的正文段落中未结束的元素（含段落本身）；不在正文段落中时为 None
+    let mut depth: Option<usize> = None;
+    let mut nested_blocks = 0;
+    // 未结束的 run、`m:oMath` 和 `mc:Fallback` 的深度
+    let mut run_depth: Option<usize> = None;
+    let mut math_depth: Option<usize> = None;
+    let mut fallback: Option<usize> = None;
+    let mut position = ScanPosition::default();
+
+    loop {
+        position.start = reader.buffer_position();
+        let event = reader.read_event()?;
+        position.end = reader.buffer_position();
+        let empty = matches!(event, Event::Empty(_));
+        match (event, depth) {
+            (Event::Start(ref e) | Event::Empty(ref e), None) => match e.local_name().as_ref() {
+                b"tbl" | b"sdt" if !empty => nested_blocks += 1,
+                b"p" if nested_blocks == 0 => {
+                    collectors.iter_mut().for_each(|collector| collector.paragraph());
+                    position.runs = 0;
+                    if !empty {
+                        depth = Some(1);
+                    }
+                }
+                _ => {}
+            },
+            (Event::Start(ref e) | Event::Empty(ref e), Some(d)) => {
+                if !empty {
+                    depth = Some(d + 1);
+                }
+                if fallback.is_some() {
+                    continue;
+                }
+                if math_depth.is_some() {
+                    if e.local_name().as_ref() == b"r" {
+                        position.runs += 1;
+                    }
+                    continue;
+                }
+                position.depth = d + 1;
+                match e.local_name().as_ref() {
+                    b"Fallback" if !empty => {
+                        fallback = Some(d + 1);
+                        continue;
+                    }
+                    b"oMath" if !empty => math_depth = Some(d + 1),
+                    b"r" if run_depth.is_none() => {
+                        position.runs += 1;
+                        if !empty {
+                            run_depth = Some(d + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                position.in_run = run_depth.is_some();
+                collectors.iter_mut().for_each(|collector| collector.start(e, empty, &position));
+            }
+            (Event::Text(ref t), Some(d)) if fallback.is_none() && math_depth.is_none() => {
+                let text = t.unescape()?;
+                position.depth = d;
+                position.in_run = run_depth.is_some();
+                collectors.iter_mut().for_each(|collector| collector.text(&text, &position));
+            }
+            (Event::End(_), Some(1)) => {
+                depth = None;
+                run_depth = None;
+                fallback = None;
+                math_depth = None;
+            }
+            (Event::End(ref e), Some(d)) => {
+                depth = Some(d - 1);
+                if fallback.is_some() {
+                    if fallback == Some(d) {
+                        fallback = None;
+                    }
+                    continue;
+                }
+                if math_depth.is_some_and(|math| math < d) {
+                    continue;
+                }
+                math_depth = None;
+                position.depth = d;
+                position.in_run = run_depth.is_some();
+                collectors.iter_mut().for_each(|collector| collector.end(e, &position));
+                if run_depth == Some(d) {
+                    run_depth = None;
+                }
+            }
+            (Event::End(ref e), None) if matches!(e.local_name().as_ref(), b"tbl" | b"sdt") => nested_blocks -= 1,
+            (Event::Eof, _) => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// 按本地名读取属性值（忽略命名空间前缀）
+pub fn attr(element: &BytesStart, name: &str) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name.as_bytes())
+        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+/// docx-rs 元素写回的 OOXML 中的元素及其深度（最外层为 0），按文档顺序。
+///
+/// docx-rs 的属性值多为私有字段，但写回的 OOXML 与文档中的 XML 相同，
+/// 因此可以和原始部件一样读取（如 [`RunProperties::apply_element`]）。
+///
+/// [`RunProperties::apply_element`]: super::formatting::RunProperties::apply_element
+pub fn built_elements<T: docx_rs::BuildXML>(element: &T) -> Vec<(usize, BytesStart<'static>)> {
+    let xml = element.build();
+    let mut reader = Reader::from_reader(xml.as_slice());
+    let mut buf = Vec::new();
+    let mut elements = Vec::new();
+    let mut depth = 0usize;
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                elements.push((depth, e.into_owned()));
+                depth += 1;
+            }
+            Ok(Event::Empty(e)) => elements.push((depth, e.into_owned())),
+            Ok(Event::End(_)) => depth = depth.saturating_sub(1),
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    elements
+}
+
+/// docx-rs 元素写回的 OOXML 中的文字（已反转义）
+pub fn built_text<T: docx_rs::BuildXML>(element: &T) -> String {
+    let xml = element.build();
+    let mut reader = Reader::from_reader(xml.as_slice());
+    let mut buf = Vec::new();
+    let mut text = String::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Text(t)) => text.push_str(&t.unescape().unwrap_or_default()),
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    text
+}
+
+/// 相对于声明关系的部件解析关系目标
+pub fn resolve_target(base_part: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+
+    let mut segments: Vec<&str> = base_part.split('/').collect();
+    segments.pop();
+    for segment in target.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                segments.pop();
+            }
+            other => segments.push(other),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(resolve_target("word/document.xml", "styles.xml"), "word/styles.xml");
+        assert_eq!(resolve_target("", "word/document.xml"), "word/document.xml");
+        assert_eq!(resolve_target("word/document.xml", "../customXml/item1.xml"), "customXml/item1.xml");
+        assert_eq!(resolve_target("word/document.xml", "/word/media/image1.png"), "word/media/image1.png");
+    }
+
+    /// 记录每个元素开始时的本地名和 run 计数
+    #[derive(Default)]
+    struct Starts(Vec<Vec<(String, usize)>>);
+
+    impl ParagraphCollector for Starts {
+        fn paragraph(&mut self) {
+            self.0.push(Vec::new());
+        }
+
+        fn start(&mut self, element: &BytesStart, _empty: bool, position: &ScanPosition) {
+            let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+            if let Some(current) = self.0.last_mut() {
+                current.push((name, position.runs));
+            }
+        }
+    }
+
+    #[test]
+    fn test_scan_body_paragraphs() {
+        let xml = r#"<w:document><w:body>
+            <w:p><w:r><w:t>a</w:t></w:r><m:oMath><m:r><m:t>x</m:t></m:r></m:oMath><w:hyperlink><w:r/></w:hyperlink></w:p>
+            <w:tbl><w:tr><w:tc><w:p><w:r/></w:p></w:tc></w:tr></w:tbl>
+            <w:p/>
+            <w:p><w:r><mc:AlternateContent><mc:Choice><w:drawing/></mc:Choice><mc:Fallback><w:pict/></mc:Fallback></mc:AlternateContent></w:r></w:p>
+        </w:body></w:document>"#;
+        let mut starts = Starts::default();
+        let mut other = Starts::default();
+        scan_body_paragraphs(xml, &mut [&mut starts, &mut other]).unwrap();
+
+        let names = |paragraph: &[(String, usize)]| paragraph.iter().map(|(name, runs)| format!("{}:{}", name, runs)).collect::<Vec<_>>();
+        assert_eq!(starts.0.len(), 3);
+        assert_eq!(names(&starts.0[0]), ["r:1", "t:1", "oMath:1", "hyperlink:2", "r:3"]);
+        assert!(starts.0[1].is_empty());
+        assert_eq!(names(&starts.0[2]), ["r:1", "AlternateContent:1", "Choice:1", "drawing:1"]);
+        assert_eq!(other.0, starts.0);
+    }
+}
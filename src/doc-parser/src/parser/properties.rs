@@ -0,0 +1,184 @@
+//! 来自 `docProps` 部件的文档属性。
+//!
+//! - `core.xml`：Dublin Core 字段（标题、作者、关键词、时间戳等）
+//! - `app.xml`：Word 记录的应用程序统计信息（页数、字数、模板）
+//! - `custom.xml`：用户定义的名称/值属性
+//!
+//! 各部件通过包关系定位，找不到时使用约定的 `docProps/*.xml` 名称。
+
+use super::package::{attr, DocxPackage};
+use crate::error::Result;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::BTreeMap;
+
+type ApplyPart = fn(&mut DocumentProperties, &str) -> std::result::Result<(), quick_xml::Error>;
+
+/// 包中记录的属性；所有字段都是可选的
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentProperties {
+    pub title: Option<String>,
+    pub subject: Option<String>,
+    pub creator: Option<String>,
+    pub keywords: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub last_modified_by: Option<String>,
+    pub revision: Option<u32>,
+    /// 原样保存的 W3CDTF 时间戳（如 `2024-03-01T09:30:00Z`）
+    pub created: Option<String>,
+    pub modified: Option<String>,
+    pub template: Option<String>,
+    pub application: Option<String>,
+    pub pages: Option<usize>,
+    pub words: Option<usize>,
+    pub custom: BTreeMap<String, String>,
+}
+
+impl DocumentProperties {
+    /// 读取包中所有属性部件；缺少的部件对应字段保持未设置
+    pub fn read(package: &mut DocxPackage) -> Result<Self> {
+        let relationships = package.relationships("")?;
+        let part = |kind: &str, default: &str| {
+            relationships
+                .iter()
+                .find(|rel| rel.is_type(kind))
+                .map(|rel| rel.target.clone())
+                .unwrap_or_else(|| default.to_string())
+        };
+
+        let mut properties = Self::default();
+        let parts = [
+            (part("core-properties", "docProps/core.xml"), Self::apply_core as ApplyPart),
+            (part("extended-properties", "docProps/app.xml"), Self::apply_app),
+            (part("custom-properties", "docProps/custom.xml"), Self::apply_custom),
+        ];
+        for (part_name, apply) in parts {
+            if let Some(xml) = package.read_part(&part_name)? {
+                apply(&mut properties, &xml).map_err(|e| package.xml_error(&part_name, e))?;
+            }
+        }
+        Ok(properties)
+    }
+
+    /// 应用 `docProps/core.xml`
+    pub fn apply_core(&mut self, xml: &str) -> std::result::Result<(), quick_xml::Error> {
+        for_each_text_element(xml, |name, text| {
+            let value = Some(text.to_string());
+            match name {
+                "title" => self.title = value,
+                "subject" => self.subject = value,
+                "creator" => self.creator = value,
+                "keywords" => self.keywords = value,
+                "description" => self.description = value,
+                "category" => self.category = value,
+                "lastModifiedBy" => self.last_modified_by = value,
+                "revision" => self.revision = text.parse().ok(),
+                "created" => self.created = value,
+                "modified" => self.modified = value,
+                _ => {}
+            }
+        })
+    }
+
+    /// 应用 `docProps/app.xml`
+    pub fn apply_app(&mut self, xml: &str) -> std::result::Result<(), quick_xml::Error> {
+        for_each_text_element(xml, |name, text| match name {
+            "Template" => self.template = Some(text.to_string()),
+            "Application" => self.application = Some(text.to_string()),
+            "Pages" => self.pages = text.parse().ok(),
+            "Words" => self.words = text.parse().ok(),
+            _ => {}
+        })
+    }
+
+    /// 应用 `docProps/custom.xml`；任何 `vt:` 类型的值都保存为文本
+    pub fn apply_custom(&mut self, xml: &str) -> std::result::Result<(), quick_xml::Error> {
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::new();
+        let mut current: Option<String> = None;
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(ref e) if e.local_name().as_ref() == b"property" => {
+                    current = attr(e, "name");
+                }
+                Event::End(ref e) if e.local_name().as_ref() == b"property" => current = None,
+                Event::Text(ref e) => {
+                    if let Some(name) = &current {
+                        let text = e.unescape()?;
+                        let value = self.custom.entry(name.clone()).or_default();
+                        value.push_str(text.trim());
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(())
+    }
+}
+
+/// 对每个文字非空的元素，以其本地名和去除首尾空白的文字调用 `apply`
+fn for_each_text_element(
+    xml: &str,
+    mut apply: impl FnMut(&str, &str),
+) -> std::result::Result<(), quick_xml::Error> {
+    let mut reader = Reader::from_str(xml);
+    let mut buf = Vec::new();
+    let mut current = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => current = String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+            Event::End(_) => current.clear(),
+            Event::Text(ref e) => {
+                let text = e.unescape()?;
+                let text = text.trim();
+                if !current.is_empty() && !text.is_empty() {
+                    apply(&current, text);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_and_app_properties() {
+        let core = r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>Quarterly &amp; Annual Report</dc:title><dc:creator>Li Lei</dc:creator><cp:keywords>finance, q3</cp:keywords><dc:description></dc:description><cp:lastModifiedBy>Han Meimei</cp:lastModifiedBy><cp:revision>7</cp:revision><dcterms:created xsi:type="dcterms:W3CDTF">2024-03-01T09:30:00Z</dcterms:created><dcterms:modified xsi:type="dcterms:W3CDTF">2024-03-05T16:00:00Z</dcterms:modified><cp:category>Reports</cp:category></cp:coreProperties>"#;
+        let app = r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties"><Template>Normal.dotm</Template><TotalTime>42</TotalTime><Pages>3</Pages><Words>512</Words><Characters>2900</Characters><Application>Microsoft Office Word</Application></Properties>"#;
+
+        let mut properties = DocumentProperties::default();
+        properties.apply_core(core).unwrap();
+        properties.apply_app(app).unwrap();
+
+        assert_eq!(properties.title.as_deref(), Some("Quarterly & Annual Report"));
+        assert_eq!(properties.creator.as_deref(), Some("Li Lei"));
+        assert_eq!(properties.last_modified_by.as_deref(), Some("Han Meimei"));
+        assert_eq!(properties.description, None);
+        assert_eq!(properties.revision, Some(7));
+        assert_eq!(properties.created.as_deref(), Some("2024-03-01T09:30:00Z"));
+        assert_eq!(properties.category.as_deref(), Some("Reports"));
+        assert_eq!(properties.template.as_deref(), Some("Normal.dotm"));
+        assert_eq!((properties.pages, properties.words), (Some(3), Some(512)));
+    }
+
+    #[test]
+    fn test_custom_properties() {
+        let custom = r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes"><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="Project"><vt:lpwstr>Apollo</vt:lpwstr></property><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="3" name="Approved"><vt:bool>true</vt:bool></property></Properties>"#;
+
+        let mut properties = DocumentProperties::default();
+        properties.apply_custom(custom).unwrap();
+        assert_eq!(properties.custom.get("Project").map(String::as_str), Some("Apollo"));
+        assert_eq!(properties.custom.get("Approved").map(String::as_str), Some("true"));
+    }
+}
@@ -0,0 +1,257 @@
+//! 文本框、绘图画布和 SmartArt 图形中的文字。
+//!
+//! docx-rs 不提供 DrawingML（`wps:txbx`）或 VML（`v:textbox`）图形中的 `w:txbxContent`，
+//! SmartArt 的文字则位于单独的图形数据部件中。与图片一样（见 [`super::images::ImageCollector`]），
+//! 图形通过直接扫描文档部件得到，其下标与 docx-rs 生成的正文段落一致。
+//! `mc:AlternateContent` 块只读取 `mc:Choice`，其 `mc:Fallback` 通常以 VML 重复同一图形。
+
+use super::package::{attr, ParagraphCollector, ScanPosition};
+use super::{RevisionMode, ShapeKind};
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::Reader;
+
+/// 扫描得到的含文字图形
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawShape {
+    pub kind: ShapeKind,
+    /// 绘图对象名称，取自 `wp:docPr`（DrawingML）或形状 ID（VML）
+    pub name: Option<String>,
+    /// 浮动图形（`wp:anchor`），而非嵌入行内
+    pub anchored: bool,
+    /// 图形中文本框的非空段落，按文档顺序
+    pub lines: Vec<String>,
+    /// SmartArt 数据部件的关系 ID（`dgm:relIds r:dm`）
+    pub diagram_rel_id: Option<String>,
+}
+
+/// 正在读取的绘图对象及当前所在的文本框段落
+#[derive(Debug)]
+struct OpenShape {
+    shape: RawShape,
+    /// `w:drawing`/`w:pict` 的深度
+    depth: usize,
+    /// 未结束的文本框段落的文字
+    line: Option<String>,
+    /// 位于 `w:t`（保留删除内容时也包括 `w:delText`）之内
+    in_text: bool,
+    /// 在当前修订模式下文字被丢弃的 `w:ins`/`w:del` 的深度
+    dropped: Option<usize>,
+}
+
+/// 按文档顺序收集每个正文段落中含文字的图形
+#[derive(Debug)]
+pub struct ShapeCollector {
+    pub paragraphs: Vec<Vec<RawShape>>,
+    revisions: RevisionMode,
+    current: Option<OpenShape>,
+}
+
+impl ShapeCollector {
+    pub fn new(revisions: RevisionMode) -> Self {
+        Self {
+            paragraphs: Vec::new(),
+            revisions,
+            current: None,
+        }
+    }
+}
+
+impl ParagraphCollector for ShapeCollector {
+    fn paragraph(&mut self) {
+        self.paragraphs.push(Vec::new());
+    }
+
+    fn start(&mut self, e: &BytesStart, empty: bool, position: &ScanPosition) {
+        let name = e.local_name();
+        let Some(open) = self.current.as_mut() else {
+            if matches!(name.as_ref(), b"drawing" | b"pict") && !empty {
+                self.current = Some(OpenShape {
+                    shape: RawShape {
+                        kind: ShapeKind::TextBox,
+                        name: None,
+                        anchored: false,
+                        lines: Vec::new(),
+                        diagram_rel_id: None,
+                    },
+                    depth: position.depth,
+                    line: None,
+                    in_text: false,
+                    dropped: None,
+                });
+            }
+            return;
+        };
+        let dropped_revision: &[u8] = match self.revisions {
+            RevisionMode::Reject => b"ins",
+            RevisionMode::Accept => b"del",
+            RevisionMode::Show => b"",
+        };
+        match name.as_ref() {
+            b"anchor" => open.shape.anchored = true,
+            b"docPr" => open.shape.name = open.shape.name.take().or_else(|| attr(e, "name")),
+            b"shape" | b"rect" | b"roundrect" => open.shape.name = open.shape.name.take().or_else(|| attr(e, "id")),
+            b"wpc" | b"wgp" | b"group" => open.shape.kind = ShapeKind::Canvas,
+            b"relIds" => {
+                open.shape.kind = ShapeKind::SmartArt;
+                open.shape.diagram_rel_id = attr(e, "dm");
+            }
+            b"p" if !empty => open.line = Some(String::new()),
+            _ if open.dropped.is_some() => {}
+            element @ (b"ins" | b"del") if element == dropped_revision && !empty => open.dropped = Some(position.depth),
+            b"t" if !empty => open.in_text = true,
+            b"delText" if self.revisions != RevisionMode::Accept && !empty => open.in_text = true,
+            b"tab" => push_text(open, "\t"),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str, _position: &ScanPosition) {
+        if let Some(open) = self.current.as_mut().filter(|open| open.in_text) {
+            push_text(open, text);
+        }
+    }
+
+    fn end(&mut self, e: &BytesEnd, position: &ScanPosition) {
+        let Some(open) = self.current.as_mut() else {
+            return;
+        };
+        match e.local_name().as_ref() {
+            b"t" | b"delText" => open.in_text = false,
+            b"p" => {
+                let line = open.line.take().unwrap_or_default();
+                if !line.trim().is_empty() {
+                    open.shape.lines.push(line.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+        if open.dropped == Some(position.depth) {
+            open.dropped = None;
+        }
+        if open.depth == position.depth {
+            let shape = self.current.take().map(|open| open.shape);
+            if let (Some(shape), Some(shapes)) = (shape, self.paragraphs.last_mut()) {
+                if !shape.lines.is_empty() || shape.diagram_rel_id.is_some() {
+                    shapes.push(shape);
+                }
+            }
+        }
+    }
+}
+
+fn push_text(open: &mut OpenShape, text: &str) {
+    if let Some(line) = open.line.as_mut() {
+        line.push_str(text);
+    }
+}
+
+/// SmartArt 数据部件（`dgm:dataModel`）中节点的文字，每段一行；
+/// 连接点和呈现点不含用户文字，予以跳过
+pub fn diagram_text(data_xml: &str) -> Result<Vec<String>, quick_xml::Error> {
+    let mut reader = Reader::from_str(data_xml);
+    let mut lines = Vec::new();
+    // 位于节点（没有类型或类型为 "node" 的 `dgm:pt`）之内
+    let mut in_node = false;
+    let mut line: Option<String> = None;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(ref e) => match e.local_name().as_ref() {
+                b"pt" => in_node = attr(e, "type").is_none_or(|kind| kind == "node"),
+                b"p" if in_node => line = Some(String::new()),
+                b"t" if line.is_some() => in_text = true,
+                _ => {}
+            },
+            Event::Text(ref t) if in_text => {
+                if let Some(line) = line.as_mut() {
+                    line.push_str(&t.unescape()?);
+                }
+            }
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"pt" => in_node = false,
+                b"t" => in_text = false,
+                b"p" => {
+                    if let Some(text) = line.take().filter(|text| !text.trim().is_empty()) {
+                        lines.push(text.trim().to_string());
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::package::scan_body_paragraphs;
+
+    fn shape_references(xml: &str, revisions: RevisionMode) -> Vec<Vec<RawShape>> {
+        let mut collector = ShapeCollector::new(revisions);
+        scan_body_paragraphs(xml, &mut [&mut collector]).unwrap();
+        collector.paragraphs
+    }
+
+    #[test]
+    fn test_text_box_prefers_choice_over_fallback() {
+        let xml = concat!(
+            r#"<w:document><w:body><w:p><w:r><w:t>Intro</w:t></w:r><w:r><mc:AlternateContent>"#,
+            r#"<mc:Choice Requires="wps"><w:drawing><wp:anchor><wp:docPr id="1" name="Text Box 1"/><a:graphic><a:graphicData><wps:wsp><wps:txbx><w:txbxContent>"#,
+            r#"<w:p><w:r><w:t>Key </w:t></w:r><w:r><w:t>point</w:t></w:r></w:p><w:p/><w:p><w:ins><w:r><w:t>added</w:t></w:r></w:ins><w:del><w:r><w:delText>gone</w:delText></w:r></w:del></w:p>"#,
+            r#"</w:txbxContent></wps:txbx></wps:wsp></a:graphicData></a:graphic></wp:anchor></w:drawing></mc:Choice>"#,
+            r#"<mc:Fallback><w:pict><v:shape id="Text Box 1"><v:textbox><w:txbxContent><w:p><w:r><w:t>Key point</w:t></w:r></w:p></w:txbxContent></v:textbox></v:shape></w:pict></mc:Fallback>"#,
+            r#"</mc:AlternateContent></w:r></w:p>"#,
+            r#"<w:p><w:r><w:pict><v:shape id="_x0000_s1026"><v:textbox><w:txbxContent><w:p><w:r><w:t>Legacy</w:t></w:r></w:p></w:txbxContent></v:textbox></v:shape></w:pict></w:r></w:p>"#,
+            r#"</w:body></w:document>"#,
+        );
+        let paragraphs = shape_references(xml, RevisionMode::Accept);
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(
+            paragraphs[0],
+            vec![RawShape {
+                kind: ShapeKind::TextBox,
+                name: Some("Text Box 1".to_string()),
+                anchored: true,
+                lines: vec!["Key point".to_string(), "added".to_string()],
+                diagram_rel_id: None,
+            }]
+        );
+        assert_eq!(paragraphs[1][0].lines, vec!["Legacy"]);
+        assert_eq!(paragraphs[1][0].name.as_deref(), Some("_x0000_s1026"));
+
+        let rejected = shape_references(xml, RevisionMode::Reject);
+        assert_eq!(rejected[0][0].lines, vec!["Key point", "gone"]);
+    }
+
+    #[test]
+    fn test_canvas_and_smartart() {
+        let xml = concat!(
+            r#"<w:body><w:p><w:r><w:drawing><wp:inline><wp:docPr id="2" name="Canvas 2"/><a:graphic><a:graphicData><wpc:wpc>"#,
+            r#"<wps:wsp><wps:txbx><w:txbxContent><w:p><w:r><w:t>Client</w:t></w:r></w:p></w:txbxContent></wps:txbx></wps:wsp>"#,
+            r#"<wps:wsp><wps:txbx><w:txbxContent><w:p><w:r><w:t>Server</w:t></w:r></w:p></w:txbxContent></wps:txbx></wps:wsp>"#,
+            r#"</wpc:wpc></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#,
+            r#"<w:r><w:drawing><wp:inline><wp:docPr id="3" name="Diagram 3"/><a:graphic><a:graphicData><dgm:relIds r:dm="rId8" r:lo="rId9"/></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#,
+            r#"<w:r><w:drawing><wp:inline><wp:docPr id="4" name="Picture 4"/></wp:inline></w:drawing></w:r></w:p></w:body>"#,
+        );
+        let shapes = &shape_references(xml, RevisionMode::Accept)[0];
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(shapes[0].kind, ShapeKind::Canvas);
+        assert_eq!(shapes[0].lines, vec!["Client", "Server"]);
+        assert_eq!(shapes[1].kind, ShapeKind::SmartArt);
+        assert_eq!(shapes[1].diagram_rel_id.as_deref(), Some("rId8"));
+
+        let data = concat!(
+            r#"<dgm:dataModel><dgm:ptLst>"#,
+            r#"<dgm:pt modelId="0" type="doc"><dgm:t><a:p><a:r><a:t>Document</a:t></a:r></a:p></dgm:t></dgm:pt>"#,
+            r#"<dgm:pt modelId="1"><dgm:t><a:p><a:r><a:t>Plan</a:t></a:r></a:p></dgm:t></dgm:pt>"#,
+            r#"<dgm:pt modelId="2" type="parTrans"><dgm:t><a:p><a:r><a:t>x</a:t></a:r></a:p></dgm:t></dgm:pt>"#,
+            r#"<dgm:pt modelId="3" type="node"><dgm:t><a:p><a:r><a:t>Build &amp; ship</a:t></a:r></a:p></dgm:t></dgm:pt>"#,
+            r#"</dgm:ptLst></dgm:dataModel>"#,
+        );
+        assert_eq!(diagram_text(data).unwrap(), vec!["Plan", "Build & ship"]);
+    }
+}
@@ -0,0 +1,385 @@
+//! 解析 `word/styles.xml` 中的段落样式。
+//!
+//! 标题的识别方式与 Word 相同：按段落或其样式（沿 `basedOn` 继承）的大纲级别；
+//! 对只带样式名的文档，按（可能已本地化的）"Heading N" 名称。
+//! 字符格式同样沿 `basedOn` 链解析。
+
+use super::formatting::RunProperties;
+use super::package::attr;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+/// 跟随的 `basedOn` 链的最大长度，防止循环定义
+const MAX_STYLE_DEPTH: usize = 16;
+
+/// 内置 "Heading N" 样式名的本地化前缀（小写）
+const HEADING_NAME_PREFIXES: &[&str] = &[
+    "heading",
+    "标题",
+    "標題",
+    "überschrift",
+    "titre",
+    "título",
+    "titolo",
+    "kop",
+    "заголовок",
+    "見出し",
+    "제목",
+    "nagłówek",
+    "rubrik",
+    "overskrift",
+    "otsikko",
+    "nadpis",
+    "başlık",
+];
+
+/// 一个 `w:style` 定义
+#[derive(Debug, Clone, Default)]
+pub struct Style {
+    pub id: String,
+    pub name: Option<String>,
+    /// "paragraph"、"character"、"table" 或 "numbering"
+    pub style_type: String,
+    pub based_on: Option<String>,
+    /// `w:pPr/w:outlineLvl` 中从 0 开始的大纲级别（9 表示正文）
+    pub outline_level: Option<u8>,
+    pub is_default: bool,
+    /// 样式自身 `w:rPr` 中的字符格式
+    pub run_properties: RunProperties,
+    /// `w:pPr/w:numPr` 中的列表编号 (`numId`, `ilvl`)
+    pub numbering: Option<(usize, usize)>,
+}
+
+/// 文档中的所有样式，按样式 ID 索引
+#[derive(Debug, Clone, Default)]
+pub struct StyleSheet {
+    styles: HashMap<String, Style>,
+    /// `w:docDefaults/w:rPrDefault`
+    default_run_properties: RunProperties,
+}
+
+impl StyleSheet {
+    /// 解析 `word/styles.xml` 的内容
+    pub fn parse(xml: &str) -> std::result::Result<Self, quick_xml::Error> {
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::new();
+        let mut styles = HashMap::new();
+        let mut default_run_properties = RunProperties::default();
+        let mut current: Option<Style> = None;
+        let mut in_paragraph_props = false;
+        let mut in_run_props = false;
+        let mut in_run_defaults = false;
+        // 表格样式的条件格式（w:tblStylePr）一般不作用于 run
+        let mut in_conditional = false;
+
+        loop {
+            let event = reader.read_event_into(&mut buf)?;
+            // 空元素（`<w:rPr/>`）没有结束事件，不能开启作用域
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) => match e.local_name().as_ref() {
+                    b"style" => {
+                        current = Some(Style {
+                            id: attr(e, "styleId").unwrap_or_default(),
+                            style_type: attr(e, "type").unwrap_or_else(|| "paragraph".to_string()),
+                            is_default: matches!(attr(e, "default").as_deref(), Some("1" | "true" | "on")),
+                            ..Default::default()
+                        });
+                    }
+                    b"pPr" if !empty => in_paragraph_props = true,
+                    b"rPr" if !empty => in_run_props = true,
+                    b"rPrDefault" if !empty => in_run_defaults = true,
+                    b"tblStylePr" if !empty => in_conditional = true,
+                    b"pPr" | b"rPr" | b"rPrDefault" | b"tblStylePr" => {}
+                    _ if in_run_props && !in_conditional => {
+                        if in_run_defaults {
+                            default_run_properties.apply_element(e);
+                        } else if let Some(style) = current.as_mut() {
+                            style.run_properties.apply_element(e);
+                        }
+                    }
+                    b"name" => {
+                        if let Some(style) = current.as_mut() {
+                            style.name = attr(e, "val");
+                        }
+                    }
+                    b"basedOn" => {
+                        if let Some(style) = current.as_mut() {
+                            style.based_on = attr(e, "val");
+                        }
+                    }
+                    b"outlineLvl" if in_paragraph_props => {
+                        if let Some(style) = current.as_mut() {
+                            style.outline_level = attr(e, "val").and_then(|v| v.parse().ok());
+                        }
+                    }
+                    b"numId" if in_paragraph_props => {
+                        if let (Some(style), Some(num_id)) = (current.as_mut(), attr(e, "val").and_then(|v| v.parse().ok())) {
+                            let level = style.numbering.map_or(0, |(_, level)| level);
+                            style.numbering = Some((num_id, level));
+                        }
+                    }
+                    b"ilvl" if in_paragraph_props => {
+                        if let (Some(style), Some(level)) = (current.as_mut(), attr(e, "val").and_then(|v| v.parse().ok())) {
+                            let num_id = style.numbering.map_or(0, |(num_id, _)| num_id);
+                            style.numbering = Some((num_id, level));
+                        }
+                    }
+                    _ => {}
+                },
+                Event::End(ref e) => match e.local_name().as_ref() {
+                    b"style" => {
+                        if let Some(style) = current.take() {
+                            styles.insert(style.id.clone(), style);
+                        }
+                    }
+                    b"pPr" => in_paragraph_props = false,
+                    b"rPr" => in_run_props = false,
+                    b"rPrDefault" => in_run_defaults = false,
+                    b"tblStylePr" => in_conditional = false,
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(Self {
+            styles,
+            default_run_properties,
+        })
+    }
+
+    pub fn get(&self, style_id: &str) -> Option<&Style> {
+        self.styles.get(style_id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+
+    /// 没有显式 `w:pStyle` 的段落所用的样式
+    pub fn default_paragraph_style(&self) -> Option<&Style> {
+        self.styles
+            .values()
+            .find(|style| style.is_default && style.style_type == "paragraph")
+    }
+
+    /// 样式及其祖先样式，由近及远
+    pub fn inheritance_chain<'a>(&'a self, style_id: &str) -> Vec<&'a Style> {
+        let mut chain = Vec::new();
+        let mut next = self.styles.get(style_id);
+        while let Some(style) = next {
+            if chain.len() >= MAX_STYLE_DEPTH || chain.iter().any(|s: &&Style| s.id == style.id) {
+                break;
+            }
+            chain.push(style);
+            next = style.based_on.as_deref().and_then(|id| self.styles.get(id));
+        }
+        chain
+    }
+
+    /// run 在应用直接格式之前的字符格式：
+    /// 依次为文档默认格式、段落样式链、字符样式链
+    pub fn run_properties(&self, paragraph_style: Option<&str>, run_style: Option<&str>) -> RunProperties {
+        let mut properties = self.default_run_properties.clone();
+
+        let paragraph_style = paragraph_style.or_else(|| self.default_paragraph_style().map(|s| s.id.as_str()));
+        for style_id in [paragraph_style, run_style].into_iter().flatten() {
+            for style in self.inheritance_chain(style_id).iter().rev() {
+                properties.merge(&style.run_properties);
+            }
+        }
+        properties
+    }
+
+    /// 段落样式为其段落指定的列表编号（`numId`、`ilvl`）
+    pub fn numbering(&self, style_id: Option<&str>) -> Option<(usize, usize)> {
+        let style_id = style_id.or_else(|| self.default_paragraph_style().map(|s| s.id.as_str()))?;
+        self.inheritance_chain(style_id)
+            .iter()
+            .find_map(|style| style.numbering)
+            .filter(|(num_id, _)| *num_id != 0)
+    }
+
+    /// 段落的标题级别（1-9），取自段落直接设置的大纲级别或其样式。
+    ///
+    /// `style_id` 为段落的 `w:pStyle`；为 None 时使用默认段落样式。
+    pub fn heading_level(&self, style_id: Option<&str>, direct_outline_level: Option<u8>) -> Option<u8> {
+        if let Some(level) = direct_outline_level {
+            return outline_to_heading(level);
+        }
+
+        let style_id = match style_id {
+            Some(id) => id,
+            None => return self.default_paragraph_style().and_then(|s| self.heading_level(Some(&s.id), None)),
+        };
+
+        let chain = self.inheritance_chain(style_id);
+        if chain.is_empty() {
+            // styles.xml 中没有该样式；"Heading2" 等内置 ID 仍可确定级别
+            return heading_level_from_name(style_id);
+        }
+
+        for style in &chain {
+            if let Some(level) = style.outline_level {
+                return outline_to_heading(level);
+            }
+            let named_level = style
+                .name
+                .as_deref()
+                .and_then(heading_level_from_name)
+                .or_else(|| heading_level_from_name(&style.id));
+            if named_level.is_some() {
+                return named_level;
+            }
+        }
+        None
+    }
+
+    /// 段落样式是否为内置的 "caption" 样式或由其派生
+    pub fn is_caption(&self, style_id: Option<&str>) -> bool {
+        let Some(style_id) = style_id else {
+            return false;
+        };
+        let is_caption = |name: &str| name.eq_ignore_ascii_case("caption");
+        let chain = self.inheritance_chain(style_id);
+        if chain.is_empty() {
+            return is_caption(style_id);
+        }
+        chain
+            .iter()
+            .any(|style| is_caption(&style.id) || style.name.as_deref().is_some_and(is_caption))
+    }
+}
+
+/// 大纲级别 0-8 对应标题 1-9；级别 9 明确表示正文
+fn outline_to_heading(level: u8) -> Option<u8> {
+    (level < 9).then_some(level + 1)
+}
+
+/// 任一已知界面语言中 "Heading N" 样式名的级别
+pub fn heading_level_from_name(name: &str) -> Option<u8> {
+    let name = name.trim().to_lowercase();
+    HEADING_NAME_PREFIXES.iter().find_map(|prefix| {
+        let rest = name.strip_prefix(prefix)?.trim();
+        rest.parse::<u8>().ok().filter(|level| (1..=9).contains(level))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLES: &str = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+        <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+        <w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="0"/></w:pPr></w:style>
+        <w:style w:type="paragraph" w:styleId="ChapterTitle"><w:name w:val="Chapter Title"/><w:basedOn w:val="Heading1"/></w:style>
+        <w:style w:type="paragraph" w:styleId="2"><w:name w:val="标题 2"/><w:basedOn w:val="Normal"/></w:style>
+        <w:style w:type="paragraph" w:styleId="berschrift3"><w:name w:val="Überschrift 3"/></w:style>
+        <w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Quote"/></w:style>
+    </w:styles>"#;
+
+    #[test]
+    fn test_heading_levels_from_styles() {
+        let styles = StyleSheet::parse(STYLES).unwrap();
+
+        assert_eq!(styles.heading_level(Some("Heading1"), None), Some(1));
+        // 通过 basedOn 继承
+        assert_eq!(styles.heading_level(Some("ChapterTitle"), None), Some(1));
+        // 本地化名称
+        assert_eq!(styles.heading_level(Some("2"), None), Some(2));
+        assert_eq!(styles.heading_level(Some("berschrift3"), None), Some(3));
+        // 未知的内置样式 ID
+        assert_eq!(styles.heading_level(Some("Heading4"), None), Some(4));
+
+        assert_eq!(styles.heading_level(Some("Normal"), None), None);
+        assert_eq!(styles.heading_level(None, None), None);
+        // 自引用的 basedOn 必须能终止
+        assert_eq!(styles.heading_level(Some("Quote"), None), None);
+    }
+
+    #[test]
+    fn test_direct_outline_level() {
+        let styles = StyleSheet::parse(STYLES).unwrap();
+
+        assert_eq!(styles.heading_level(Some("Normal"), Some(2)), Some(3));
+        // 正文大纲级别优先于标题样式
+        assert_eq!(styles.heading_level(Some("Heading1"), Some(9)), None);
+    }
+
+    #[test]
+    fn test_run_properties_inheritance() {
+        let styles = StyleSheet::parse(r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri"/><w:sz w:val="22"/></w:rPr></w:rPrDefault></w:docDefaults>
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Heading1"><w:basedOn w:val="Normal"/><w:rPr><w:b/><w:color w:val="2F5496"/><w:sz w:val="32"/></w:rPr></w:style>
+            <w:style w:type="paragraph" w:styleId="Quiet"><w:basedOn w:val="Heading1"/><w:rPr><w:b w:val="0"/></w:rPr></w:style>
+            <w:style w:type="character" w:styleId="Emphasis"><w:rPr><w:i/></w:rPr></w:style>
+            <w:style w:type="table" w:styleId="Grid"><w:tblStylePr w:type="firstRow"><w:rPr><w:b/></w:rPr></w:tblStylePr></w:style>
+        </w:styles>"#).unwrap();
+
+        let normal = styles.run_properties(None, None).to_format_info();
+        assert_eq!(normal.font_family.as_deref(), Some("Calibri"));
+        assert_eq!(normal.font_size, Some(11.0));
+        assert!(!normal.bold);
+
+        let heading = styles.run_properties(Some("Heading1"), Some("Emphasis")).to_format_info();
+        assert!(heading.bold && heading.italic);
+        assert_eq!(heading.font_size, Some(16.0));
+        assert_eq!(heading.color.as_deref(), Some("#2F5496"));
+
+        // 较近的样式覆盖继承的值
+        let quiet = styles.run_properties(Some("Quiet"), None).to_format_info();
+        assert!(!quiet.bold);
+        assert_eq!(quiet.font_size, Some(16.0));
+
+        // 表格条件格式不属于样式的 run 属性
+        assert_eq!(styles.get("Grid").unwrap().run_properties, RunProperties::default());
+    }
+
+    #[test]
+    fn test_empty_property_elements() {
+        // `<w:rPr/>` 和 `<w:pPr/>` 没有结束标签，不能吞掉后面的元素
+        let styles = StyleSheet::parse(r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:style w:type="paragraph" w:styleId="Plain"><w:name w:val="Plain"/><w:pPr/><w:rPr/></w:style>
+            <w:style w:type="paragraph" w:styleId="Section"><w:name w:val="Section Title"/><w:basedOn w:val="Plain"/><w:pPr><w:outlineLvl w:val="1"/></w:pPr></w:style>
+            <w:style w:type="paragraph" w:styleId="Body"><w:pPr/><w:rPr><w:b/></w:rPr><w:pPr/><w:outlineLvl w:val="3"/></w:style>
+        </w:styles>"#).unwrap();
+
+        let section = styles.get("Section").unwrap();
+        assert_eq!(section.name.as_deref(), Some("Section Title"));
+        assert_eq!(section.based_on.as_deref(), Some("Plain"));
+        assert_eq!(styles.heading_level(Some("Section"), None), Some(2));
+        assert!(styles.run_properties(Some("Body"), None).to_format_info().bold);
+        // `w:pPr` 之外的大纲级别被忽略
+        assert_eq!(styles.heading_level(Some("Body"), None), None);
+    }
+
+    #[test]
+    fn test_style_numbering() {
+        let styles = StyleSheet::parse(r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:style w:type="paragraph" w:styleId="ListNumber"><w:pPr><w:numPr><w:numId w:val="5"/></w:numPr></w:pPr></w:style>
+            <w:style w:type="paragraph" w:styleId="ListNumber2"><w:basedOn w:val="ListNumber"/><w:pPr><w:numPr><w:ilvl w:val="1"/><w:numId w:val="5"/></w:numPr></w:pPr></w:style>
+            <w:style w:type="paragraph" w:styleId="Unnumbered"><w:basedOn w:val="ListNumber"/><w:pPr><w:numPr><w:numId w:val="0"/></w:numPr></w:pPr></w:style>
+        </w:styles>"#).unwrap();
+
+        assert_eq!(styles.numbering(Some("ListNumber")), Some((5, 0)));
+        assert_eq!(styles.numbering(Some("ListNumber2")), Some((5, 1)));
+        assert_eq!(styles.numbering(Some("Unnumbered")), None);
+        assert_eq!(styles.numbering(None), None);
+    }
+
+    #[test]
+    fn test_heading_level_from_name() {
+        assert_eq!(heading_level_from_name("heading 2"), Some(2));
+        assert_eq!(heading_level_from_name("Heading2"), Some(2));
+        assert_eq!(heading_level_from_name("标题 1"), Some(1));
+        assert_eq!(heading_level_from_name("Titre 5"), Some(5));
+        assert_eq!(heading_level_from_name("Title"), None);
+        assert_eq!(heading_level_from_name("标题"), None);
+        assert_eq!(heading_level_from_name("Heading 10"), None);
+    }
+}
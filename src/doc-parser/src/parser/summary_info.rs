@@ -0,0 +1,209 @@
+//! OLE 复合文档的 `\u{5}SummaryInformation` 属性集（MS-OLEPS）：
+//! 老式 Word 文件的标题、作者、时间戳和统计信息。
+
+use super::word97::{u16_at, u32_at};
+use encoding_rs::Encoding;
+
+// SummaryInformation 属性集的属性标识符
+const PID_CODEPAGE: u32 = 1;
+const PID_TITLE: u32 = 2;
+const PID_SUBJECT: u32 = 3;
+const PID_AUTHOR: u32 = 4;
+const PID_KEYWORDS: u32 = 5;
+const PID_COMMENTS: u32 = 6;
+const PID_TEMPLATE: u32 = 7;
+const PID_LASTAUTHOR: u32 = 8;
+const PID_REVNUMBER: u32 = 9;
+const PID_CREATE_DTM: u32 = 12;
+const PID_LASTSAVE_DTM: u32 = 13;
+const PID_PAGECOUNT: u32 = 14;
+const PID_WORDCOUNT: u32 = 15;
+const PID_APPNAME: u32 = 18;
+
+// 属性类型
+const VT_I2: u16 = 0x0002;
+const VT_I4: u16 = 0x0003;
+const VT_LPSTR: u16 = 0x001E;
+const VT_LPWSTR: u16 = 0x001F;
+const VT_FILETIME: u16 = 0x0040;
+
+/// UTF-16 字符串的代码页
+const CP_UTF16: u16 = 1200;
+/// 1601-01-01（FILETIME 纪元）与 1970-01-01 之间的秒数
+const FILETIME_UNIX_OFFSET: u64 = 11_644_473_600;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SummaryInformation {
+    pub title: Option<String>,
+    pub subject: Option<String>,
+    pub author: Option<String>,
+    pub keywords: Option<String>,
+    pub comments: Option<String>,
+    pub template: Option<String>,
+    pub last_author: Option<String>,
+    pub revision: Option<u32>,
+    /// ISO 8601 时间戳（UTC）
+    pub created: Option<String>,
+    pub modified: Option<String>,
+    pub pages: Option<usize>,
+    pub words: Option<usize>,
+    pub application: Option<String>,
+}
+
+enum Value {
+    Int(i32),
+    Text(String),
+    Time(String),
+}
+
+impl SummaryInformation {
+    /// 解析属性集流；不是有效的属性集时为 None
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if u16_at(data, 0)? != 0xFFFE || u32_at(data, 24)? == 0 {
+            return None;
+        }
+        // 第一个属性集位于其 16 字节的 FMTID 之后
+        let start = u32_at(data, 44)? as usize;
+        let set = data.get(start..)?;
+        let count = u32_at(set, 4)? as usize;
+        let properties: Vec<(u32, usize)> = (0..count)
+            .map_while(|i| Some((u32_at(set, 8 + i * 8)?, u32_at(set, 12 + i * 8)? as usize)))
+            .collect();
+
+        let codepage = properties
+            .iter()
+            .find(|(id, _)| *id == PID_CODEPAGE)
+            .and_then(|(_, offset)| match read_value(set, *offset, 0)? {
+                Value::Int(codepage) => Some(codepage as u16),
+                _ => None,
+            })
+            .unwrap_or(1252);
+
+        let mut info = Self::default();
+        for (id, offset) in properties {
+            let Some(value) = read_value(set, offset, codepage) else {
+                continue;
+            };
+            match (id, value) {
+                (PID_TITLE, Value::Text(text)) => info.title = non_empty(text),
+                (PID_SUBJECT, Value::Text(text)) => info.subject = non_empty(text),
+                (PID_AUTHOR, Value::Text(text)) => info.author = non_empty(text),
+                (PID_KEYWORDS, Value::Text(text)) => info.keywords = non_empty(text),
+                (PID_COMMENTS, Value::Text(text)) => info.comments = non_empty(text),
+                (PID_TEMPLATE, Value::Text(text)) => info.template = non_empty(text),
+                (PID_LASTAUTHOR, Value::Text(text)) => info.last_author = non_empty(text),
+                (PID_REVNUMBER, Value::Text(text)) => info.revision = text.trim().parse().ok(),
+                (PID_APPNAME, Value::Text(text)) => info.application = non_empty(text),
+                (PID_CREATE_DTM, Value::Time(time)) => info.created = Some(time),
+                (PID_LASTSAVE_DTM, Value::Time(time)) => info.modified = Some(time),
+                (PID_PAGECOUNT, Value::Int(count)) => info.pages = usize::try_from(count).ok(),
+                (PID_WORDCOUNT, Value::Int(count)) => info.words = usize::try_from(count).ok(),
+                _ => {}
+            }
+        }
+        Some(info)
+    }
+}
+
+fn read_value(set: &[u8], offset: usize, codepage: u16) -> Option<Value> {
+    let kind = u16_at(set, offset)?;
+    let data = offset + 4;
+    match kind {
+        VT_I2 => Some(Value::Int(u16_at(set, data)? as i16 as i32)),
+        VT_I4 => Some(Value::Int(u32_at(set, data)? as i32)),
+        VT_LPSTR => {
+            let size = u32_at(set, data)? as usize;
+            let bytes = set.get(data + 4..data + 4 + size)?;
+            Some(Value::Text(decode(bytes, codepage)))
+        }
+        VT_LPWSTR => {
+            let chars = u32_at(set, data)? as usize;
+            let bytes = set.get(data + 4..data + 4 + chars * 2)?;
+            Some(Value::Text(decode(bytes, CP_UTF16)))
+        }
+        VT_FILETIME => {
+            let ticks = u32_at(set, data)? as u64 | (u32_at(set, data + 4)? as u64) << 32;
+            // 未设置的时间戳存储为 0
+            (ticks / 10_000_000)
+                .checked_sub(FILETIME_UNIX_OFFSET)
+                .filter(|_| ticks != 0)
+                .map(|seconds| Value::Time(iso_timestamp(seconds)))
+        }
+        _ => None,
+    }
+}
+
+/// 按属性集的代码页解码字符串，去掉结束符
+fn decode(bytes: &[u8], codepage: u16) -> String {
+    let text = if codepage == CP_UTF16 {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        encoding_for_codepage(codepage).decode_without_bom_handling(bytes).0.into_owned()
+    };
+    text.trim_end_matches('\0').to_string()
+}
+
+fn encoding_for_codepage(codepage: u16) -> &'static Encoding {
+    let label = match codepage {
+        65001 => "utf-8",
+        932 => "shift_jis",
+        936 => "gbk",
+        949 => "euc-kr",
+        950 => "big5",
+        874 => "windows-874",
+        10000 => "macintosh",
+        1250..=1258 => return Encoding::for_label(format!("windows-{}", codepage).as_bytes()).unwrap_or(encoding_rs::WINDOWS_1252),
+        _ => "windows-1252",
+    };
+    Encoding::for_label(label.as_bytes()).unwrap_or(encoding_rs::WINDOWS_1252)
+}
+
+fn non_empty(text: String) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// 将 Unix 纪元以来的秒数格式化为 `YYYY-MM-DDTHH:MM:SSZ`
+fn iso_timestamp(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+    // 由 1970-01-01 以来的天数计算公历日期（Howard Hinnant 的算法）
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso_timestamp() {
+        assert_eq!(iso_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(iso_timestamp(951_782_400 + 3_723), "2000-02-29T01:02:03Z");
+        assert_eq!(iso_timestamp(1_704_067_200), "2024-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_decode_codepages() {
+        assert_eq!(decode(b"Caf\xE9\0", 1252), "Café");
+        assert_eq!(decode(&[0xD6, 0xD0, 0xCE, 0xC4, 0x00], 936), "中文");
+        assert_eq!(decode(&[0x41, 0x00, 0x00, 0x00], CP_UTF16), "A");
+    }
+}
@@ -22,6 +22,12 @@
 //! ```
 
 use crate::error::Result;
+use crate::parser::docx::paragraph_numbering;
+use crate::parser::formatting::RunProperties;
+use crate::parser::numbering::{ListCounter, Numbering};
+use crate::parser::package::{attr, built_elements};
+use crate::parser::styles::StyleSheet;
+use crate::parser::RevisionMode;
 use serde::{Deserialize, Serialize};
 
 /// Table data structure containing all extracted table information
@@ -71,6 +77,10 @@ pub struct TableCell {
     pub formatting: Option<CellFormatting>,
     /// Type of cell (header, data, merged, empty)
     pub cell_type: CellType,
+    /// Block structure of the cell (paragraphs, list items, nested tables);
+    /// empty when the cell holds a single paragraph
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<CellBlock>,
 }
 
 /// Cell alignment options
@@ -99,6 +109,39 @@ pub enum CellType {
     Empty,
 }
 
+/// Block-level content of a table cell, in document order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CellBlock {
+    /// A paragraph of text
+    Paragraph { text: String },
+    /// A numbered or bulleted list item
+    ListItem {
+        /// Nesting level, starting at 0
+        level: u8,
+        /// Whether the list is numbered
+        ordered: bool,
+        /// Rendered number or bullet (`1.`, `a)`, `•`)
+        label: String,
+        text: String,
+    },
+    /// A table nested inside the cell
+    Table { table: TableData },
+}
+
+impl CellBlock {
+    /// Plain text of the block; nested tables become one line per row
+    pub fn plain_text(&self) -> String {
+        match self {
+            CellBlock::Paragraph { text } => text.clone(),
+            CellBlock::ListItem { level, label, text, .. } => {
+                format!("{}{} {}", "  ".repeat(*level as usize), label, text)
+            }
+            CellBlock::Table { table } => table.plain_text(),
+        }
+    }
+}
+
 /// Cell formatting information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CellFormatting {
@@ -113,7 +156,7 @@ pub struct CellFormatting {
     /// Text color (hex format)
     pub text_color: Option<String>,
     /// Font size in points
-    pub font_size: Option<u32>,
+    pub font_size: Option<f32>,
     /// Font family name
     pub font_family: Option<String>,
 }
@@ -146,6 +189,8 @@ pub struct TableExtractionConfig {
     pub merge_cells_handling: MergeCellsHandling,
     /// Output format preference
     pub output_format: TableOutputFormat,
+    /// How tracked changes in cell text are applied
+    pub revisions: RevisionMode,
 }
 
 /// Merged cells handling strategies
@@ -176,6 +221,33 @@ pub enum TableOutputFormat {
     HTML,
 }
 
+impl TableOutputFormat {
+    /// Parse a format name as accepted on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "text" | "txt" => Some(Self::PlainText),
+            "csv" => Some(Self::CSV),
+            "tsv" => Some(Self::TSV),
+            "markdown" | "md" => Some(Self::Markdown),
+            "json" => Some(Self::JSON),
+            "html" => Some(Self::HTML),
+            _ => None,
+        }
+    }
+
+    /// File extension for files written in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::PlainText => "txt",
+            Self::CSV => "csv",
+            Self::TSV => "tsv",
+            Self::Markdown => "md",
+            Self::JSON => "json",
+            Self::HTML => "html",
+        }
+    }
+}
+
 /// Cell data types for analysis
 #[derive(Debug, Clone, PartialEq)]
 enum CellDataType {
@@ -191,8 +263,9 @@ enum CellDataType {
     Boolean,
 }
 
-/// Merged cell range information
-#[derive(Debug, Clone)]
+/// A merged cell range on the table grid (inclusive bounds), from
+/// `w:gridSpan` (columns) and `w:vMerge` (rows)
+#[derive(Debug, Clone, PartialEq)]
 struct MergedRange {
     /// Starting row index
     start_row: usize,
@@ -202,22 +275,29 @@ struct MergedRange {
     start_col: usize,
     /// Ending column index
     end_col: usize,
-    /// Type of merge
-    merge_type: MergeType,
 }
 
-/// Types of cell merges
-#[derive(Debug, Clone, PartialEq)]
-enum MergeType {
-    /// Horizontal merge (across columns)
-    Horizontal,
-    /// Vertical merge (across rows)
-    Vertical,
+impl MergedRange {
+    /// Whether the range covers more than its origin cell
+    fn is_merge(&self) -> bool {
+        self.end_row > self.start_row || self.end_col > self.start_col
+    }
+
+    /// Grid positions covered by the range, excluding the origin cell
+    fn covered(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start_row..=self.end_row)
+            .flat_map(move |row| (self.start_col..=self.end_col).map(move |col| (row, col)))
+            .skip(1)
+    }
 }
 
 /// Table extractor with configuration
 pub struct TableExtractor {
     config: TableExtractionConfig,
+    /// Document styles, used to resolve inherited run formatting
+    styles: StyleSheet,
+    /// List definitions, used to label list items inside cells
+    numbering: Numbering,
 }
 
 impl TableData {
@@ -297,9 +377,30 @@ impl TableData {
         self.row_count = self.rows.len();
         self.column_count = self.rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
     }
+
+    /// Flatten the table to text: one line per non-empty row, cells joined with " | "
+    pub fn plain_text(&self) -> String {
+        self.rows
+            .iter()
+            .filter(|row| !row.is_blank())
+            .map(TableRow::plain_text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 impl TableRow {
+    /// Cells trimmed and joined with " | ", as in [`TableData::plain_text`]
+    pub fn plain_text(&self) -> String {
+        let cells: Vec<&str> = self.cells.iter().map(|cell| cell.content.trim()).collect();
+        cells.join(" | ").trim_end().to_string()
+    }
+
+    /// Whether every cell is empty or whitespace
+    pub fn is_blank(&self) -> bool {
+        self.cells.iter().all(|cell| cell.content.trim().is_empty())
+    }
+
     /// Create a new table row
     pub fn new(row_index: usize) -> Self {
         Self {
@@ -358,7 +459,25 @@ impl TableCell {
             alignment: None,
             formatting: None,
             cell_type: CellType::Data,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Create a cell from its blocks; `content` is their plain text, one block per line
+    pub fn from_blocks(blocks: Vec<CellBlock>) -> Self {
+        let content = blocks
+            .iter()
+            .map(CellBlock::plain_text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if content.trim().is_empty() {
+            return Self::empty();
+        }
+        let mut cell = Self::new(content);
+        if blocks.len() > 1 || blocks.iter().any(|block| !matches!(block, CellBlock::Paragraph { .. })) {
+            cell.blocks = blocks;
         }
+        cell
     }
 
     /// Create a new table cell with formatting
@@ -371,6 +490,7 @@ impl TableCell {
             alignment: None,
             formatting: Some(formatting),
             cell_type: CellType::Data,
+            blocks: Vec::new(),
         }
     }
 
@@ -384,6 +504,7 @@ impl TableCell {
             alignment: None,
             formatting: None,
             cell_type: CellType::Empty,
+            blocks: Vec::new(),
         }
     }
 
@@ -487,6 +608,7 @@ impl TableExtractionConfig {
             include_empty_cells: false,
             merge_cells_handling: MergeCellsHandling::Preserve,
             output_format: TableOutputFormat::PlainText,
+            revisions: RevisionMode::Accept,
         }
     }
 
@@ -499,6 +621,7 @@ impl TableExtractionConfig {
             include_empty_cells: false,
             merge_cells_handling: MergeCellsHandling::Ignore,
             output_format: TableOutputFormat::PlainText,
+            revisions: RevisionMode::Accept,
         }
     }
 
@@ -511,6 +634,7 @@ impl TableExtractionConfig {
             include_empty_cells: true,
             merge_cells_handling: MergeCellsHandling::Preserve,
             output_format: TableOutputFormat::JSON,
+            revisions: RevisionMode::Accept,
         }
     }
 
@@ -532,6 +656,12 @@ impl TableExtractionConfig {
         self
     }
 
+    /// Builder method to set how tracked changes are applied
+    pub fn with_revisions(mut self, revisions: RevisionMode) -> Self {
+        self.revisions = revisions;
+        self
+    }
+
     /// Builder method to set extraction mode
     pub fn with_mode(mut self, mode: TableExtractionMode) -> Self {
         self.mode = mode;
@@ -554,31 +684,99 @@ impl TableExtractionConfig {
 impl TableExtractor {
     /// Create a new table extractor with configuration
     pub fn new(config: TableExtractionConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            styles: StyleSheet::default(),
+            numbering: Numbering::default(),
+        }
+    }
+
+    /// Resolve run formatting through the document's styles
+    pub fn with_styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Label list items in cells using the document's numbering definitions
+    pub fn with_numbering(mut self, numbering: Numbering) -> Self {
+        self.numbering = numbering;
+        self
     }
 
     /// Extract table data from a docx-rs Table
+    ///
+    /// Cells are placed on the table grid: a cell spanning `w:gridSpan`
+    /// columns is followed by empty cells for the columns it covers, and a
+    /// `w:vMerge` continuation leaves an empty cell under the cell that
+    /// started the merge. Rows are padded to the `w:tblGrid` width, so the
+    /// result is rectangular.
     pub fn extract_table(&self, table: &docx_rs::Table) -> Result<TableData> {
-        let mut table_data = TableData::with_capacity(table.rows.len());
-        
-        // Step 1: Extract basic table structure
+        let mut rows: Vec<Vec<TableCell>> = Vec::with_capacity(table.rows.len());
+        let mut merged_ranges: Vec<MergedRange> = Vec::new();
+        // Per grid column, the range of a vertical merge that may continue in the next row
+        let mut open_merges: Vec<Option<usize>> = Vec::new();
+
+        // Step 1: Extract the cells onto the table grid
         for (row_index, row_child) in table.rows.iter().enumerate() {
             // TableChild should be TableRow
             let docx_rs::TableChild::TableRow(row) = row_child;
-            let mut table_row = TableRow::with_capacity(row_index, row.cells.len());
-            
+            let grid_before = grid_before(&row.property, table.grid.len());
+            let mut cells: Vec<TableCell> = (0..grid_before).map(|_| TableCell::empty()).collect();
+            let mut continued: Vec<Option<usize>> = Vec::new();
+
             for cell_child in &row.cells {
                 // TableRowChild should be TableCell
                 let docx_rs::TableRowChild::TableCell(cell) = cell_child;
-                let cell_data = self.extract_cell_data(cell)?;
-                table_row.add_cell(cell_data);
+                let col = cells.len();
+                let (span, vertical_merge) = cell_merge(&cell.property);
+                let vertical_merge = vertical_merge.as_deref();
+                continued.resize(col + span, None);
+
+                // A continuation extends the merge above it when it covers the same columns
+                let above = open_merges.get(col).copied().flatten().filter(|&index| {
+                    let range: &MergedRange = &merged_ranges[index];
+                    range.start_col == col && range.end_col == col + span - 1
+                });
+                if let (Some("continue"), Some(index)) = (vertical_merge, above) {
+                    merged_ranges[index].end_row = row_index;
+                    continued[col..col + span].fill(Some(index));
+                    cells.extend((0..span).map(|_| TableCell::empty()));
+                    continue;
+                }
+
+                cells.push(self.extract_cell_data(cell)?);
+                cells.extend((1..span).map(|_| TableCell::empty()));
+                if span > 1 || vertical_merge == Some("restart") {
+                    merged_ranges.push(MergedRange {
+                        start_row: row_index,
+                        end_row: row_index,
+                        start_col: col,
+                        end_col: col + span - 1,
+                    });
+                    if vertical_merge == Some("restart") {
+                        continued[col..col + span].fill(Some(merged_ranges.len() - 1));
+                    }
+                }
+            }
+
+            open_merges = continued;
+            rows.push(cells);
+        }
+
+        let column_count = rows.iter().map(Vec::len).max().unwrap_or(0).max(table.grid.len());
+        let mut table_data = TableData::with_capacity(rows.len());
+        for (row_index, mut cells) in rows.into_iter().enumerate() {
+            cells.resize_with(column_count, TableCell::empty);
+            let mut table_row = TableRow::with_capacity(row_index, column_count);
+            for cell in cells {
+                table_row.add_cell(cell);
             }
-            
             table_data.add_row(table_row);
         }
-        
+        merged_ranges.retain(MergedRange::is_merge);
+
         // Step 2: Process according to configuration
-        self.process_table_data(&mut table_data)?;
+        self.process_table_data(&mut table_data, &merged_ranges)?;
         
         Ok(table_data)
     }
@@ -599,33 +797,47 @@ impl TableExtractor {
 
     /// Extract cell data from a docx-rs TableCell
     fn extract_cell_data(&self, cell: &docx_rs::TableCell) -> Result<TableCell> {
-        let mut content = String::new();
         let mut formatting = CellFormatting::new();
-        
-        // Extract text content from cell children
+        // List numbering restarts in every cell
+        let mut list_counter = ListCounter::new();
+        let mut blocks = Vec::new();
+
         for child in &cell.children {
-            if let docx_rs::TableCellContent::Paragraph(para) = child {
-                let para_text = self.extract_paragraph_text(para)?;
-                if !para_text.is_empty() {
-                    content.push_str(&para_text);
-                    content.push('\n');
+            match child {
+                docx_rs::TableCellContent::Paragraph(para) => {
+                    // Empty list paragraphs still consume a number
+                    let list = paragraph_numbering(para, &self.styles)
+                        .and_then(|(num_id, level)| list_counter.next(&self.numbering, num_id, level));
+                    let text = self.extract_paragraph_text(para)?.trim().to_string();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    blocks.push(match list {
+                        Some(list) => CellBlock::ListItem {
+                            level: list.level,
+                            ordered: list.ordered,
+                            label: if list.label.trim().is_empty() { "•".to_string() } else { list.label.trim().to_string() },
+                            text,
+                        },
+                        None => CellBlock::Paragraph { text },
+                    });
+                }
+                docx_rs::TableCellContent::Table(table) => {
+                    let table = self.extract_table(table)?;
+                    if !table.is_empty() {
+                        blocks.push(CellBlock::Table { table });
+                    }
                 }
+                _ => {}
             }
         }
         
-        // Clean up content
-        content = content.trim().to_string();
-        
         // Extract formatting if required
         if self.config.preserve_formatting {
             formatting = self.extract_cell_formatting(cell)?;
         }
         
-        let mut table_cell = if content.is_empty() {
-            TableCell::empty()
-        } else {
-            TableCell::new(content)
-        };
+        let mut table_cell = TableCell::from_blocks(blocks);
         
         if formatting.has_formatting() {
             table_cell.formatting = Some(formatting);
@@ -636,19 +848,7 @@ impl TableExtractor {
 
     /// Extract text from a paragraph
     fn extract_paragraph_text(&self, paragraph: &docx_rs::Paragraph) -> Result<String> {
-        let mut text = String::new();
-        
-        for child in &paragraph.children {
-            if let docx_rs::ParagraphChild::Run(run) = child {
-                for run_child in &run.children {
-                    if let docx_rs::RunChild::Text(text_element) = run_child {
-                        text.push_str(&text_element.text);
-                    }
-                }
-            }
-        }
-        
-        Ok(text)
+        Ok(super::inline::paragraph_text(paragraph, self.config.revisions))
     }
 
     /// Extract formatting from a cell (enhanced implementation)
@@ -676,9 +876,10 @@ impl TableExtractor {
         let mut formatting = CellFormatting::new();
         
         // Extract formatting from runs within the paragraph
+        let paragraph_style = paragraph.property.style.as_ref().map(|style| style.val.as_str());
         for child in &paragraph.children {
             if let docx_rs::ParagraphChild::Run(run) = child {
-                let run_formatting = self.extract_run_formatting(run)?;
+                let run_formatting = self.extract_run_formatting(run, paragraph_style)?;
                 formatting = self.merge_formatting(formatting, run_formatting);
             }
         }
@@ -686,15 +887,31 @@ impl TableExtractor {
         Ok(formatting)
     }
     
-    /// Extract formatting from a text run
-    fn extract_run_formatting(&self, _run: &docx_rs::Run) -> Result<CellFormatting> {
-        let formatting = CellFormatting::new();
-        
-        // TODO: Implement proper formatting extraction when docx-rs API is more stable
-        // For now, return basic formatting to avoid compilation issues
-        // This will be enhanced in future versions
-        
-        Ok(formatting)
+    /// Extract formatting from a text run, including formatting inherited from styles
+    fn extract_run_formatting(&self, run: &docx_rs::Run, paragraph_style: Option<&str>) -> Result<CellFormatting> {
+        // Runs without text (e.g. bookmarks or breaks) do not contribute formatting
+        let has_text = run
+            .children
+            .iter()
+            .any(|child| matches!(child, docx_rs::RunChild::Text(text) if !text.text.trim().is_empty()));
+        if !has_text {
+            return Ok(CellFormatting::new());
+        }
+
+        let run_style = run.run_property.style.as_ref().map(|style| style.val.as_str());
+        let mut properties = self.styles.run_properties(paragraph_style, run_style);
+        properties.merge(&RunProperties::from_docx(&run.run_property));
+        let format = properties.to_format_info();
+
+        Ok(CellFormatting {
+            bold: format.bold,
+            italic: format.italic,
+            underline: format.underline,
+            background_color: format.highlight,
+            text_color: format.color,
+            font_size: format.font_size,
+            font_family: format.font_family,
+        })
     }
     
     /// Extract formatting from cell properties
@@ -735,31 +952,8 @@ impl TableExtractor {
         base
     }
     
-    /// Convert highlight color to hex format
-    fn convert_highlight_to_hex(&self, highlight: &str) -> String {
-        // Convert common highlight colors to hex values
-        match highlight.to_lowercase().as_str() {
-            "yellow" => "#FFFF00".to_string(),
-            "green" => "#00FF00".to_string(),
-            "cyan" => "#00FFFF".to_string(),
-            "magenta" => "#FF00FF".to_string(),
-            "blue" => "#0000FF".to_string(),
-            "red" => "#FF0000".to_string(),
-            "darkblue" => "#000080".to_string(),
-            "darkcyan" => "#008080".to_string(),
-            "darkgreen" => "#008000".to_string(),
-            "darkmagenta" => "#800080".to_string(),
-            "darkred" => "#800000".to_string(),
-            "darkyellow" => "#808000".to_string(),
-            "darkgray" => "#808080".to_string(),
-            "lightgray" => "#C0C0C0".to_string(),
-            "black" => "#000000".to_string(),
-            _ => format!("#{}", highlight), // Assume it's already a hex value
-        }
-    }
-
     /// Process extracted table data according to configuration
-    fn process_table_data(&self, table_data: &mut TableData) -> Result<()> {
+    fn process_table_data(&self, table_data: &mut TableData, merged_ranges: &[MergedRange]) -> Result<()> {
         // Update statistics
         table_data.update_statistics();
         
@@ -769,7 +963,7 @@ impl TableExtractor {
         }
         
         // Handle merged cells
-        self.handle_merged_cells(table_data)?;
+        self.handle_merged_cells(table_data, merged_ranges)?;
         
         // Filter empty cells if configured
         if !self.config.include_empty_cells {
@@ -1231,145 +1425,37 @@ impl TableExtractor {
     }
 
     /// Handle merged cells according to configuration
-    fn handle_merged_cells(&self, table_data: &mut TableData) -> Result<()> {
+    fn handle_merged_cells(&self, table_data: &mut TableData, merged_ranges: &[MergedRange]) -> Result<()> {
         match self.config.merge_cells_handling {
             MergeCellsHandling::Ignore => {
-                // No processing needed
+                // Covered positions stay as empty cells
                 Ok(())
             }
             MergeCellsHandling::Preserve => {
-                self.mark_merged_cells(table_data)
+                self.mark_merged_cells(table_data, merged_ranges)
             }
             MergeCellsHandling::Expand => {
-                self.expand_merged_cells(table_data)
+                self.expand_merged_cells(table_data, merged_ranges)
             }
         }
     }
     
-    /// Mark merged cells in the table data
-    fn mark_merged_cells(&self, table_data: &mut TableData) -> Result<()> {
-        // Detect merged cells by analyzing cell properties and content patterns
-        let mut merged_ranges = Vec::new();
-        
-        // Step 1: Detect horizontal merges (same row, consecutive empty cells)
-        for (row_idx, row) in table_data.rows.iter().enumerate() {
-            let mut current_merge: Option<(usize, usize)> = None;
-            
-            for (col_idx, cell) in row.cells.iter().enumerate() {
-                if cell.is_empty() {
-                    if let Some((start_col, _)) = current_merge {
-                        // Continue the merge
-                        current_merge = Some((start_col, col_idx));
-                    } else {
-                        // Start a new merge if previous cell had content
-                        if col_idx > 0 && !row.cells[col_idx - 1].is_empty() {
-                            current_merge = Some((col_idx - 1, col_idx));
-                        }
-                    }
-                } else {
-                    // End current merge if any
-                    if let Some((start_col, end_col)) = current_merge {
-                        if end_col > start_col {
-                            merged_ranges.push(MergedRange {
-                                start_row: row_idx,
-                                end_row: row_idx,
-                                start_col,
-                                end_col,
-                                merge_type: MergeType::Horizontal,
-                            });
-                        }
-                        current_merge = None;
-                    }
-                }
-            }
-            
-            // Handle merge at end of row
-            if let Some((start_col, end_col)) = current_merge {
-                if end_col > start_col {
-                    merged_ranges.push(MergedRange {
-                        start_row: row_idx,
-                        end_row: row_idx,
-                        start_col,
-                        end_col,
-                        merge_type: MergeType::Horizontal,
-                    });
-                }
-            }
-        }
-        
-        // Step 2: Detect vertical merges (same column, consecutive empty cells)
-        for col_idx in 0..table_data.column_count {
-            let mut current_merge: Option<(usize, usize)> = None;
-            
-            for (row_idx, row) in table_data.rows.iter().enumerate() {
-                if let Some(cell) = row.cells.get(col_idx) {
-                    if cell.is_empty() {
-                        if let Some((start_row, _)) = current_merge {
-                            // Continue the merge
-                            current_merge = Some((start_row, row_idx));
-                        } else {
-                            // Start a new merge if previous cell had content
-                            if row_idx > 0 {
-                                if let Some(prev_cell) = table_data.rows[row_idx - 1].cells.get(col_idx) {
-                                    if !prev_cell.is_empty() {
-                                        current_merge = Some((row_idx - 1, row_idx));
-                                    }
-                                }
-                            }
-                        }
-                    } else {
-                        // End current merge if any
-                        if let Some((start_row, end_row)) = current_merge {
-                            if end_row > start_row {
-                                merged_ranges.push(MergedRange {
-                                    start_row,
-                                    end_row,
-                                    start_col: col_idx,
-                                    end_col: col_idx,
-                                    merge_type: MergeType::Vertical,
-                                });
-                            }
-                            current_merge = None;
-                        }
-                    }
-                }
-            }
-            
-            // Handle merge at end of column
-            if let Some((start_row, end_row)) = current_merge {
-                if end_row > start_row {
-                    merged_ranges.push(MergedRange {
-                        start_row,
-                        end_row,
-                        start_col: col_idx,
-                        end_col: col_idx,
-                        merge_type: MergeType::Vertical,
-                    });
-                }
+    /// Record spans on the origin cell of each range and mark the covered cells as merged
+    fn mark_merged_cells(&self, table_data: &mut TableData, merged_ranges: &[MergedRange]) -> Result<()> {
+        for range in merged_ranges {
+            if let Some(origin) = table_data
+                .rows
+                .get_mut(range.start_row)
+                .and_then(|row| row.cells.get_mut(range.start_col))
+            {
+                let colspan = range.end_col - range.start_col + 1;
+                let rowspan = range.end_row - range.start_row + 1;
+                origin.colspan = (colspan > 1).then_some(colspan);
+                origin.rowspan = (rowspan > 1).then_some(rowspan);
             }
-        }
-        
-        // Step 3: Apply merged cell information
-        self.apply_merged_ranges(table_data, &merged_ranges)?;
-        
-        Ok(())
-    }
-    
-    /// Expand merged cells by filling empty cells with the merged content
-    fn expand_merged_cells(&self, table_data: &mut TableData) -> Result<()> {
-        // First mark merged cells
-        self.mark_merged_cells(table_data)?;
-        
-        // Then expand the content
-        for row in &mut table_data.rows {
-            for cell in &mut row.cells {
-                if cell.is_merged() {
-                    // Find the source cell and copy its content
-                    if let Some(source_content) = self.find_merged_source_content(cell) {
-                        cell.content = source_content;
-                        cell.formatted_content = cell.content.clone();
-                        cell.cell_type = CellType::Data;
-                    }
+            for (row, col) in range.covered() {
+                if let Some(cell) = table_data.rows.get_mut(row).and_then(|row| row.cells.get_mut(col)) {
+                    cell.set_merged(None, None);
                 }
             }
         }
@@ -1377,40 +1463,18 @@ impl TableExtractor {
         Ok(())
     }
     
-    /// Apply merged ranges to table data
-    fn apply_merged_ranges(&self, table_data: &mut TableData, merged_ranges: &[MergedRange]) -> Result<()> {
+    /// Expand merged cells by copying the origin cell into every position it covers
+    fn expand_merged_cells(&self, table_data: &mut TableData, merged_ranges: &[MergedRange]) -> Result<()> {
         for range in merged_ranges {
-            match range.merge_type {
-                MergeType::Horizontal => {
-                    // Mark horizontal merge
-                    if let Some(row) = table_data.rows.get_mut(range.start_row) {
-                        for col_idx in range.start_col..=range.end_col {
-                            if let Some(cell) = row.cells.get_mut(col_idx) {
-                                if col_idx == range.start_col {
-                                    // Source cell
-                                    cell.set_merged(Some(range.end_col - range.start_col + 1), None);
-                                } else {
-                                    // Merged cell
-                                    cell.set_merged(None, None);
-                                }
-                            }
-                        }
-                    }
-                }
-                MergeType::Vertical => {
-                    // Mark vertical merge
-                    for row_idx in range.start_row..=range.end_row {
-                        if let Some(row) = table_data.rows.get_mut(row_idx) {
-                            if let Some(cell) = row.cells.get_mut(range.start_col) {
-                                if row_idx == range.start_row {
-                                    // Source cell
-                                    cell.set_merged(None, Some(range.end_row - range.start_row + 1));
-                                } else {
-                                    // Merged cell
-                                    cell.set_merged(None, None);
-                                }
-                            }
-                        }
+            let Some(origin) = table_data.get_cell(range.start_row, range.start_col).cloned() else {
+                continue;
+            };
+            for (row, col) in range.covered() {
+                if let Some(cell) = table_data.rows.get_mut(row).and_then(|row| row.cells.get_mut(col)) {
+                    let is_header = cell.is_header();
+                    *cell = origin.clone();
+                    if is_header {
+                        cell.cell_type = CellType::Header;
                     }
                 }
             }
@@ -1418,13 +1482,6 @@ impl TableExtractor {
         
         Ok(())
     }
-    
-    /// Find the source content for a merged cell
-    fn find_merged_source_content(&self, _merged_cell: &TableCell) -> Option<String> {
-        // This is a simplified implementation
-        // In a real implementation, you would track the source cell for each merged cell
-        Some("Merged Content".to_string())
-    }
 
     /// Filter empty cells if configured
     fn filter_empty_cells(&self, table_data: &mut TableData) -> Result<()> {
@@ -1442,6 +1499,31 @@ impl TableExtractor {
     }
 }
 
+/// The `w:gridSpan` and `w:vMerge` of a cell, read from the `w:tcPr` docx-rs writes back
+fn cell_merge(property: &docx_rs::TableCellProperty) -> (usize, Option<String>) {
+    let mut span = 1;
+    let mut vertical_merge = None;
+    for (_, element) in built_elements(property).iter().filter(|(depth, _)| *depth == 1) {
+        match element.local_name().as_ref() {
+            b"gridSpan" => span = attr(element, "val").and_then(|val| val.parse().ok()).unwrap_or(1).max(1),
+            // A `w:vMerge` without a value continues the merge above
+            b"vMerge" => vertical_merge = Some(attr(element, "val").unwrap_or_else(|| "continue".to_string())),
+            _ => {}
+        }
+    }
+    (span, vertical_merge)
+}
+
+/// The `w:gridBefore` of a row, at most `columns`
+///
+/// docx-rs neither exposes this value nor writes it back, so it is found by
+/// comparing the row with copies given each candidate count.
+fn grid_before(property: &docx_rs::TableRowProperty, columns: usize) -> usize {
+    (1..=columns as u32)
+        .find(|&count| property.clone().grid_before(count) == *property)
+        .map_or(0, |count| count as usize)
+}
+
 impl Default for TableData {
     fn default() -> Self {
         Self::new()
@@ -1474,7 +1556,7 @@ mod tests {
 
     #[test]
     fn test_table_row_creation() {
-        let mut row = TableRow::new(0);
+        let row = TableRow::new(0);
         assert!(row.is_empty());
         assert_eq!(row.cell_count(), 0);
         assert_eq!(row.row_index, 0);
@@ -1704,44 +1786,88 @@ mod tests {
         assert!(!extractor.is_currency_pattern("$"));
     }
     
+    /// 3-column grid: a 2-column header span, a vertical merge in column 0
+    /// and a genuinely empty cell
+    fn merged_docx_table() -> docx_rs::Table {
+        use docx_rs::{Paragraph, Run, TableCell as Cell, TableRow as Row, VMergeType};
+        let cell = |text: &str| Cell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(text)));
+        docx_rs::Table::new(vec![
+            Row::new(vec![cell("Region").vertical_merge(VMergeType::Restart), cell("Sales").grid_span(2)]),
+            Row::new(vec![Cell::new().vertical_merge(VMergeType::Continue), cell("Q1"), cell("Q2")]),
+            Row::new(vec![cell("North"), cell("10"), Cell::new()]),
+            Row::new(vec![cell("South")]),
+        ])
+        .set_grid(vec![2000, 2000, 2000])
+    }
+
+    fn extract_merged(handling: MergeCellsHandling) -> TableData {
+        let config = TableExtractionConfig::new()
+            .with_headers(false)
+            .with_empty_cells(true)
+            .with_merge_cells_handling(handling);
+        TableExtractor::new(config).extract_table(&merged_docx_table()).unwrap()
+    }
+
     #[test]
-    fn test_merged_cells_detection() {
-        let extractor = TableExtractor::new(
-            TableExtractionConfig::new().with_merge_cells_handling(MergeCellsHandling::Preserve)
+    fn test_merged_cells_from_grid_span_and_vmerge() {
+        let table = extract_merged(MergeCellsHandling::Preserve);
+        // Every row is padded to the table grid
+        assert!(table.rows.iter().all(|row| row.cells.len() == 3));
+
+        let region = table.get_cell(0, 0).unwrap();
+        assert_eq!((region.colspan, region.rowspan), (None, Some(2)));
+        let sales = table.get_cell(0, 1).unwrap();
+        assert_eq!((sales.colspan, sales.rowspan), (Some(2), None));
+        assert!(table.get_cell(0, 2).unwrap().is_merged());
+        assert!(table.get_cell(1, 0).unwrap().is_merged());
+        assert_eq!(table.get_cell(1, 1).unwrap().content, "Q1");
+
+        // An empty cell without merge properties is not merged
+        let empty = table.get_cell(2, 2).unwrap();
+        assert!(empty.is_empty() && !empty.is_merged());
+        assert!(!table.get_cell(3, 1).unwrap().is_merged());
+    }
+
+    #[test]
+    fn test_grid_before_shifts_row() {
+        use docx_rs::{Paragraph, Run, TableCell as Cell, TableRow as Row};
+        let cell = |text: &str| Cell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(text)));
+        let docx_table = docx_rs::Table::new(vec![
+            Row::new(vec![cell("Name"), cell("Total"), cell("Note")]),
+            Row::new(vec![cell("42")]).grid_before(1).row_height(300.0),
+        ])
+        .set_grid(vec![2000, 2000, 2000]);
+        let config = TableExtractionConfig::new().with_headers(false).with_empty_cells(true);
+        let table = TableExtractor::new(config).extract_table(&docx_table).unwrap();
+
+        assert!(table.get_cell(1, 0).unwrap().is_empty());
+        assert_eq!(table.get_cell(1, 1).unwrap().content, "42");
+    }
+
+    #[test]
+    fn test_expand_copies_merged_content() {
+        let table = extract_merged(MergeCellsHandling::Expand);
+        let contents: Vec<Vec<&str>> = table
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(|cell| cell.content.as_str()).collect())
+            .collect();
+        assert_eq!(
+            contents,
+            vec![
+                vec!["Region", "Sales", "Sales"],
+                vec!["Region", "Q1", "Q2"],
+                vec!["North", "10", ""],
+                vec!["South", "", ""],
+            ]
         );
-        
-        // Create test table with merged cells pattern
-        let mut table_data = TableData::new();
-        
-        // Row 1: "Header 1" | "Header 2" | "Header 3"
-        let mut row1 = TableRow::new(0);
-        row1.add_cell(TableCell::new("Header 1".to_string()));
-        row1.add_cell(TableCell::new("Header 2".to_string()));
-        row1.add_cell(TableCell::new("Header 3".to_string()));
-        table_data.add_row(row1);
-        
-        // Row 2: "Data 1" | "" | "Data 3" (middle cell empty - simulates horizontal merge)
-        let mut row2 = TableRow::new(1);
-        row2.add_cell(TableCell::new("Data 1".to_string()));
-        row2.add_cell(TableCell::empty());
-        row2.add_cell(TableCell::new("Data 3".to_string()));
-        table_data.add_row(row2);
-        
-        // Row 3: "Data 4" | "Data 5" | "Data 6"
-        let mut row3 = TableRow::new(2);
-        row3.add_cell(TableCell::new("Data 4".to_string()));
-        row3.add_cell(TableCell::new("Data 5".to_string()));
-        row3.add_cell(TableCell::new("Data 6".to_string()));
-        table_data.add_row(row3);
-        
-        // Test merged cells handling
-        extractor.handle_merged_cells(&mut table_data).unwrap();
-        
-        // The middle cell in row 2 should be marked as merged
-        let middle_cell = &table_data.rows[1].cells[1];
-        assert!(middle_cell.is_merged());
+        assert!(table.rows.iter().flat_map(|row| &row.cells).all(|cell| cell.colspan.is_none() && !cell.is_merged()));
+
+        let ignored = extract_merged(MergeCellsHandling::Ignore);
+        assert_eq!(ignored.get_cell(0, 2).unwrap().content, "");
+        assert!(ignored.get_cell(0, 1).unwrap().colspan.is_none());
     }
-    
+
     #[test]
     fn test_formatting_extraction() {
         let extractor = TableExtractor::new(
@@ -1761,29 +1887,27 @@ mod tests {
         assert!(merged2.bold);
         assert!(merged2.italic);
     }
-    
+
     #[test]
-    fn test_highlight_color_conversion() {
-        let extractor = TableExtractor::new(TableExtractionConfig::simple());
-        
-        // Test standard colors
-        assert_eq!(extractor.convert_highlight_to_hex("yellow"), "#FFFF00");
-        assert_eq!(extractor.convert_highlight_to_hex("red"), "#FF0000");
-        assert_eq!(extractor.convert_highlight_to_hex("blue"), "#0000FF");
-        assert_eq!(extractor.convert_highlight_to_hex("green"), "#00FF00");
-        
-        // Test dark colors  
-        assert_eq!(extractor.convert_highlight_to_hex("darkRed"), "#800000");
-        assert_eq!(extractor.convert_highlight_to_hex("darkBlue"), "#000080");
-        
-        // Test case insensitive
-        assert_eq!(extractor.convert_highlight_to_hex("YELLOW"), "#FFFF00");
-        assert_eq!(extractor.convert_highlight_to_hex("Red"), "#FF0000");
-        
-        // Test hex passthrough
-        assert_eq!(extractor.convert_highlight_to_hex("FF5500"), "#FF5500");
+    fn test_run_formatting_with_styles() {
+        use docx_rs::{Paragraph, Run, TableCell as DocxCell};
+
+        let styles = StyleSheet::parse(r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:style w:type="paragraph" w:styleId="TableHeading"><w:rPr><w:b/><w:sz w:val="20"/></w:rPr></w:style></w:styles>"#).unwrap();
+        let extractor = TableExtractor::new(TableExtractionConfig::full()).with_styles(styles);
+
+        let cell = DocxCell::new().add_paragraph(
+            Paragraph::new()
+                .style("TableHeading")
+                .add_run(Run::new().add_text("Total").color("00FF00").highlight("yellow")),
+        );
+        let formatting = extractor.extract_cell_formatting(&cell).unwrap();
+        assert!(formatting.bold);
+        assert_eq!(formatting.font_size, Some(10.0));
+        assert_eq!(formatting.text_color.as_deref(), Some("#00FF00"));
+        assert_eq!(formatting.background_color.as_deref(), Some("#FFFF00"));
     }
     
+    
     #[test]
     fn test_date_pattern_detection() {
         let extractor = TableExtractor::new(TableExtractionConfig::simple());
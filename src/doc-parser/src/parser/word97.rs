@@ -0,0 +1,541 @@
+//! Word 97–2003 二进制文档（MS-DOC）。
+//!
+//! `WordDocument` 流以文件信息块（FIB）开头，由它定位其余所有结构。
+//! 文字由表流中的片段表（CLX）重建：每个片段将一段字符位置（CP）映射到
+//! `WordDocument` 流中的文件偏移（FC），以 8 位 CP1252 或 UTF-16 存储。
+//! 段落属性位于 PAPX 格式化磁盘页（FKP）中，按段落标记的 FC 查找。
+
+use super::NoteKind;
+use encoding_rs::WINDOWS_1252;
+
+/// Word 二进制文档的 `wIdent`
+const WORD_IDENT: u16 = 0xA5EC;
+/// Word 6 和 Word 95 使用的最大 `nFib`，其 FIB 布局不同
+const NFIB_WORD95: u16 = 0x0068;
+/// 格式化磁盘页的大小
+const FKP_SIZE: usize = 512;
+
+// FibBase 标志
+const F_ENCRYPTED: u16 = 0x0100;
+const F_WHICH_TBL_STM: u16 = 0x0200;
+
+// FibRgFcLcb97 中的下标
+const FC_STSHF: usize = 1;
+const FC_PLCFFND_REF: usize = 2;
+const FC_PLCFFND_TXT: usize = 3;
+const FC_PLCF_BTE_PAPX: usize = 13;
+const FC_CLX: usize = 33;
+const FC_PLCFEND_REF: usize = 46;
+const FC_PLCFEND_TXT: usize = 47;
+
+// 段落 sprm
+const SPRM_P_F_IN_TABLE: u16 = 0x2416;
+const SPRM_P_F_TTP: u16 = 0x2417;
+const SPRM_P_OUT_LVL: u16 = 0x2640;
+const SPRM_P_ITAP: u16 = 0x6649;
+const SPRM_P_CHG_TABS: u16 = 0xC615;
+const SPRM_T_DEF_TABLE: u16 = 0xD608;
+
+pub type ParseResult<T> = std::result::Result<T, String>;
+
+/// 本读取器用到的 FIB 字段
+#[derive(Debug, Clone)]
+pub struct Fib {
+    pub n_fib: u16,
+    pub encrypted: bool,
+    /// "0Table" 或 "1Table"
+    pub table_stream: &'static str,
+    /// 主文档及其后各子文档的字符数
+    pub ccp_text: u32,
+    pub ccp_ftn: u32,
+    pub ccp_hdd: u32,
+    pub ccp_mcr: u32,
+    pub ccp_atn: u32,
+    pub ccp_edn: u32,
+    fc_lcb: Vec<(u32, u32)>,
+}
+
+impl Fib {
+    pub fn parse(stream: &[u8]) -> ParseResult<Self> {
+        let ident = u16_at(stream, 0).ok_or("WordDocument stream is too short")?;
+        if ident != WORD_IDENT {
+            return Err(format!("not a Word binary document (wIdent {:#06x})", ident));
+        }
+        let n_fib = u16_at(stream, 2).unwrap_or(0);
+        if n_fib <= NFIB_WORD95 {
+            return Err(format!("Word 6/95 documents (nFib {:#06x}) are not supported", n_fib));
+        }
+        let flags = u16_at(stream, 0x0A).unwrap_or(0);
+
+        // FibBase 之后是三个带长度前缀的数组：fibRgW、fibRgLw 和 fibRgFcLcb
+        let truncated = || "FIB is truncated".to_string();
+        let csw = u16_at(stream, 32).ok_or_else(truncated)? as usize;
+        let rg_lw = 32 + 2 + csw * 2 + 2;
+        let cslw = u16_at(stream, rg_lw - 2).ok_or_else(truncated)? as usize;
+        let lw = |index: usize| if index < cslw { u32_at(stream, rg_lw + index * 4).unwrap_or(0) } else { 0 };
+        let rg_fc_lcb = rg_lw + cslw * 4 + 2;
+        let pairs = u16_at(stream, rg_fc_lcb - 2).ok_or_else(truncated)? as usize;
+        let fc_lcb = (0..pairs)
+            .map_while(|index| {
+                let offset = rg_fc_lcb + index * 8;
+                Some((u32_at(stream, offset)?, u32_at(stream, offset + 4)?))
+            })
+            .collect();
+
+        Ok(Self {
+            n_fib,
+            encrypted: flags & F_ENCRYPTED != 0,
+            table_stream: if flags & F_WHICH_TBL_STM != 0 { "1Table" } else { "0Table" },
+            ccp_text: lw(3),
+            ccp_ftn: lw(4),
+            ccp_hdd: lw(5),
+            ccp_mcr: lw(6),
+            ccp_atn: lw(7),
+            ccp_edn: lw(8),
+            fc_lcb,
+        })
+    }
+
+    /// 结构在表流中的偏移和大小；不存在时为 None
+    fn blob(&self, index: usize) -> Option<(usize, usize)> {
+        self.fc_lcb
+            .get(index)
+            .filter(|(_, lcb)| *lcb > 0)
+            .map(|(fc, lcb)| (*fc as usize, *lcb as usize))
+    }
+}
+
+/// 在 `WordDocument` 流中连续存储的一段文字
+#[derive(Debug, Clone, PartialEq)]
+struct Piece {
+    cp_start: u32,
+    cp_end: u32,
+    /// 第一个字符的字节偏移
+    offset: u32,
+    /// 以 8 位 CP1252 而非 UTF-16 存储
+    compressed: bool,
+}
+
+/// 一个字符位置：其文件偏移和 UTF-16 码元
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    pub cp: u32,
+    pub fc: u32,
+    pub code: u16,
+}
+
+/// 文字提取用到的段落属性
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParagraphProperties {
+    /// 样式表中的样式下标
+    pub istd: u16,
+    pub in_table: bool,
+    /// 表格行结束标记（TTP）
+    pub row_end: bool,
+    /// 表格嵌套深度
+    pub table_depth: u32,
+    /// 从 0 开始的大纲级别；9 为正文
+    pub outline_level: Option<u8>,
+}
+
+/// 脚注或尾注：其引用标记的 CP 和正文范围
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteRange {
+    pub reference: u32,
+    pub text: std::ops::Range<u32>,
+}
+
+pub struct WordDocument {
+    stream: Vec<u8>,
+    table: Vec<u8>,
+    pub fib: Fib,
+    pieces: Vec<Piece>,
+    /// PlcBtePapx：FC 范围及保存其属性的 FKP 页
+    papx_pages: Vec<(u32, u32, u32)>,
+    /// 样式下标 -> 内置样式标识符（sti）
+    style_ids: Vec<u16>,
+    cp1252: Vec<u16>,
+}
+
+impl WordDocument {
+    /// `fib` 为从 `stream` 解析的 FIB；`table` 为其指定的表流
+    pub fn new(stream: Vec<u8>, table: Vec<u8>, fib: Fib) -> ParseResult<Self> {
+        if fib.encrypted {
+            return Err("encrypted documents are not supported".to_string());
+        }
+        let (clx_offset, clx_size) = fib.blob(FC_CLX).ok_or("document has no piece table")?;
+        let clx = table
+            .get(clx_offset..clx_offset + clx_size)
+            .ok_or("piece table lies outside the table stream")?;
+        let pieces = parse_clx(clx)?;
+
+        let papx_pages = fib
+            .blob(FC_PLCF_BTE_PAPX)
+            .and_then(|(offset, size)| table.get(offset..offset + size))
+            .map(|plc| {
+                let (fcs, pns) = plc_entries(plc, 4);
+                pns.iter()
+                    .enumerate()
+                    .filter_map(|(i, pn)| Some((fcs[i], fcs[i + 1], u32_at(pn, 0)? & 0x003F_FFFF)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let style_ids = fib
+            .blob(FC_STSHF)
+            .and_then(|(offset, size)| table.get(offset..offset + size))
+            .map(parse_style_ids)
+            .unwrap_or_default();
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        let cp1252 = WINDOWS_1252
+            .decode_without_bom_handling(&bytes)
+            .0
+            .chars()
+            .map(|c| c as u32 as u16)
+            .collect();
+
+        Ok(Self {
+            stream,
+            table,
+            fib,
+            pieces,
+            papx_pages,
+            style_ids,
+            cp1252,
+        })
+    }
+
+    /// `[start, end)` 中的字符，按 CP 顺序
+    pub fn units(&self, start: u32, end: u32) -> Vec<Unit> {
+        let mut units = Vec::new();
+        for piece in &self.pieces {
+            let from = start.max(piece.cp_start);
+            let to = end.min(piece.cp_end);
+            for cp in from..to {
+                let index = (cp - piece.cp_start) as usize;
+                let unit = if piece.compressed {
+                    let fc = piece.offset as usize + index;
+                    self.stream.get(fc).map(|byte| (fc, self.cp1252[*byte as usize]))
+                } else {
+                    let fc = piece.offset as usize + index * 2;
+                    u16_at(&self.stream, fc).map(|code| (fc, code))
+                };
+                match unit {
+                    Some((fc, code)) => units.push(Unit { cp, fc: fc as u32, code }),
+                    None => return units,
+                }
+            }
+        }
+        units
+    }
+
+    /// 段落标记位于 `fc` 的段落的属性
+    pub fn paragraph_properties(&self, fc: u32) -> ParagraphProperties {
+        let Some(&(_, _, pn)) = self.papx_pages.iter().find(|(start, end, _)| (*start..*end).contains(&fc)) else {
+            return ParagraphProperties::default();
+        };
+        let page_start = pn as usize * FKP_SIZE;
+        let Some(page) = self.stream.get(page_start..page_start + FKP_SIZE) else {
+            return ParagraphProperties::default();
+        };
+        papx_in_fkp(page, fc).map(parse_papx).unwrap_or_default()
+    }
+
+    /// 由段落的内置标题样式或大纲级别得到的标题级别
+    pub fn heading_level(&self, properties: &ParagraphProperties) -> Option<u8> {
+        let sti = self.style_ids.get(properties.istd as usize).copied();
+        // sti 1-9 为内置的 "heading 1" 到 "heading 9" 样式
+        let level = match (sti, properties.outline_level) {
+            (Some(sti @ 1..=9), _) => sti as u8,
+            (_, Some(level @ 0..=8)) => level + 1,
+            _ => return None,
+        };
+        Some(level.min(6))
+    }
+
+    /// 脚注或尾注及其在主文档中引用的 CP；
+    /// FIB 中的字符数相加溢出时为空
+    pub fn notes(&self, kind: NoteKind) -> Vec<NoteRange> {
+        let fib = &self.fib;
+        let (references, texts, subdocument) = match kind {
+            NoteKind::Footnote => (FC_PLCFFND_REF, FC_PLCFFND_TXT, Some(fib.ccp_text)),
+            NoteKind::Endnote => (
+                FC_PLCFEND_REF,
+                FC_PLCFEND_TXT,
+                [fib.ccp_ftn, fib.ccp_hdd, fib.ccp_mcr, fib.ccp_atn]
+                    .into_iter()
+                    .try_fold(fib.ccp_text, u32::checked_add),
+            ),
+            NoteKind::Comment => return Vec::new(),
+        };
+        let Some(subdocument) = subdocument else {
+            return Vec::new();
+        };
+        let plc = |index| fib.blob(index).and_then(|(offset, size)| self.table.get(offset..offset.checked_add(size)?));
+        let (Some(references), Some(texts)) = (plc(references), plc(texts)) else {
+            return Vec::new();
+        };
+        let (reference_cps, _) = plc_entries(references, 2);
+        let text_cps: Vec<u32> = texts.chunks_exact(4).filter_map(|cp| u32_at(cp, 0)).collect();
+
+        reference_cps
+            .iter()
+            .take(reference_cps.len().saturating_sub(1))
+            .zip(text_cps.windows(2))
+            .map(|(reference, range)| {
+                Some(NoteRange {
+                    reference: *reference,
+                    text: subdocument.checked_add(range[0])?..subdocument.checked_add(range[1])?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+}
+
+/// 将 CLX 拆分为片段，跳过片段表之前的属性修饰（Prc）
+fn parse_clx(clx: &[u8]) -> ParseResult<Vec<Piece>> {
+    let mut offset = 0;
+    while clx.get(offset) == Some(&0x01) {
+        let size = u16_at(clx, offset + 1).ok_or("truncated CLX")? as usize;
+        offset += 3 + size;
+    }
+    if clx.get(offset) != Some(&0x02) {
+        return Err("CLX has no piece table".to_string());
+    }
+    let size = u32_at(clx, offset + 1).ok_or("truncated CLX")? as usize;
+    let plc = clx.get(offset + 5..offset + 5 + size).ok_or("truncated piece table")?;
+
+    let (cps, descriptors) = plc_entries(plc, 8);
+    Ok(descriptors
+        .iter()
+        .enumerate()
+        .filter_map(|(i, pcd)| {
+            let fc = u32_at(pcd, 2)?;
+            // 第 30 位标记压缩文字，其字节偏移按两倍存储
+            let compressed = fc & 0x4000_0000 != 0;
+            let fc = fc & 0x3FFF_FFFF;
+            Some(Piece {
+                cp_start: cps[i],
+                cp_end: cps[i + 1],
+                offset: if compressed { fc / 2 } else { fc },
+                compressed,
+            })
+        })
+        .collect())
+}
+
+/// 将 PLC 拆分为 n+1 个 CP/FC 和 n 个 `element_size` 字节的元素
+fn plc_entries(plc: &[u8], element_size: usize) -> (Vec<u32>, Vec<&[u8]>) {
+    if plc.len() < 4 {
+        return (Vec::new(), Vec::new());
+    }
+    let count = (plc.len() - 4) / (4 + element_size);
+    let positions = (0..=count).filter_map(|i| u32_at(plc, i * 4)).collect();
+    let elements_start = (count + 1) * 4;
+    let elements = (0..count)
+        .filter_map(|i| plc.get(elements_start + i * element_size..elements_start + (i + 1) * element_size))
+        .collect();
+    (positions, elements)
+}
+
+/// PAPX FKP 中包含 `fc` 的段落区间的 GrpPrlAndIstd
+fn papx_in_fkp(page: &[u8], fc: u32) -> Option<&[u8]> {
+    let runs = page[FKP_SIZE - 1] as usize;
+    let index = (0..runs).find(|&i| {
+        let start = u32_at(page, i * 4).unwrap_or(u32::MAX);
+        let end = u32_at(page, (i + 1) * 4).unwrap_or(0);
+        (start..end).contains(&fc)
+    })?;
+    // BxPap：指向 PAPX 的字偏移，后跟 12 字节的布局提示
+    let offset = *page.get((runs + 1) * 4 + index * 13)? as usize * 2;
+    if offset == 0 {
+        return None;
+    }
+    let (start, size) = match *page.get(offset)? {
+        0 => (offset + 2, *page.get(offset + 1)? as usize * 2),
+        cb => (offset + 1, cb as usize * 2 - 1),
+    };
+    page.get(start..(start + size).min(FKP_SIZE - 1))
+}
+
+fn parse_papx(papx: &[u8]) -> ParagraphProperties {
+    let mut properties = ParagraphProperties {
+        istd: u16_at(papx, 0).unwrap_or(0),
+        ..Default::default()
+    };
+    for (sprm, operand) in sprms(papx.get(2..).unwrap_or_default()) {
+        let flag = operand.first().is_some_and(|value| *value != 0);
+        match sprm {
+            SPRM_P_F_IN_TABLE => properties.in_table = flag,
+            SPRM_P_F_TTP => properties.row_end = flag,
+            SPRM_P_ITAP => properties.table_depth = u32_at(operand, 0).unwrap_or(0),
+            SPRM_P_OUT_LVL => properties.outline_level = operand.first().copied(),
+            _ => {}
+        }
+    }
+    if properties.in_table && properties.table_depth == 0 {
+        properties.table_depth = 1;
+    }
+    properties
+}
+
+/// 遍历 grpprl 中的 (sprm, 操作数) 对
+fn sprms(grpprl: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+    while let Some(sprm) = u16_at(grpprl, offset) {
+        offset += 2;
+        // spra 字段（最高三位）给出操作数大小
+        let (start, size) = match sprm >> 13 {
+            0 | 1 => (offset, 1),
+            2 | 4 | 5 => (offset, 2),
+            3 => (offset, 4),
+            7 => (offset, 3),
+            _ if sprm == SPRM_T_DEF_TABLE => match u16_at(grpprl, offset) {
+                Some(cb) => (offset + 2, (cb as usize).saturating_sub(1)),
+                None => break,
+            },
+            _ if sprm == SPRM_P_CHG_TABS => match grpprl.get(offset) {
+                Some(cb) => (offset + 1, *cb as usize),
+                None => break,
+            },
+            _ => match grpprl.get(offset) {
+                Some(cb) => (offset + 1, *cb as usize),
+                None => break,
+            },
+        };
+        let Some(operand) = grpprl.get(start..start + size) else {
+            break;
+        };
+        result.push((sprm, operand));
+        offset = start + size;
+    }
+    result
+}
+
+/// STSH 中各样式的内置样式标识符（sti），按 istd 索引
+fn parse_style_ids(stsh: &[u8]) -> Vec<u16> {
+    let Some(header_size) = u16_at(stsh, 0) else {
+        return Vec::new();
+    };
+    let count = u16_at(stsh, 2).unwrap_or(0) as usize;
+    let mut offset = 2 + header_size as usize;
+    let mut ids = Vec::with_capacity(count);
+    for _ in 0..count {
+        let Some(size) = u16_at(stsh, offset) else {
+            break;
+        };
+        // 空位保留其下标；sti 0x0FFE 表示“非内置样式”
+        let sti = if size == 0 { 0x0FFE } else { u16_at(stsh, offset + 2).map_or(0x0FFE, |value| value & 0x0FFF) };
+        ids.push(sti);
+        offset += 2 + size as usize;
+    }
+    ids
+}
+
+pub(crate) fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+pub(crate) fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clx_pieces() {
+        let mut clx = vec![0x01, 0x02, 0x00, 0xAA, 0xBB, 0x02];
+        let mut plc = Vec::new();
+        for cp in [0u32, 5, 8] {
+            plc.extend(cp.to_le_bytes());
+        }
+        plc.extend([0, 0]);
+        plc.extend((0x4000_0000u32 | 2048).to_le_bytes());
+        plc.extend([0, 0, 0, 0]);
+        plc.extend(4096u32.to_le_bytes());
+        plc.extend([0, 0]);
+        clx.extend((plc.len() as u32).to_le_bytes());
+        clx.extend(plc);
+
+        let pieces = parse_clx(&clx).unwrap();
+        assert_eq!(
+            pieces,
+            vec![
+                Piece { cp_start: 0, cp_end: 5, offset: 1024, compressed: true },
+                Piece { cp_start: 5, cp_end: 8, offset: 4096, compressed: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sprm_operand_sizes() {
+        let grpprl = [
+            0x16, 0x24, 0x01, // sprmPFInTable
+            0x49, 0x66, 0x02, 0x00, 0x00, 0x00, // sprmPItap
+            0x08, 0xD6, 0x03, 0x00, 0xAA, 0xBB, // sprmTDefTable, cb = 3
+            0x17, 0x24, 0x01, // sprmPFTtp
+        ];
+        let parsed: Vec<u16> = sprms(&grpprl).into_iter().map(|(sprm, _)| sprm).collect();
+        assert_eq!(parsed, vec![SPRM_P_F_IN_TABLE, SPRM_P_ITAP, SPRM_T_DEF_TABLE, SPRM_P_F_TTP]);
+
+        let mut papx = vec![0x01, 0x00];
+        papx.extend(grpprl);
+        let properties = parse_papx(&papx);
+        assert_eq!(properties.istd, 1);
+        assert!(properties.in_table && properties.row_end);
+        assert_eq!(properties.table_depth, 2);
+    }
+
+    #[test]
+    fn test_notes_with_overflowing_character_counts() {
+        let mut table = Vec::new();
+        // PlcfendRef：引用 CP 为 3 和 9，各带一个 2 字节元素
+        for cp in [3u32, 9, 10] {
+            table.extend(cp.to_le_bytes());
+        }
+        table.extend([0, 0, 0, 0]);
+        // PlcfendTxt：尾注正文分别从 0 和 4 开始
+        for cp in [0u32, 4, 8] {
+            table.extend(cp.to_le_bytes());
+        }
+        let mut fc_lcb = vec![(0, 0); FC_PLCFEND_TXT + 1];
+        fc_lcb[FC_PLCFEND_REF] = (0, 16);
+        fc_lcb[FC_PLCFEND_TXT] = (16, 12);
+        let mut document = WordDocument {
+            stream: Vec::new(),
+            table,
+            fib: Fib {
+                n_fib: 0x00C1,
+                encrypted: false,
+                table_stream: "1Table",
+                ccp_text: 20,
+                ccp_ftn: 0,
+                ccp_hdd: 0,
+                ccp_mcr: 0,
+                ccp_atn: 0,
+                ccp_edn: 8,
+                fc_lcb,
+            },
+            pieces: Vec::new(),
+            papx_pages: Vec::new(),
+            style_ids: Vec::new(),
+            cp1252: Vec::new(),
+        };
+        assert_eq!(
+            document.notes(NoteKind::Endnote),
+            vec![NoteRange { reference: 3, text: 20..24 }, NoteRange { reference: 9, text: 24..28 }]
+        );
+
+        document.fib.ccp_hdd = u32::MAX - 10;
+        assert!(document.notes(NoteKind::Endnote).is_empty());
+        document.fib.ccp_hdd = 0;
+        document.fib.ccp_text = u32::MAX - 5;
+        assert!(document.notes(NoteKind::Endnote).is_empty());
+    }
+}
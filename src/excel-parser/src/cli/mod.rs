@@ -1,12 +1,16 @@
-use clap::Parser;
-use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Input Excel file (.xlsx, .xlsm, .xlsb, .xls)
-    #[arg(help = "Input Excel file path")]
-    pub input: PathBuf,
+    #[arg(required = true, help = "Input Excel file path")]
+    pub input: Option<PathBuf>,
 
     /// Output file (default: stdout)
     #[arg(short, long, help = "Output file path (default: stdout)")]
@@ -16,18 +20,75 @@ pub struct Args {
     #[arg(short, long, help = "Specific sheet name to process")]
     pub sheet: Option<String>,
 
+    /// Specific Excel table (ListObject) to process
+    #[arg(long, conflicts_with = "sheet", help = "Process only the named Excel table (e.g. tblSales)")]
+    pub table: Option<String>,
+
+    /// Password for encrypted workbooks
+    #[arg(
+        long,
+        env = "EXCEL_PARSER_PASSWORD",
+        hide_env_values = true,
+        help = "Password for encrypted workbooks (or set EXCEL_PARSER_PASSWORD)"
+    )]
+    pub password: Option<String>,
+
     /// Output format
-    #[arg(short = 'f', long, default_value = "csv", help = "Output format: csv, json, table")]
+    #[arg(short = 'f', long, default_value = "csv", help = "Output format: csv, json, table, markdown, html")]
     pub format: String,
 
     /// Custom delimiter for CSV output
     #[arg(short, long, default_value = ",", help = "Custom delimiter for CSV output")]
     pub delimiter: String,
 
+    /// Quote character for CSV output
+    #[arg(long, default_value = "\"", help = "Quote character for CSV output")]
+    pub quote_char: String,
+
+    /// CSV quoting policy
+    #[arg(long = "quote", default_value = "necessary", help = "CSV quoting: always, necessary, never, nonnumeric")]
+    pub quote_style: String,
+
+    /// Escape character for quotes inside CSV fields
+    #[arg(long, help = "Escape quotes with this character instead of doubling them")]
+    pub escape: Option<String>,
+
+    /// Line ending for CSV output
+    #[arg(long, default_value = "lf", help = "CSV line ending: lf, crlf")]
+    pub line_ending: String,
+
+    /// Write a UTF-8 byte order mark
+    #[arg(long, help = "Start CSV output with a UTF-8 byte order mark")]
+    pub bom: bool,
+
+    /// Output encoding for CSV
+    #[arg(long, default_value = "utf-8", help = "CSV output encoding (e.g. utf-8, gbk, windows-1252)")]
+    pub encoding: String,
+
     /// Don't treat first row as header
     #[arg(short = 'n', long, help = "Don't treat first row as header")]
     pub no_header: bool,
 
+    /// Add companion columns for cell comments and hyperlinks
+    #[arg(long, help = "Add <column>__comment and <column>__link columns to CSV output")]
+    pub annotation_columns: bool,
+
+    /// Unpivot (melt) sheets from wide to long format
+    #[arg(long, conflicts_with = "pivot", help = "Unpivot wide sheets, e.g. id_cols=Region,Product[;value_cols=Jan,Feb]")]
+    pub unpivot: Option<String>,
+
+    /// Pivot sheets from long to wide format
+    #[arg(long, help = "Pivot long sheets back to wide, e.g. id_cols=Region,Product")]
+    pub pivot: Option<String>,
+
+    /// Name of the column holding the former column headers
+    #[arg(long, default_value = crate::transform::DEFAULT_VAR_NAME, help = "Variable column name for --unpivot/--pivot")]
+    pub var_name: String,
+
+    /// Name of the column holding the values
+    #[arg(long, default_value = crate::transform::DEFAULT_VALUE_NAME, help = "Value column name for --unpivot/--pivot")]
+    pub value_name: String,
+
     /// Pretty print JSON output
     #[arg(long, help = "Pretty print JSON output")]
     pub pretty: bool,
@@ -40,11 +101,79 @@ pub struct Args {
     #[arg(long, help = "Hide borders in table output")]
     pub no_borders: bool,
 
+    /// Omit the embedded stylesheet from HTML output
+    #[arg(long, help = "Omit the embedded CSS from HTML output")]
+    pub no_css: bool,
+
+    /// Enable verbose output
+    #[arg(short, long, help = "Enable verbose output")]
+    pub verbose: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List embedded images and charts, optionally extracting the images
+    Media(MediaArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct MediaArgs {
+    /// Input Excel file (.xlsx, .xlsm)
+    #[arg(help = "Input Excel file path")]
+    pub input: PathBuf,
+
+    /// Output file for the listing (default: stdout)
+    #[arg(short, long, help = "Output file path (default: stdout)")]
+    pub output: Option<PathBuf>,
+
+    /// Directory to write the images to
+    #[arg(long, help = "Write embedded images to this directory")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Password for encrypted workbooks
+    #[arg(
+        long,
+        env = "EXCEL_PARSER_PASSWORD",
+        hide_env_values = true,
+        help = "Password for encrypted workbooks (or set EXCEL_PARSER_PASSWORD)"
+    )]
+    pub password: Option<String>,
+
+    /// Listing format
+    #[arg(short = 'f', long, default_value = "text", help = "Listing format: text, json")]
+    pub format: String,
+
+    /// Pretty print JSON output
+    #[arg(long, help = "Pretty print JSON output")]
+    pub pretty: bool,
+
     /// Enable verbose output
     #[arg(short, long, help = "Enable verbose output")]
     pub verbose: bool,
 }
 
+impl MediaArgs {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.input.exists() {
+            return Err(format!("Input file does not exist: {}", self.input.display()));
+        }
+
+        let extension = self.input
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        if !matches!(extension.as_str(), "xlsx" | "xlsm") {
+            return Err(format!("Images and charts are only available in .xlsx/.xlsm files, got: {}", extension));
+        }
+
+        match self.format.to_lowercase().as_str() {
+            "text" | "json" => Ok(()),
+            _ => Err(format!("Unsupported media listing format: {}", self.format)),
+        }
+    }
+}
+
 impl Args {
     pub fn parse_args() -> Self {
         Self::parse()
@@ -60,13 +189,17 @@ impl Args {
 
     pub fn get_output_format(&self) -> Result<crate::output::OutputFormat, String> {
         match self.format.to_lowercase().as_str() {
-            "csv" => {
-                if self.no_header {
-                    Ok(crate::output::OutputFormat::csv_no_headers())
-                } else {
-                    Ok(crate::output::OutputFormat::csv_with_delimiter(self.get_delimiter()))
-                }
-            }
+            "csv" => Ok(crate::output::OutputFormat::Csv {
+                delimiter: self.get_delimiter(),
+                quote_char: single_byte(&self.quote_char).unwrap_or(b'"'),
+                has_headers: self.has_headers(),
+                annotation_columns: self.annotation_columns,
+                bom: self.bom,
+                line_ending: self.line_ending.parse()?,
+                quote_style: self.quote_style.parse()?,
+                escape: self.escape.as_deref().and_then(single_byte),
+                encoding: self.encoding.clone(),
+            }),
             "json" => {
                 if self.pretty {
                     Ok(crate::output::OutputFormat::json_pretty())
@@ -83,18 +216,51 @@ impl Args {
                     Ok(crate::output::OutputFormat::table())
                 }
             }
+            "markdown" | "md" => Ok(crate::output::OutputFormat::Markdown {
+                has_headers: self.has_headers(),
+            }),
+            "html" => Ok(crate::output::OutputFormat::Html {
+                has_headers: self.has_headers(),
+                include_css: !self.no_css,
+            }),
             _ => Err(format!("Unsupported output format: {}", self.format)),
         }
     }
 
+    pub fn get_transform(&self) -> Result<Option<crate::transform::Transform>, String> {
+        use crate::transform::Transform;
+
+        let transform = if let Some(spec) = &self.unpivot {
+            Some(Transform::unpivot_from_spec(spec, &self.var_name, &self.value_name))
+        } else {
+            self.pivot
+                .as_ref()
+                .map(|spec| Transform::pivot_from_spec(spec, &self.var_name, &self.value_name))
+        };
+
+        transform.transpose().map_err(|e| e.to_string())
+    }
+
+    /// Input file of the conversion (always present unless a subcommand was given)
+    pub fn input_path(&self) -> Result<&Path, String> {
+        self.input
+            .as_deref()
+            .ok_or_else(|| "Missing input file".to_string())
+    }
+
     pub fn validate(&self) -> Result<(), String> {
+        if let Some(Command::Media(media)) = &self.command {
+            return media.validate();
+        }
+
         // Check if input file exists
-        if !self.input.exists() {
-            return Err(format!("Input file does not exist: {}", self.input.display()));
+        let input = self.input_path()?;
+        if !input.exists() {
+            return Err(format!("Input file does not exist: {}", input.display()));
         }
 
         // Check if input file has valid extension
-        let extension = self.input
+        let extension = input
             .extension()
             .and_then(|ext| ext.to_str())
             .unwrap_or("")
@@ -107,10 +273,17 @@ impl Args {
 
         // Validate format
         match self.format.to_lowercase().as_str() {
-            "csv" | "json" | "table" => {}
+            "csv" | "json" | "table" | "markdown" | "md" | "html" => {}
             _ => return Err(format!("Unsupported output format: {}", self.format)),
         }
 
+        if self.no_css && self.format.to_lowercase() != "html" {
+            return Err("--no-css is only supported for HTML output".to_string());
+        }
+
+        // Validate transformation spec
+        self.get_transform()?;
+
         // Validate delimiter (only for CSV)
         if self.format.to_lowercase() == "csv" {
             if self.delimiter.is_empty() {
@@ -120,12 +293,39 @@ impl Args {
             if self.delimiter.len() > 1 {
                 return Err("Delimiter must be a single character".to_string());
             }
+
+            if single_byte(&self.quote_char).is_none() {
+                return Err("Quote character must be a single ASCII character".to_string());
+            }
+
+            if let Some(escape) = &self.escape {
+                if single_byte(escape).is_none() {
+                    return Err("Escape character must be a single ASCII character".to_string());
+                }
+            }
+
+            self.line_ending.parse::<crate::output::LineEnding>()?;
+            self.quote_style.parse::<crate::output::QuoteStyle>()?;
+
+            let encoding = crate::output::csv::lookup_encoding(&self.encoding)
+                .map_err(|e| e.to_string())?;
+            if self.bom && encoding != encoding_rs::UTF_8 {
+                return Err("--bom is only supported for UTF-8 output".to_string());
+            }
         }
 
         Ok(())
     }
 }
 
+/// Parse a single ASCII character option (delimiter, quote, escape) into a byte
+fn single_byte(value: &str) -> Option<u8> {
+    match value.as_bytes() {
+        [byte] if byte.is_ascii() => Some(*byte),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,15 +333,30 @@ mod tests {
     #[test]
     fn test_delimiter_parsing() {
         let args = Args {
-            input: PathBuf::from("test.xlsx"),
+            command: None,
+            input: Some(PathBuf::from("test.xlsx")),
             output: None,
             sheet: None,
+            table: None,
+            password: None,
             format: "csv".to_string(),
             delimiter: ",".to_string(),
             no_header: false,
+            quote_char: "\"".to_string(),
+            quote_style: "necessary".to_string(),
+            escape: None,
+            line_ending: "lf".to_string(),
+            bom: false,
+            encoding: "utf-8".to_string(),
+            annotation_columns: false,
+            unpivot: None,
+            pivot: None,
+            var_name: "variable".to_string(),
+            value_name: "value".to_string(),
             pretty: false,
             max_width: None,
             no_borders: false,
+            no_css: false,
             verbose: false,
         };
         
@@ -152,15 +367,30 @@ mod tests {
     #[test]
     fn test_tab_delimiter() {
         let args = Args {
-            input: PathBuf::from("test.xlsx"),
+            command: None,
+            input: Some(PathBuf::from("test.xlsx")),
             output: None,
             sheet: None,
+            table: None,
+            password: None,
             format: "csv".to_string(),
             delimiter: "\t".to_string(),
             no_header: false,
+            quote_char: "\"".to_string(),
+            quote_style: "necessary".to_string(),
+            escape: None,
+            line_ending: "lf".to_string(),
+            bom: false,
+            encoding: "utf-8".to_string(),
+            annotation_columns: false,
+            unpivot: None,
+            pivot: None,
+            var_name: "variable".to_string(),
+            value_name: "value".to_string(),
             pretty: false,
             max_width: None,
             no_borders: false,
+            no_css: false,
             verbose: false,
         };
         
@@ -170,18 +400,145 @@ mod tests {
     #[test]
     fn test_no_header_flag() {
         let args = Args {
-            input: PathBuf::from("test.xlsx"),
+            command: None,
+            input: Some(PathBuf::from("test.xlsx")),
             output: None,
             sheet: None,
+            table: None,
+            password: None,
             format: "csv".to_string(),
             delimiter: ",".to_string(),
             no_header: true,
+            quote_char: "\"".to_string(),
+            quote_style: "necessary".to_string(),
+            escape: None,
+            line_ending: "lf".to_string(),
+            bom: false,
+            encoding: "utf-8".to_string(),
+            annotation_columns: false,
+            unpivot: None,
+            pivot: None,
+            var_name: "variable".to_string(),
+            value_name: "value".to_string(),
             pretty: false,
             max_width: None,
             no_borders: false,
+            no_css: false,
             verbose: false,
         };
         
         assert!(!args.has_headers());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_csv_dialect_options() {
+        let args = Args::parse_from([
+            "excel-parser",
+            "test.xlsx",
+            "--bom",
+            "--line-ending",
+            "crlf",
+            "--quote",
+            "always",
+            "--escape",
+            "\\",
+            "--encoding",
+            "gbk",
+        ]);
+
+        match args.get_output_format().unwrap() {
+            crate::output::OutputFormat::Csv {
+                bom,
+                line_ending,
+                quote_style,
+                escape,
+                encoding,
+                ..
+            } => {
+                assert!(bom);
+                assert_eq!(line_ending, crate::output::LineEnding::Crlf);
+                assert_eq!(quote_style, crate::output::QuoteStyle::Always);
+                assert_eq!(escape, Some(b'\\'));
+                assert_eq!(encoding, "gbk");
+            }
+            other => panic!("unexpected format: {:?}", other),
+        }
+
+        let args = Args::parse_from(["excel-parser", "test.xlsx", "--quote", "sometimes"]);
+        assert!(args.get_output_format().is_err());
+    }
+
+    #[test]
+    fn test_transform_options() {
+        let args = Args::parse_from([
+            "excel-parser",
+            "test.xlsx",
+            "--unpivot",
+            "id_cols=Region,Product",
+            "--var-name",
+            "Month",
+            "--value-name",
+            "Amount",
+        ]);
+        match args.get_transform().unwrap() {
+            Some(crate::transform::Transform::Unpivot(unpivot)) => {
+                assert_eq!(unpivot.id_columns, vec!["Region", "Product"]);
+                assert_eq!(unpivot.var_name, "Month");
+                assert_eq!(unpivot.value_name, "Amount");
+            }
+            other => panic!("unexpected transform: {:?}", other),
+        }
+
+        let args = Args::parse_from(["excel-parser", "test.xlsx"]);
+        assert!(args.get_transform().unwrap().is_none());
+
+        assert!(Args::try_parse_from([
+            "excel-parser",
+            "test.xlsx",
+            "--unpivot",
+            "id_cols=A",
+            "--pivot",
+            "id_cols=A",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_markdown_and_html_formats() {
+        let args = Args::parse_from(["excel-parser", "test.xlsx", "-f", "md", "--no-header"]);
+        assert!(matches!(
+            args.get_output_format().unwrap(),
+            crate::output::OutputFormat::Markdown { has_headers: false }
+        ));
+
+        let args = Args::parse_from(["excel-parser", "test.xlsx", "-f", "html", "--no-css"]);
+        assert!(matches!(
+            args.get_output_format().unwrap(),
+            crate::output::OutputFormat::Html {
+                has_headers: true,
+                include_css: false
+            }
+        ));
+    }
+
+    #[test]
+    fn test_media_subcommand() {
+        let args = Args::parse_from(["excel-parser", "media", "report.xlsx", "--output-dir", "images", "-f", "json"]);
+        assert!(args.input.is_none());
+        match args.command {
+            Some(Command::Media(media)) => {
+                assert_eq!(media.input, PathBuf::from("report.xlsx"));
+                assert_eq!(media.output_dir, Some(PathBuf::from("images")));
+                assert_eq!(media.format, "json");
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        // A plain conversion still takes the file as first argument
+        let args = Args::parse_from(["excel-parser", "report.xlsx"]);
+        assert!(args.command.is_none());
+        assert_eq!(args.input_path().unwrap(), Path::new("report.xlsx"));
+
+        assert!(Args::try_parse_from(["excel-parser"]).is_err());
+    }
+}
@@ -16,6 +16,12 @@ pub enum ExcelParserError {
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
     
+    #[error("ZIP extraction error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    
+    #[error("XML parsing error: {0}")]
+    Xml(#[from] quick_xml::Error),
+    
     #[error("File not found: {0}")]
     FileNotFound(String),
     
@@ -25,9 +31,24 @@ pub enum ExcelParserError {
     #[error("Sheet not found: {0}")]
     SheetNotFound(String),
     
+    #[error("Table not found: {0}")]
+    TableNotFound(String),
+    
     #[error("Invalid range: {0}")]
     InvalidRange(String),
     
+    #[error("Workbook is password protected; use --password or EXCEL_PARSER_PASSWORD")]
+    PasswordRequired,
+    
+    #[error("Wrong password for encrypted workbook")]
+    WrongPassword,
+    
+    #[error("Decryption error: {0}")]
+    Decryption(String),
+    
+    #[error("Transformation error: {0}")]
+    Transform(String),
+    
     #[error("Empty file or no data found")]
     EmptyFile,
     
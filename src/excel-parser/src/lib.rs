@@ -2,8 +2,10 @@ pub mod parser;
 pub mod output;
 pub mod cli;
 pub mod error;
+pub mod transform;
 
 pub use error::{ExcelParserError, Result};
-pub use parser::{ExcelParser, ExcelData, Sheet};
+pub use parser::{ExcelParser, ExcelData, ExcelTable, Sheet, TableInfo, WorkbookMedia};
 pub use output::{OutputFormat, OutputProcessor};
-pub use cli::Args;
\ No newline at end of file
+pub use cli::Args;
+pub use transform::Transform;
\ No newline at end of file
@@ -1,6 +1,9 @@
+use excel_parser::cli::{Command, MediaArgs};
+use excel_parser::output::MediaOutput;
 use excel_parser::{Args, ExcelParser, OutputProcessor, Result};
 use std::fs::File;
 use std::io::{self, BufWriter, Write};
+use std::path::Path;
 
 fn main() -> Result<()> {
     let args = Args::parse_args();
@@ -11,20 +14,47 @@ fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    if let Some(Command::Media(media_args)) = &args.command {
+        return run_media(media_args);
+    }
+
+    let input: &Path = args.input_path().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
     if args.verbose {
-        eprintln!("Parsing file: {}", args.input.display());
+        eprintln!("Parsing file: {}", input.display());
     }
 
     // Create parser
-    let parser = ExcelParser::new();
+    let parser = match &args.password {
+        Some(password) => ExcelParser::new().with_password(password),
+        None => ExcelParser::new(),
+    };
 
     // Parse Excel file
-    let data = if let Some(sheet_name) = &args.sheet {
+    let data = if let Some(table_name) = &args.table {
+        // Parse a single Excel table
+        if args.verbose {
+            eprintln!("Parsing table: {}", table_name);
+        }
+        let table = parser.parse_table(input, table_name)?;
+        if args.verbose {
+            eprintln!(
+                "Table {} on sheet {} covers {}",
+                table.info.display_name, table.info.sheet, table.info.range
+            );
+        }
+        excel_parser::parser::ExcelData {
+            sheets: vec![table.to_sheet()],
+        }
+    } else if let Some(sheet_name) = &args.sheet {
         // Parse specific sheet
         if args.verbose {
             eprintln!("Parsing sheet: {}", sheet_name);
         }
-        let sheet = parser.parse_sheet(&args.input, sheet_name)?;
+        let sheet = parser.parse_sheet(input, sheet_name)?;
         excel_parser::parser::ExcelData {
             sheets: vec![sheet],
         }
@@ -33,7 +63,26 @@ fn main() -> Result<()> {
         if args.verbose {
             eprintln!("Parsing all sheets");
         }
-        parser.parse(&args.input)?
+        parser.parse(input)?
+    };
+
+    // Reshape sheets before writing
+    let data = match args.get_transform() {
+        Ok(Some(transform)) => {
+            if args.verbose {
+                eprintln!("Applying transformation: {:?}", transform);
+            }
+            let (data, skipped) = transform.apply_all(&data)?;
+            for name in skipped {
+                eprintln!("Warning: sheet {} lacks the transformation's columns; left unchanged", name);
+            }
+            data
+        }
+        Ok(None) => data,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
     };
 
     if args.verbose {
@@ -84,6 +133,42 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// `excel-parser media`: list embedded images and charts, extracting images on request
+fn run_media(args: &MediaArgs) -> Result<()> {
+    let parser = match &args.password {
+        Some(password) => ExcelParser::new().with_password(password),
+        None => ExcelParser::new(),
+    };
+
+    let media = match &args.output_dir {
+        Some(output_dir) => {
+            let (media, written) = parser.extract_media(&args.input, output_dir)?;
+            if args.verbose {
+                eprintln!("Wrote {} image(s) to {}", written.len(), output_dir.display());
+            }
+            media
+        }
+        None => parser.list_media(&args.input)?,
+    };
+
+    let output = MediaOutput::new(args.format.eq_ignore_ascii_case("json"), args.pretty);
+    match &args.output {
+        Some(output_path) => {
+            let mut writer = BufWriter::new(File::create(output_path)?);
+            output.write_media(&media, &mut writer)?;
+            writer.flush()?;
+        }
+        None => {
+            let stdout = io::stdout();
+            let mut writer = stdout.lock();
+            output.write_media(&media, &mut writer)?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     // Integration tests can be added here
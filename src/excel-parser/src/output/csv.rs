@@ -1,13 +1,109 @@
 use std::io::Write;
-use csv::WriterBuilder;
-use crate::error::Result;
+use std::str::FromStr;
+use csv::{Terminator, WriterBuilder};
+use encoding_rs::{Encoding, UTF_8};
+use crate::error::{ExcelParserError, Result};
+use crate::parser::package::column_name;
 use crate::parser::{ExcelData, Sheet};
 use super::OutputWriter;
 
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+impl FromStr for LineEnding {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lf" => Ok(LineEnding::Lf),
+            "crlf" => Ok(LineEnding::Crlf),
+            _ => Err(format!("Unsupported line ending: {} (use lf or crlf)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// Quote every field
+    Always,
+    /// Quote only fields containing delimiters, quotes or line breaks
+    #[default]
+    Necessary,
+    /// Never quote fields
+    Never,
+    /// Quote every field that is not a number
+    NonNumeric,
+}
+
+impl FromStr for QuoteStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "always" => Ok(QuoteStyle::Always),
+            "necessary" => Ok(QuoteStyle::Necessary),
+            "never" => Ok(QuoteStyle::Never),
+            "nonnumeric" => Ok(QuoteStyle::NonNumeric),
+            _ => Err(format!(
+                "Unsupported quote style: {} (use always, necessary, never or nonnumeric)",
+                s
+            )),
+        }
+    }
+}
+
+impl From<QuoteStyle> for csv::QuoteStyle {
+    fn from(style: QuoteStyle) -> Self {
+        match style {
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+        }
+    }
+}
+
+/// Look up an output encoding by its WHATWG label ("utf-8", "gbk", "windows-1252", ...)
+///
+/// UTF-16 and the "replacement" encoding are decode-only in encoding_rs and would
+/// silently produce UTF-8, so they are rejected.
+pub fn lookup_encoding(label: &str) -> Result<&'static Encoding> {
+    let encoding = Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| ExcelParserError::Other(format!("Unsupported encoding: {}", label)))?;
+    if encoding.output_encoding() != encoding {
+        return Err(ExcelParserError::Other(format!(
+            "Encoding {} is not supported for output",
+            encoding.name()
+        )));
+    }
+    Ok(encoding)
+}
+
 pub struct CsvOutput {
     delimiter: u8,
     quote_char: u8,
     has_headers: bool,
+    annotation_columns: bool,
+    bom: bool,
+    line_ending: LineEnding,
+    quote_style: QuoteStyle,
+    escape: Option<u8>,
+    encoding: &'static Encoding,
 }
 
 impl CsvOutput {
@@ -16,15 +112,183 @@ impl CsvOutput {
             delimiter,
             quote_char,
             has_headers,
+            annotation_columns: false,
+            bom: false,
+            line_ending: LineEnding::default(),
+            quote_style: QuoteStyle::default(),
+            escape: None,
+            encoding: UTF_8,
         }
     }
 
+    /// Add `<column>__comment` / `<column>__link` companion columns for annotated columns
+    pub fn with_annotation_columns(mut self, annotation_columns: bool) -> Self {
+        self.annotation_columns = annotation_columns;
+        self
+    }
+
+    /// Start the output with a UTF-8 byte order mark (helps Excel on Windows detect UTF-8)
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    pub fn with_quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Escape quotes with this character instead of doubling them
+    pub fn with_escape(mut self, escape: Option<u8>) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: &'static Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
     fn create_writer<W: Write>(&self, writer: W) -> csv::Writer<W> {
-        WriterBuilder::new()
+        let mut builder = WriterBuilder::new();
+        builder
             .delimiter(self.delimiter)
             .quote(self.quote_char)
-            .has_headers(self.has_headers)
-            .from_writer(writer)
+            .quote_style(self.quote_style.into())
+            .has_headers(self.has_headers);
+
+        if let Some(escape) = self.escape {
+            builder.escape(escape).double_quote(false);
+        }
+        if self.line_ending == LineEnding::Crlf {
+            builder.terminator(Terminator::CRLF);
+        }
+
+        builder.from_writer(writer)
+    }
+
+    fn write_bom<W: Write>(&self, writer: &mut W) -> Result<()> {
+        if self.bom && self.encoding == UTF_8 {
+            writer.write_all(UTF8_BOM)?;
+        }
+        Ok(())
+    }
+
+    /// Write text in the configured output encoding
+    fn write_encoded<W: Write>(&self, text: &str, writer: &mut W) -> Result<()> {
+        if self.encoding == UTF_8 {
+            writer.write_all(text.as_bytes())?;
+        } else {
+            let (bytes, _, had_errors) = self.encoding.encode(text);
+            if had_errors {
+                return Err(ExcelParserError::Other(format!(
+                    "Text contains characters that cannot be represented in {}",
+                    self.encoding.name()
+                )));
+            }
+            writer.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    fn write_sheet_body<W: Write>(&self, sheet: &Sheet, writer: &mut W) -> Result<()> {
+        if sheet.data.is_empty() {
+            return Ok(());
+        }
+
+        // Records are serialized as UTF-8 first, then converted to the output encoding
+        let mut buffer = Vec::new();
+        {
+            let mut csv_writer = self.create_writer(&mut buffer);
+
+            if self.annotation_columns && !sheet.annotations.is_empty() {
+                for row in self.annotated_rows(sheet) {
+                    csv_writer.write_record(&row)?;
+                }
+            } else {
+                for row in &sheet.data {
+                    // Convert all cell values to strings and write as CSV record
+                    let string_row: Vec<&str> = row.iter().map(|s| s.as_str()).collect();
+                    csv_writer.write_record(&string_row)?;
+                }
+            }
+
+            csv_writer.flush()?;
+        }
+
+        self.write_encoded(&String::from_utf8_lossy(&buffer), writer)
+    }
+
+    /// Interleave comment/link columns right after every column that carries them
+    fn annotated_rows(&self, sheet: &Sheet) -> Vec<Vec<String>> {
+        let column_count = sheet.data.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut comment_columns = vec![false; column_count];
+        let mut link_columns = vec![false; column_count];
+        let annotations = sheet.annotation_index();
+
+        for (&(_, column), annotation) in &annotations {
+            if column < column_count {
+                comment_columns[column] |= !annotation.comments.is_empty();
+                link_columns[column] |= annotation.hyperlink.is_some();
+            }
+        }
+
+        let mut rows = Vec::with_capacity(sheet.data.len());
+        for (row_idx, row) in sheet.data.iter().enumerate() {
+            let is_header = self.has_headers && row_idx == 0;
+            let mut record = Vec::new();
+
+            for column in 0..column_count {
+                let value = row.get(column).cloned().unwrap_or_default();
+                let header = if value.is_empty() {
+                    column_name(column as u32)
+                } else {
+                    value.clone()
+                };
+                let annotation = annotations.get(&(row_idx, column)).copied();
+                record.push(value);
+
+                if comment_columns[column] {
+                    record.push(if is_header {
+                        format!("{}__comment", header)
+                    } else {
+                        annotation
+                            .map(|a| {
+                                a.comments
+                                    .iter()
+                                    .map(|c| c.text.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join("\n")
+                            })
+                            .unwrap_or_default()
+                    });
+                }
+
+                if link_columns[column] {
+                    record.push(if is_header {
+                        format!("{}__link", header)
+                    } else {
+                        annotation
+                            .and_then(|a| a.hyperlink.as_ref())
+                            .and_then(|link| {
+                                link.target
+                                    .clone()
+                                    .or_else(|| link.location.as_ref().map(|l| format!("#{}", l)))
+                            })
+                            .unwrap_or_default()
+                    });
+                }
+            }
+
+            rows.push(record);
+        }
+
+        rows
     }
 }
 
@@ -33,18 +297,20 @@ impl OutputWriter for CsvOutput {
         // For multiple sheets, we'll write them separated by empty lines
         // and include sheet names as comments
         let mut first_sheet = true;
+        let newline = self.line_ending.as_str();
+
+        self.write_bom(writer)?;
         
         for sheet in &data.sheets {
             if !first_sheet {
                 // Add separator between sheets
-                writeln!(writer)?;
-                writeln!(writer, "# Sheet: {}", sheet.name)?;
+                self.write_encoded(&format!("{}# Sheet: {}{}", newline, sheet.name, newline), writer)?;
             } else if data.sheets.len() > 1 {
                 // Add sheet name for first sheet if there are multiple sheets
-                writeln!(writer, "# Sheet: {}", sheet.name)?;
+                self.write_encoded(&format!("# Sheet: {}{}", sheet.name, newline), writer)?;
             }
             
-            self.write_sheet(sheet, writer)?;
+            self.write_sheet_body(sheet, writer)?;
             first_sheet = false;
         }
         
@@ -56,16 +322,8 @@ impl OutputWriter for CsvOutput {
             return Ok(());
         }
 
-        let mut csv_writer = self.create_writer(writer);
-        
-        for row in &sheet.data {
-            // Convert all cell values to strings and write as CSV record
-            let string_row: Vec<&str> = row.iter().map(|s| s.as_str()).collect();
-            csv_writer.write_record(&string_row)?;
-        }
-        
-        csv_writer.flush()?;
-        Ok(())
+        self.write_bom(writer)?;
+        self.write_sheet_body(sheet, writer)
     }
 }
 
@@ -91,6 +349,7 @@ mod tests {
         let empty_sheet = Sheet {
             name: "Empty".to_string(),
             data: vec![],
+            ..Default::default()
         };
         
         let mut output = Vec::new();
@@ -104,6 +363,7 @@ mod tests {
         let sheet = Sheet {
             name: "Test".to_string(),
             data: vec![vec!["Name".to_string(), "Age".to_string()]],
+            ..Default::default()
         };
         
         let mut output = Vec::new();
@@ -123,6 +383,7 @@ mod tests {
                 vec!["John".to_string(), "25".to_string()],
                 vec!["Jane".to_string(), "30".to_string()],
             ],
+            ..Default::default()
         };
         
         let mut output = Vec::new();
@@ -133,4 +394,134 @@ mod tests {
         assert!(result.contains("John,25"));
         assert!(result.contains("Jane,30"));
     }
+
+    #[test]
+    fn test_annotation_columns() {
+        use crate::parser::{CellAnnotation, CellComment, Hyperlink};
+
+        let mut sheet = Sheet::new(
+            "Test",
+            vec![
+                vec!["Name".to_string(), "Site".to_string()],
+                vec!["John".to_string(), "Home".to_string()],
+            ],
+        );
+        sheet.annotations = vec![
+            CellAnnotation {
+                reference: "A2".to_string(),
+                row: Some(1),
+                column: Some(0),
+                comments: vec![CellComment {
+                    author: None,
+                    text: "Team lead".to_string(),
+                    created: None,
+                    threaded: false,
+                }],
+                ..Default::default()
+            },
+            CellAnnotation {
+                reference: "B2".to_string(),
+                row: Some(1),
+                column: Some(1),
+                hyperlink: Some(Hyperlink {
+                    target: Some("https://example.com".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ];
+
+        let mut output = Vec::new();
+        CsvOutput::default()
+            .with_annotation_columns(true)
+            .write_sheet(&sheet, &mut output)
+            .unwrap();
+
+        let result = String::from_utf8(output).unwrap();
+        assert!(result.contains("Name,Name__comment,Site,Site__link"));
+        assert!(result.contains("John,Team lead,Home,https://example.com"));
+
+        // Without the option the data is written unchanged
+        let mut output = Vec::new();
+        CsvOutput::default().write_sheet(&sheet, &mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().starts_with("Name,Site\n"));
+    }
+
+    fn dialect_sheet() -> Sheet {
+        Sheet::new(
+            "Test",
+            vec![
+                vec!["Name".to_string(), "Amount".to_string()],
+                vec!["Say \"hi\"".to_string(), "12.5".to_string()],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_bom_and_crlf() {
+        let csv_output = CsvOutput::default()
+            .with_bom(true)
+            .with_line_ending(LineEnding::Crlf);
+
+        let mut output = Vec::new();
+        csv_output.write_sheet(&dialect_sheet(), &mut output).unwrap();
+
+        assert!(output.starts_with(UTF8_BOM));
+        let result = String::from_utf8(output[3..].to_vec()).unwrap();
+        assert_eq!(result, "Name,Amount\r\n\"Say \"\"hi\"\"\",12.5\r\n");
+    }
+
+    #[test]
+    fn test_quote_styles() {
+        let write = |csv_output: CsvOutput| {
+            let mut output = Vec::new();
+            csv_output.write_sheet(&dialect_sheet(), &mut output).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(
+            write(CsvOutput::default().with_quote_style(QuoteStyle::Always)),
+            "\"Name\",\"Amount\"\n\"Say \"\"hi\"\"\",\"12.5\"\n"
+        );
+        assert_eq!(
+            write(CsvOutput::default().with_quote_style(QuoteStyle::NonNumeric)),
+            "\"Name\",\"Amount\"\n\"Say \"\"hi\"\"\",12.5\n"
+        );
+        assert_eq!(
+            write(CsvOutput::new(b',', b'\'', true).with_quote_style(QuoteStyle::Never)),
+            "Name,Amount\nSay \"hi\",12.5\n"
+        );
+        assert_eq!(
+            write(CsvOutput::default().with_escape(Some(b'\\'))),
+            "Name,Amount\n\"Say \\\"hi\\\"\",12.5\n"
+        );
+    }
+
+    #[test]
+    fn test_gbk_encoding() {
+        let sheet = Sheet::new("Test", vec![vec!["地区".to_string(), "金额".to_string()]]);
+        let csv_output = CsvOutput::default().with_encoding(lookup_encoding("gbk").unwrap());
+
+        let mut output = Vec::new();
+        csv_output.write_sheet(&sheet, &mut output).unwrap();
+
+        let (decoded, _, had_errors) = encoding_rs::GBK.decode(&output);
+        assert!(!had_errors);
+        assert_eq!(decoded, "地区,金额\n");
+        assert_ne!(output, "地区,金额\n".as_bytes());
+        assert!(lookup_encoding("klingon").is_err());
+    }
+
+    #[test]
+    fn test_unencodable_text() {
+        let sheet = Sheet::new("Test", vec![vec!["Café".to_string(), "地区".to_string()]]);
+        let csv_output = CsvOutput::default().with_encoding(lookup_encoding("windows-1252").unwrap());
+
+        let mut output = Vec::new();
+        assert!(csv_output.write_sheet(&sheet, &mut output).is_err());
+
+        // Decode-only encodings would fall back to UTF-8 output
+        assert!(lookup_encoding("utf-16le").is_err());
+        assert!(lookup_encoding("UTF-16").is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,260 @@
+use std::io::Write;
+use crate::error::Result;
+use crate::parser::package::column_name;
+use crate::parser::{ExcelData, Sheet};
+use super::{column_alignments, Alignment, OutputWriter};
+
+/// Standalone HTML document with one `<table>` per sheet
+pub struct HtmlOutput {
+    has_headers: bool,
+    include_css: bool,
+}
+
+impl HtmlOutput {
+    pub fn new(has_headers: bool, include_css: bool) -> Self {
+        Self {
+            has_headers,
+            include_css,
+        }
+    }
+
+    fn write_css<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writeln!(writer, r#"<style>
+body {{
+    font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
+    line-height: 1.5;
+    margin: 0 auto;
+    padding: 20px;
+    background-color: #f5f5f5;
+}}
+
+.sheet-nav ul {{
+    list-style: none;
+    padding: 0;
+    display: flex;
+    flex-wrap: wrap;
+    gap: 8px;
+}}
+
+.sheet-nav a {{
+    display: inline-block;
+    padding: 4px 12px;
+    border-radius: 4px;
+    background-color: #3498db;
+    color: white;
+    text-decoration: none;
+}}
+
+.sheet {{
+    background-color: white;
+    padding: 20px;
+    margin: 20px 0;
+    border-radius: 5px;
+    box-shadow: 0 1px 3px rgba(0,0,0,0.1);
+    overflow-x: auto;
+}}
+
+.sheet h2 {{
+    color: #2c3e50;
+    margin-top: 0;
+}}
+
+table {{
+    border-collapse: collapse;
+}}
+
+table th, table td {{
+    border: 1px solid #bdc3c7;
+    padding: 6px 10px;
+    text-align: left;
+    vertical-align: top;
+}}
+
+table th {{
+    background-color: #ecf0f1;
+}}
+
+table tr:nth-child(even) td {{
+    background-color: #f8f9fa;
+}}
+
+.num {{
+    text-align: right;
+}}
+
+.bool {{
+    text-align: center;
+}}
+</style>"#)?;
+        Ok(())
+    }
+
+    fn escape_html(&self, text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#x27;")
+    }
+
+    fn write_head<W: Write>(&self, title: &str, writer: &mut W) -> Result<()> {
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "    <meta charset=\"UTF-8\">")?;
+        writeln!(writer, "    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">")?;
+        writeln!(writer, "    <title>{}</title>", self.escape_html(title))?;
+        if self.include_css {
+            self.write_css(writer)?;
+        }
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        Ok(())
+    }
+
+    fn write_nav<W: Write>(&self, sheets: &[Sheet], writer: &mut W) -> Result<()> {
+        writeln!(writer, "    <nav class=\"sheet-nav\">")?;
+        writeln!(writer, "        <ul>")?;
+        for (index, sheet) in sheets.iter().enumerate() {
+            writeln!(
+                writer,
+                "            <li><a href=\"#{}\">{}</a></li>",
+                sheet_anchor(index),
+                self.escape_html(&sheet.name)
+            )?;
+        }
+        writeln!(writer, "        </ul>")?;
+        writeln!(writer, "    </nav>")?;
+        Ok(())
+    }
+
+    fn write_section<W: Write>(&self, index: usize, sheet: &Sheet, writer: &mut W) -> Result<()> {
+        writeln!(writer, "    <section class=\"sheet\" id=\"{}\">", sheet_anchor(index))?;
+        writeln!(writer, "        <h2>{}</h2>", self.escape_html(&sheet.name))?;
+
+        let column_count = sheet.data.iter().map(|row| row.len()).max().unwrap_or(0);
+        if column_count == 0 {
+            writeln!(writer, "        <p class=\"empty\">(Empty sheet)</p>")?;
+            writeln!(writer, "    </section>")?;
+            return Ok(());
+        }
+
+        let classes: Vec<&str> = column_alignments(&sheet.data, self.has_headers)
+            .into_iter()
+            .map(|alignment| match alignment {
+                Alignment::Left => "",
+                Alignment::Center => " class=\"bool\"",
+                Alignment::Right => " class=\"num\"",
+            })
+            .collect();
+
+        writeln!(writer, "        <table>")?;
+        writeln!(writer, "            <thead>")?;
+        writeln!(writer, "                <tr>")?;
+        let body = if self.has_headers {
+            for column in 0..column_count {
+                let header = sheet.data[0].get(column).map(String::as_str).unwrap_or("");
+                writeln!(writer, "                    <th>{}</th>", self.escape_html(header))?;
+            }
+            &sheet.data[1..]
+        } else {
+            for column in 0..column_count {
+                writeln!(writer, "                    <th>{}</th>", column_name(column as u32))?;
+            }
+            &sheet.data[..]
+        };
+        writeln!(writer, "                </tr>")?;
+        writeln!(writer, "            </thead>")?;
+
+        writeln!(writer, "            <tbody>")?;
+        for row in body {
+            writeln!(writer, "                <tr>")?;
+            for (column, class) in classes.iter().enumerate() {
+                let cell = row.get(column).map(String::as_str).unwrap_or("");
+                writeln!(writer, "                    <td{}>{}</td>", class, self.escape_html(cell))?;
+            }
+            writeln!(writer, "                </tr>")?;
+        }
+        writeln!(writer, "            </tbody>")?;
+        writeln!(writer, "        </table>")?;
+        writeln!(writer, "    </section>")?;
+        Ok(())
+    }
+}
+
+/// Stable anchor for the sheet at `index`; sheet names may contain anything
+fn sheet_anchor(index: usize) -> String {
+    format!("sheet-{}", index + 1)
+}
+
+impl OutputWriter for HtmlOutput {
+    fn write_excel_data<W: Write>(&self, data: &ExcelData, writer: &mut W) -> Result<()> {
+        self.write_head("Workbook", writer)?;
+        if data.sheets.len() > 1 {
+            self.write_nav(&data.sheets, writer)?;
+        }
+        for (index, sheet) in data.sheets.iter().enumerate() {
+            self.write_section(index, sheet, writer)?;
+        }
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+
+    fn write_sheet<W: Write>(&self, sheet: &Sheet, writer: &mut W) -> Result<()> {
+        self.write_head(&sheet.name, writer)?;
+        self.write_section(0, sheet, writer)?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ExcelData {
+        ExcelData {
+            sheets: vec![
+                Sheet::new(
+                    "Sales & Costs",
+                    vec![
+                        vec!["Item".to_string(), "Amount".to_string()],
+                        vec!["<Tea>".to_string(), "12.5".to_string()],
+                    ],
+                ),
+                Sheet::new("Empty", Vec::new()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_html_workbook() {
+        let mut buffer = Vec::new();
+        HtmlOutput::new(true, true).write_excel_data(&sample(), &mut buffer).unwrap();
+        let result = String::from_utf8(buffer).unwrap();
+
+        assert!(result.starts_with("<!DOCTYPE html>"));
+        assert!(result.contains("<style>"));
+        assert!(result.contains("<li><a href=\"#sheet-1\">Sales &amp; Costs</a></li>"));
+        assert!(result.contains("<section class=\"sheet\" id=\"sheet-2\">"));
+        assert!(result.contains("<thead>\n                <tr>\n                    <th>Item</th>"));
+        assert!(result.contains("<td>&lt;Tea&gt;</td>"));
+        assert!(result.contains("<td class=\"num\">12.5</td>"));
+        assert!(result.contains("(Empty sheet)"));
+    }
+
+    #[test]
+    fn test_html_sheet_without_css() {
+        let data = sample();
+        let mut buffer = Vec::new();
+        HtmlOutput::new(false, false).write_sheet(&data.sheets[0], &mut buffer).unwrap();
+        let result = String::from_utf8(buffer).unwrap();
+
+        assert!(!result.contains("<style>"));
+        assert!(!result.contains("<nav"));
+        assert!(result.contains("<th>A</th>"));
+        assert!(result.contains("<td>Item</td>"));
+    }
+}
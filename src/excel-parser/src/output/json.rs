@@ -1,7 +1,7 @@
 use std::io::Write;
 use serde::{Deserialize, Serialize};
 use crate::error::Result;
-use crate::parser::{ExcelData, Sheet};
+use crate::parser::{CellAnnotation, DataValidation, ExcelData, Sheet};
 use super::OutputWriter;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +15,23 @@ pub struct JsonSheet {
     pub rows: usize,
     pub columns: usize,
     pub data: Vec<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<CellAnnotation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validations: Vec<DataValidation>,
+}
+
+impl From<&Sheet> for JsonSheet {
+    fn from(sheet: &Sheet) -> Self {
+        Self {
+            name: sheet.name.clone(),
+            rows: sheet.data.len(),
+            columns: sheet.data.first().map(|row| row.len()).unwrap_or(0),
+            data: sheet.data.clone(),
+            annotations: sheet.annotations.clone(),
+            validations: sheet.validations.clone(),
+        }
+    }
 }
 
 pub struct JsonOutput {
@@ -30,14 +47,7 @@ impl JsonOutput {
 impl OutputWriter for JsonOutput {
     fn write_excel_data<W: Write>(&self, data: &ExcelData, writer: &mut W) -> Result<()> {
         let json_workbook = JsonWorkbook {
-            sheets: data.sheets.iter().map(|sheet| {
-                JsonSheet {
-                    name: sheet.name.clone(),
-                    rows: sheet.data.len(),
-                    columns: sheet.data.first().map(|row| row.len()).unwrap_or(0),
-                    data: sheet.data.clone(),
-                }
-            }).collect(),
+            sheets: data.sheets.iter().map(JsonSheet::from).collect(),
         };
 
         let json_str = if self.pretty {
@@ -52,12 +62,7 @@ impl OutputWriter for JsonOutput {
     }
 
     fn write_sheet<W: Write>(&self, sheet: &Sheet, writer: &mut W) -> Result<()> {
-        let json_sheet = JsonSheet {
-            name: sheet.name.clone(),
-            rows: sheet.data.len(),
-            columns: sheet.data.first().map(|row| row.len()).unwrap_or(0),
-            data: sheet.data.clone(),
-        };
+        let json_sheet = JsonSheet::from(sheet);
 
         let json_str = if self.pretty {
             serde_json::to_string_pretty(&json_sheet)?
@@ -84,6 +89,7 @@ mod tests {
                 vec!["Name".to_string(), "Age".to_string()],
                 vec!["John".to_string(), "25".to_string()],
             ],
+            ..Default::default()
         };
 
         let json_output = JsonOutput::new(false);
@@ -104,6 +110,7 @@ mod tests {
                 vec!["Name".to_string(), "Age".to_string()],
                 vec!["John".to_string(), "25".to_string()],
             ],
+            ..Default::default()
         };
 
         let json_output = JsonOutput::new(true);
@@ -115,4 +122,28 @@ mod tests {
         assert!(result.contains("  "));
         assert!(result.contains("TestSheet"));
     }
+
+    #[test]
+    fn test_json_annotations() {
+        let mut sheet = Sheet::new("Notes", vec![vec!["Total".to_string()]]);
+        sheet.annotations.push(CellAnnotation {
+            reference: "A1".to_string(),
+            row: Some(0),
+            column: Some(0),
+            comments: vec![crate::parser::CellComment {
+                author: Some("Alice".to_string()),
+                text: "Excludes VAT".to_string(),
+                created: None,
+                threaded: false,
+            }],
+            ..Default::default()
+        });
+
+        let mut buffer = Vec::new();
+        JsonOutput::new(false).write_sheet(&sheet, &mut buffer).unwrap();
+        let result = String::from_utf8(buffer).unwrap();
+        assert!(result.contains("\"annotations\""));
+        assert!(result.contains("Excludes VAT"));
+        assert!(!result.contains("\"validations\""));
+    }
 }
\ No newline at end of file
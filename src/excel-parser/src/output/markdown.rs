@@ -0,0 +1,124 @@
+use std::io::Write;
+use crate::error::Result;
+use crate::parser::package::column_name;
+use crate::parser::{ExcelData, Sheet};
+use super::{column_alignments, Alignment, OutputWriter};
+
+/// GitHub-flavored Markdown tables, one per sheet
+pub struct MarkdownOutput {
+    has_headers: bool,
+}
+
+impl MarkdownOutput {
+    pub fn new(has_headers: bool) -> Self {
+        Self { has_headers }
+    }
+
+    fn escape_cell(&self, text: &str) -> String {
+        text.trim()
+            .replace('\\', "\\\\")
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace(['\n', '\r'], "<br>")
+    }
+
+    fn write_row<W: Write>(&self, cells: &[String], column_count: usize, writer: &mut W) -> Result<()> {
+        let mut line = String::from("|");
+        for column in 0..column_count {
+            let cell = cells.get(column).map(|cell| self.escape_cell(cell)).unwrap_or_default();
+            line.push(' ');
+            line.push_str(&cell);
+            line.push_str(" |");
+        }
+        writeln!(writer, "{}", line)?;
+        Ok(())
+    }
+
+    fn write_table<W: Write>(&self, data: &[Vec<String>], writer: &mut W) -> Result<()> {
+        let column_count = data.iter().map(|row| row.len()).max().unwrap_or(0);
+        if column_count == 0 {
+            writeln!(writer, "*(Empty sheet)*")?;
+            return Ok(());
+        }
+
+        // GFM tables always need a header row; fall back to column letters
+        let (header, body) = if self.has_headers {
+            (data[0].clone(), &data[1..])
+        } else {
+            let letters = (0..column_count).map(|column| column_name(column as u32)).collect();
+            (letters, data)
+        };
+
+        self.write_row(&header, column_count, writer)?;
+
+        let mut separator = String::from("|");
+        for alignment in column_alignments(data, self.has_headers) {
+            separator.push_str(match alignment {
+                Alignment::Left => " --- |",
+                Alignment::Center => " :---: |",
+                Alignment::Right => " ---: |",
+            });
+        }
+        writeln!(writer, "{}", separator)?;
+
+        for row in body {
+            self.write_row(row, column_count, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl OutputWriter for MarkdownOutput {
+    fn write_excel_data<W: Write>(&self, data: &ExcelData, writer: &mut W) -> Result<()> {
+        for (i, sheet) in data.sheets.iter().enumerate() {
+            if i > 0 {
+                writeln!(writer)?;
+            }
+            self.write_sheet(sheet, writer)?;
+        }
+        Ok(())
+    }
+
+    fn write_sheet<W: Write>(&self, sheet: &Sheet, writer: &mut W) -> Result<()> {
+        writeln!(writer, "## {}", sheet.name)?;
+        writeln!(writer)?;
+        self.write_table(&sheet.data, writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(output: &MarkdownOutput, sheet: &Sheet) -> String {
+        let mut buffer = Vec::new();
+        output.write_sheet(sheet, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let sheet = Sheet::new(
+            "Sales",
+            vec![
+                vec!["Item".to_string(), "Amount".to_string()],
+                vec!["Tea | Coffee".to_string(), "12.5".to_string()],
+                vec!["Line\nbreak".to_string(), "3".to_string()],
+            ],
+        );
+
+        let result = render(&MarkdownOutput::new(true), &sheet);
+        assert_eq!(
+            result,
+            "## Sales\n\n| Item | Amount |\n| --- | ---: |\n| Tea \\| Coffee | 12.5 |\n| Line<br>break | 3 |\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_without_headers() {
+        let sheet = Sheet::new("Raw", vec![vec!["x".to_string()], vec!["y".to_string(), "TRUE".to_string()]]);
+
+        let result = render(&MarkdownOutput::new(false), &sheet);
+        assert!(result.contains("| A | B |\n| --- | :---: |\n| x |  |\n| y | TRUE |\n"));
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+use crate::error::Result;
+use crate::parser::WorkbookMedia;
+
+/// Listing of the images and charts embedded in a workbook
+pub struct MediaOutput {
+    json: bool,
+    pretty: bool,
+}
+
+impl MediaOutput {
+    pub fn new(json: bool, pretty: bool) -> Self {
+        Self { json, pretty }
+    }
+
+    pub fn write_media<W: Write>(&self, media: &WorkbookMedia, writer: &mut W) -> Result<()> {
+        if self.json {
+            let json_str = if self.pretty {
+                serde_json::to_string_pretty(media)?
+            } else {
+                serde_json::to_string(media)?
+            };
+            writer.write_all(json_str.as_bytes())?;
+            writer.write_all(b"\n")?;
+            return Ok(());
+        }
+
+        writeln!(writer, "Images ({}):", media.images.len())?;
+        for image in &media.images {
+            write!(
+                writer,
+                "  {} [{}, {} bytes] at {}",
+                image.part,
+                image.format,
+                image.size,
+                location(image.sheet.as_deref(), image.anchor.as_deref())
+            )?;
+            if let Some(description) = &image.description {
+                write!(writer, " \"{}\"", description)?;
+            }
+            writeln!(writer)?;
+        }
+
+        writeln!(writer, "Charts ({}):", media.charts.len())?;
+        for chart in &media.charts {
+            writeln!(
+                writer,
+                "  {} [{}] at {}{}",
+                chart.part,
+                chart.chart_type.as_deref().unwrap_or("unknown"),
+                location(chart.sheet.as_deref(), chart.anchor.as_deref()),
+                chart
+                    .title
+                    .as_ref()
+                    .map(|title| format!(" \"{}\"", title))
+                    .unwrap_or_default()
+            )?;
+            for series in &chart.series {
+                write!(writer, "    - {}", series.name.as_deref().unwrap_or("(unnamed)"))?;
+                if let Some(values) = &series.values_ref {
+                    write!(writer, " values={}", values)?;
+                }
+                if let Some(categories) = &series.categories_ref {
+                    write!(writer, " categories={}", categories)?;
+                }
+                writeln!(writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn location(sheet: Option<&str>, anchor: Option<&str>) -> String {
+    match (sheet, anchor) {
+        (Some(sheet), Some(anchor)) => format!("{}!{}", sheet, anchor),
+        (Some(sheet), None) => sheet.to_string(),
+        _ => "(not placed)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ChartInfo, ChartSeries, ImageInfo};
+
+    #[test]
+    fn test_media_text_listing() {
+        let media = WorkbookMedia {
+            images: vec![ImageInfo {
+                part: "xl/media/image1.png".to_string(),
+                file_name: "image1.png".to_string(),
+                format: "png".to_string(),
+                size: 42,
+                sheet: Some("Report".to_string()),
+                anchor: Some("B3".to_string()),
+                name: None,
+                description: Some("Logo".to_string()),
+            }],
+            charts: vec![ChartInfo {
+                part: "xl/charts/chart1.xml".to_string(),
+                chart_type: Some("line".to_string()),
+                series: vec![ChartSeries {
+                    name: Some("Tea".to_string()),
+                    values_ref: Some("Report!$B$2:$B$5".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let mut buffer = Vec::new();
+        MediaOutput::new(false, false).write_media(&media, &mut buffer).unwrap();
+        let result = String::from_utf8(buffer).unwrap();
+
+        assert!(result.contains("xl/media/image1.png [png, 42 bytes] at Report!B3 \"Logo\""));
+        assert!(result.contains("xl/charts/chart1.xml [line] at (not placed)"));
+        assert!(result.contains("- Tea values=Report!$B$2:$B$5"));
+    }
+}
@@ -3,11 +3,17 @@ use crate::parser::{ExcelData, Sheet};
 use std::io::Write;
 
 pub mod csv;
+pub mod html;
 pub mod json;
+pub mod markdown;
+pub mod media;
 pub mod table;
 
-pub use csv::CsvOutput;
+pub use csv::{CsvOutput, LineEnding, QuoteStyle};
+pub use html::HtmlOutput;
 pub use json::JsonOutput;
+pub use markdown::MarkdownOutput;
+pub use media::MediaOutput;
 pub use table::TableOutput;
 
 #[derive(Debug, Clone)]
@@ -16,6 +22,13 @@ pub enum OutputFormat {
         delimiter: u8,
         quote_char: u8,
         has_headers: bool,
+        annotation_columns: bool,
+        bom: bool,
+        line_ending: LineEnding,
+        quote_style: QuoteStyle,
+        escape: Option<u8>,
+        /// WHATWG encoding label, e.g. "utf-8" or "gbk"
+        encoding: String,
     },
     Json {
         pretty: bool,
@@ -24,6 +37,13 @@ pub enum OutputFormat {
         max_width: Option<usize>,
         borders: bool,
     },
+    Markdown {
+        has_headers: bool,
+    },
+    Html {
+        has_headers: bool,
+        include_css: bool,
+    },
 }
 
 impl OutputFormat {
@@ -32,6 +52,12 @@ impl OutputFormat {
             delimiter: b',',
             quote_char: b'"',
             has_headers: true,
+            annotation_columns: false,
+            bom: false,
+            line_ending: LineEnding::Lf,
+            quote_style: QuoteStyle::Necessary,
+            escape: None,
+            encoding: "utf-8".to_string(),
         }
     }
 
@@ -40,6 +66,12 @@ impl OutputFormat {
             delimiter,
             quote_char: b'"',
             has_headers: true,
+            annotation_columns: false,
+            bom: false,
+            line_ending: LineEnding::Lf,
+            quote_style: QuoteStyle::Necessary,
+            escape: None,
+            encoding: "utf-8".to_string(),
         }
     }
 
@@ -48,6 +80,12 @@ impl OutputFormat {
             delimiter: b',',
             quote_char: b'"',
             has_headers: false,
+            annotation_columns: false,
+            bom: false,
+            line_ending: LineEnding::Lf,
+            quote_style: QuoteStyle::Necessary,
+            escape: None,
+            encoding: "utf-8".to_string(),
         }
     }
 
@@ -79,6 +117,58 @@ impl OutputFormat {
             borders: true,
         }
     }
+
+    pub fn markdown() -> Self {
+        Self::Markdown { has_headers: true }
+    }
+
+    pub fn html() -> Self {
+        Self::Html {
+            has_headers: true,
+            include_css: true,
+        }
+    }
+}
+
+/// Horizontal alignment of a column, inferred from its values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// Infer column alignment: numbers right, booleans centered, everything else left
+pub fn column_alignments(data: &[Vec<String>], has_headers: bool) -> Vec<Alignment> {
+    let column_count = data.iter().map(|row| row.len()).max().unwrap_or(0);
+    let body = if has_headers && !data.is_empty() { &data[1..] } else { data };
+
+    (0..column_count)
+        .map(|column| {
+            let mut values = body
+                .iter()
+                .filter_map(|row| row.get(column))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .peekable();
+
+            if values.peek().is_none() {
+                return Alignment::Left;
+            }
+
+            let values: Vec<&str> = values.collect();
+            if values.iter().all(|value| value.parse::<f64>().is_ok()) {
+                Alignment::Right
+            } else if values
+                .iter()
+                .all(|value| value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false"))
+            {
+                Alignment::Center
+            } else {
+                Alignment::Left
+            }
+        })
+        .collect()
 }
 
 pub trait OutputWriter {
@@ -100,12 +190,8 @@ impl OutputProcessor {
         writer: &mut W,
     ) -> Result<()> {
         match format {
-            OutputFormat::Csv {
-                delimiter,
-                quote_char,
-                has_headers,
-            } => {
-                let csv_output = CsvOutput::new(*delimiter, *quote_char, *has_headers);
+            OutputFormat::Csv { .. } => {
+                let csv_output = self.csv_output(format)?;
                 csv_output.write_excel_data(data, writer)
             }
             OutputFormat::Json { pretty } => {
@@ -116,6 +202,17 @@ impl OutputProcessor {
                 let table_output = TableOutput::new(*max_width, *borders);
                 table_output.write_excel_data(data, writer)
             }
+            OutputFormat::Markdown { has_headers } => {
+                let markdown_output = MarkdownOutput::new(*has_headers);
+                markdown_output.write_excel_data(data, writer)
+            }
+            OutputFormat::Html {
+                has_headers,
+                include_css,
+            } => {
+                let html_output = HtmlOutput::new(*has_headers, *include_css);
+                html_output.write_excel_data(data, writer)
+            }
         }
     }
 
@@ -126,12 +223,8 @@ impl OutputProcessor {
         writer: &mut W,
     ) -> Result<()> {
         match format {
-            OutputFormat::Csv {
-                delimiter,
-                quote_char,
-                has_headers,
-            } => {
-                let csv_output = CsvOutput::new(*delimiter, *quote_char, *has_headers);
+            OutputFormat::Csv { .. } => {
+                let csv_output = self.csv_output(format)?;
                 csv_output.write_sheet(sheet, writer)
             }
             OutputFormat::Json { pretty } => {
@@ -142,6 +235,42 @@ impl OutputProcessor {
                 let table_output = TableOutput::new(*max_width, *borders);
                 table_output.write_sheet(sheet, writer)
             }
+            OutputFormat::Markdown { has_headers } => {
+                let markdown_output = MarkdownOutput::new(*has_headers);
+                markdown_output.write_sheet(sheet, writer)
+            }
+            OutputFormat::Html {
+                has_headers,
+                include_css,
+            } => {
+                let html_output = HtmlOutput::new(*has_headers, *include_css);
+                html_output.write_sheet(sheet, writer)
+            }
+        }
+    }
+}
+
+impl OutputProcessor {
+    fn csv_output(&self, format: &OutputFormat) -> Result<CsvOutput> {
+        match format {
+            OutputFormat::Csv {
+                delimiter,
+                quote_char,
+                has_headers,
+                annotation_columns,
+                bom,
+                line_ending,
+                quote_style,
+                escape,
+                encoding,
+            } => Ok(CsvOutput::new(*delimiter, *quote_char, *has_headers)
+                .with_annotation_columns(*annotation_columns)
+                .with_bom(*bom)
+                .with_line_ending(*line_ending)
+                .with_quote_style(*quote_style)
+                .with_escape(*escape)
+                .with_encoding(csv::lookup_encoding(encoding)?)),
+            _ => Ok(CsvOutput::default()),
         }
     }
 }
@@ -161,5 +290,21 @@ mod tests {
         let _csv = OutputFormat::csv();
         let _csv_tab = OutputFormat::csv_with_delimiter(b'\t');
         let _csv_no_headers = OutputFormat::csv_no_headers();
+        let _markdown = OutputFormat::markdown();
+        let _html = OutputFormat::html();
+    }
+
+    #[test]
+    fn test_column_alignments() {
+        let data = vec![
+            vec!["Name".to_string(), "Amount".to_string(), "Active".to_string(), "Note".to_string()],
+            vec!["John".to_string(), "12.5".to_string(), "TRUE".to_string(), String::new()],
+            vec!["Jane".to_string(), "-3".to_string(), "false".to_string(), String::new()],
+        ];
+
+        assert_eq!(
+            column_alignments(&data, true),
+            vec![Alignment::Left, Alignment::Right, Alignment::Center, Alignment::Left]
+        );
     }
 }
@@ -206,6 +206,7 @@ mod tests {
                 vec!["John".to_string(), "25".to_string()],
                 vec!["Jane".to_string(), "30".to_string()],
             ],
+            ..Default::default()
         };
 
         let table_output = TableOutput::new(None, true);
@@ -227,6 +228,7 @@ mod tests {
                 vec!["Name".to_string(), "Age".to_string()],
                 vec!["John".to_string(), "25".to_string()],
             ],
+            ..Default::default()
         };
 
         let table_output = TableOutput::new(None, false);
@@ -244,6 +246,7 @@ mod tests {
         let sheet = Sheet {
             name: "EmptySheet".to_string(),
             data: vec![],
+            ..Default::default()
         };
 
         let table_output = TableOutput::new(None, true);
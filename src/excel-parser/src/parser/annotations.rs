@@ -0,0 +1,436 @@
+use super::package::{attr, parse_range_ref, Relationship, XlsxPackage};
+use crate::error::Result;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Everything attached to a single cell besides its value
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CellAnnotation {
+    /// A1-style reference of the cell in the worksheet
+    pub reference: String,
+    /// Row index into `Sheet::data`, `None` if the row was dropped as empty
+    pub row: Option<usize>,
+    /// Column index into `Sheet::data`, `None` if outside the used range
+    pub column: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<CellComment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyperlink: Option<Hyperlink>,
+    /// Index into `Sheet::validations`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<usize>,
+}
+
+/// A legacy note or one entry of a threaded comment conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CellComment {
+    pub author: Option<String>,
+    pub text: String,
+    /// Creation time, only recorded for threaded comments
+    pub created: Option<String>,
+    pub threaded: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Hyperlink {
+    /// External URL (or file path) resolved from the sheet relationships
+    pub target: Option<String>,
+    /// Location inside the workbook, e.g. "Sheet2!A1"
+    pub location: Option<String>,
+    pub display: Option<String>,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataValidation {
+    /// Ranges the rule applies to, as written in `sqref`
+    pub ranges: Vec<String>,
+    /// Validation type: list, whole, decimal, date, time, textLength or custom
+    pub validation_type: String,
+    pub operator: Option<String>,
+    pub formula1: Option<String>,
+    pub formula2: Option<String>,
+    /// Allowed values of a dropdown list, resolved from literals or cell ranges
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub list_values: Vec<String>,
+    pub allow_blank: bool,
+    pub prompt: Option<String>,
+    pub error_message: Option<String>,
+}
+
+impl DataValidation {
+    /// Check whether the rule covers the zero-based (row, column) cell
+    pub fn applies_to(&self, row: u32, column: u32) -> bool {
+        self.ranges.iter().any(|range| {
+            parse_range_ref(range).is_some_and(|(start, end)| {
+                (start.0..=end.0).contains(&row) && (start.1..=end.1).contains(&column)
+            })
+        })
+    }
+
+    /// Literal list items of a `"a,b,c"` style formula, `None` for references
+    pub fn literal_list(&self) -> Option<Vec<String>> {
+        let formula = self.formula1.as_deref()?.trim();
+        let inner = formula.strip_prefix('"')?.strip_suffix('"')?;
+        Some(
+            inner
+                .replace("\"\"", "\"")
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .collect(),
+        )
+    }
+}
+
+/// Raw annotations of a worksheet, keyed by A1 references
+#[derive(Debug, Default)]
+pub struct SheetExtras {
+    pub comments: Vec<(String, CellComment)>,
+    pub hyperlinks: Vec<(String, Hyperlink)>,
+    pub validations: Vec<DataValidation>,
+}
+
+/// Read comments, hyperlinks and data validations of a worksheet
+pub fn read_sheet_extras(package: &mut XlsxPackage, sheet_name: &str) -> Result<SheetExtras> {
+    let sheet_part = match package.sheet_part(sheet_name) {
+        Some(part) => part.to_string(),
+        None => return Ok(SheetExtras::default()),
+    };
+    let relationships = package.relationships(&sheet_part)?;
+    let mut extras = SheetExtras::default();
+
+    if let Some(content) = package.read_part(&sheet_part)? {
+        parse_sheet_xml(&content, &relationships, &mut extras)?;
+    }
+
+    // Threaded comments supersede the legacy placeholders Excel writes next to them
+    let mut threaded = Vec::new();
+    let threaded_rels: Vec<_> = relationships
+        .iter()
+        .filter(|rel| rel.is_type("threadedComment"))
+        .collect();
+    let persons = if threaded_rels.is_empty() {
+        HashMap::new()
+    } else {
+        read_persons(package)?
+    };
+    for rel in threaded_rels {
+        if let Some(content) = package.read_part(&rel.target)? {
+            threaded.extend(parse_threaded_comments(&content, &persons)?);
+        }
+    }
+
+    for rel in relationships.iter().filter(|rel| rel.is_type("comments")) {
+        if let Some(content) = package.read_part(&rel.target)? {
+            for (reference, comment) in parse_comments(&content)? {
+                if !threaded.iter().any(|(r, _)| *r == reference) {
+                    extras.comments.push((reference, comment));
+                }
+            }
+        }
+    }
+    extras.comments.extend(threaded);
+
+    Ok(extras)
+}
+
+fn parse_sheet_xml(
+    content: &str,
+    relationships: &[Relationship],
+    extras: &mut SheetExtras,
+) -> Result<()> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut current_validation: Option<DataValidation> = None;
+    let mut current_element = String::new();
+    let mut formula_slot = 0;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Empty(ref e) if e.local_name().as_ref() == b"hyperlink" => {
+                let reference = attr(e, "ref").unwrap_or_default();
+                let target = attr(e, "id").and_then(|id| {
+                    relationships
+                        .iter()
+                        .find(|rel| rel.id == id)
+                        .map(|rel| rel.target.clone())
+                });
+                extras.hyperlinks.push((
+                    reference,
+                    Hyperlink {
+                        target,
+                        location: attr(e, "location"),
+                        display: attr(e, "display"),
+                        tooltip: attr(e, "tooltip"),
+                    },
+                ));
+            }
+            Event::Start(ref e) if e.local_name().as_ref() == b"dataValidation" => {
+                current_validation = Some(DataValidation {
+                    ranges: attr(e, "sqref")
+                        .map(|sqref| sqref.split_whitespace().map(String::from).collect())
+                        .unwrap_or_default(),
+                    validation_type: attr(e, "type").unwrap_or_else(|| "none".to_string()),
+                    operator: attr(e, "operator"),
+                    allow_blank: attr(e, "allowBlank").as_deref() == Some("1"),
+                    prompt: attr(e, "prompt"),
+                    error_message: attr(e, "error"),
+                    ..Default::default()
+                });
+            }
+            Event::Start(ref e) => {
+                current_element = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                match current_element.as_str() {
+                    "formula1" => formula_slot = 1,
+                    "formula2" => formula_slot = 2,
+                    _ => {}
+                }
+            }
+            Event::Text(e) => {
+                if let Some(validation) = current_validation.as_mut() {
+                    let text = e.unescape()?.to_string();
+                    match current_element.as_str() {
+                        // Plain rules hold the formula directly, x14 rules wrap it in xm:f
+                        "formula1" | "formula2" | "f" if formula_slot == 1 => {
+                            validation.formula1 = Some(text)
+                        }
+                        "formula1" | "formula2" | "f" if formula_slot == 2 => {
+                            validation.formula2 = Some(text)
+                        }
+                        "sqref" => {
+                            validation.ranges = text.split_whitespace().map(String::from).collect()
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Event::End(ref e) => {
+                if matches!(e.local_name().as_ref(), b"formula1" | b"formula2") {
+                    formula_slot = 0;
+                }
+                if e.local_name().as_ref() == b"dataValidation" {
+                    if let Some(mut validation) = current_validation.take() {
+                        if let Some(values) = validation.literal_list() {
+                            validation.list_values = values;
+                        }
+                        extras.validations.push(validation);
+                    }
+                }
+                current_element.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+fn parse_comments(content: &str) -> Result<Vec<(String, CellComment)>> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut authors = Vec::new();
+    let mut comments = Vec::new();
+
+    let mut in_author = false;
+    let mut in_text = false;
+    let mut current: Option<(String, Option<usize>, String)> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => match e.local_name().as_ref() {
+                b"author" => {
+                    in_author = true;
+                    authors.push(String::new());
+                }
+                b"comment" => {
+                    let author_id = attr(e, "authorId").and_then(|id| id.parse().ok());
+                    current = Some((attr(e, "ref").unwrap_or_default(), author_id, String::new()));
+                }
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Event::Text(e) => {
+                let text = e.unescape()?;
+                if in_author {
+                    if let Some(author) = authors.last_mut() {
+                        author.push_str(&text);
+                    }
+                } else if in_text {
+                    if let Some((_, _, body)) = current.as_mut() {
+                        body.push_str(&text);
+                    }
+                }
+            }
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"author" => in_author = false,
+                b"t" => in_text = false,
+                b"comment" => {
+                    if let Some((reference, author_id, body)) = current.take() {
+                        let author = author_id.and_then(|id| authors.get(id).cloned());
+                        let text = strip_author_prefix(&body, author.as_deref());
+                        comments.push((
+                            reference,
+                            CellComment {
+                                author,
+                                text,
+                                created: None,
+                                threaded: false,
+                            },
+                        ));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(comments)
+}
+
+/// Excel prefixes note bodies with a bold "Author:" run; drop it since the author is kept separately
+fn strip_author_prefix(body: &str, author: Option<&str>) -> String {
+    if let Some(author) = author {
+        if let Some(rest) = body.strip_prefix(author).and_then(|rest| rest.strip_prefix(':')) {
+            return rest.trim().to_string();
+        }
+    }
+    body.trim().to_string()
+}
+
+fn parse_threaded_comments(
+    content: &str,
+    persons: &HashMap<String, String>,
+) -> Result<Vec<(String, CellComment)>> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut comments = Vec::new();
+    let mut current: Option<(String, CellComment)> = None;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => match e.local_name().as_ref() {
+                b"threadedComment" => {
+                    let author = attr(e, "personId").and_then(|id| persons.get(&id).cloned());
+                    current = Some((
+                        attr(e, "ref").unwrap_or_default(),
+                        CellComment {
+                            author,
+                            text: String::new(),
+                            created: attr(e, "dT"),
+                            threaded: true,
+                        },
+                    ));
+                }
+                b"text" => in_text = true,
+                _ => {}
+            },
+            Event::Text(e) if in_text => {
+                if let Some((_, comment)) = current.as_mut() {
+                    comment.text.push_str(&e.unescape()?);
+                }
+            }
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"text" => in_text = false,
+                b"threadedComment" => {
+                    if let Some(comment) = current.take() {
+                        comments.push(comment);
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(comments)
+}
+
+/// Map threaded comment person ids to display names
+fn read_persons(package: &mut XlsxPackage) -> Result<HashMap<String, String>> {
+    let mut persons = HashMap::new();
+    let relationships = package.relationships("xl/workbook.xml")?;
+
+    for rel in relationships.iter().filter(|rel| rel.is_type("person")) {
+        let content = match package.read_part(&rel.target)? {
+            Some(content) => content,
+            None => continue,
+        };
+        let mut reader = Reader::from_str(&content);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(ref e) | Event::Empty(ref e)
+                    if e.local_name().as_ref() == b"person" =>
+                {
+                    if let (Some(id), Some(name)) = (attr(e, "id"), attr(e, "displayName")) {
+                        persons.insert(id, name);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    Ok(persons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_comments() {
+        let xml = r#"<comments><authors><author>Alice</author></authors><commentList>
+            <comment ref="B2" authorId="0"><text><r><t>Alice:</t></r><r><t xml:space="preserve">
+Check this total</t></r></text></comment></commentList></comments>"#;
+
+        let comments = parse_comments(xml).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].0, "B2");
+        assert_eq!(comments[0].1.author.as_deref(), Some("Alice"));
+        assert_eq!(comments[0].1.text, "Check this total");
+    }
+
+    #[test]
+    fn test_parse_hyperlinks_and_validations() {
+        let xml = r#"<worksheet><sheetData/>
+            <dataValidations count="2">
+              <dataValidation type="list" allowBlank="1" sqref="C2:C10 E2"><formula1>"Yes,No"</formula1></dataValidation>
+              <dataValidation type="whole" operator="between" sqref="D2:D10"><formula1>1</formula1><formula2>10</formula2></dataValidation>
+            </dataValidations>
+            <hyperlinks><hyperlink ref="A1" r:id="rId1" display="Site"/><hyperlink ref="A2" location="Other!A1"/></hyperlinks>
+            </worksheet>"#;
+        let relationships = vec![Relationship {
+            id: "rId1".to_string(),
+            rel_type: "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink".to_string(),
+            target: "https://example.com".to_string(),
+            external: true,
+        }];
+
+        let mut extras = SheetExtras::default();
+        parse_sheet_xml(xml, &relationships, &mut extras).unwrap();
+
+        assert_eq!(extras.hyperlinks.len(), 2);
+        assert_eq!(extras.hyperlinks[0].1.target.as_deref(), Some("https://example.com"));
+        assert_eq!(extras.hyperlinks[1].1.location.as_deref(), Some("Other!A1"));
+
+        assert_eq!(extras.validations.len(), 2);
+        assert_eq!(extras.validations[0].list_values, vec!["Yes", "No"]);
+        assert!(extras.validations[0].applies_to(4, 2));
+        assert!(extras.validations[0].applies_to(1, 4));
+        assert!(!extras.validations[0].applies_to(0, 2));
+        assert_eq!(extras.validations[1].formula2.as_deref(), Some("10"));
+    }
+}
@@ -0,0 +1,609 @@
+//! Decryption of password protected OOXML workbooks (ECMA-376 / MS-OFFCRYPTO).
+//!
+//! Encrypted workbooks are stored as an OLE compound file holding two streams:
+//! `EncryptionInfo` (algorithm parameters and password verifier) and
+//! `EncryptedPackage` (the original zip package, encrypted).
+
+use super::package::attr;
+use crate::error::{ExcelParserError, Result};
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, KeyInit};
+use aes::{Aes128, Aes192, Aes256};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const OLE_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+const AGILE_SEGMENT_LENGTH: usize = 4096;
+const BLOCK_KEY_VERIFIER_INPUT: [u8; 8] = [0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79];
+const BLOCK_KEY_VERIFIER_VALUE: [u8; 8] = [0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e];
+const BLOCK_KEY_SECRET_KEY: [u8; 8] = [0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6];
+
+const STANDARD_SPIN_COUNT: u32 = 50_000;
+
+/// Check whether a file is an OLE container wrapping an encrypted OOXML package
+pub fn is_encrypted<P: AsRef<Path>>(file_path: P) -> Result<bool> {
+    let mut signature = [0u8; 8];
+    let mut file = File::open(file_path.as_ref())?;
+    if file.read_exact(&mut signature).is_err() || signature != OLE_SIGNATURE {
+        return Ok(false);
+    }
+
+    match cfb::open(file_path.as_ref()) {
+        Ok(compound) => {
+            Ok(compound.exists("/EncryptionInfo") && compound.exists("/EncryptedPackage"))
+        }
+        Err(_) => Ok(false),
+    }
+}
+
+/// Decrypt an encrypted workbook and return the bytes of the inner zip package
+pub fn decrypt_file<P: AsRef<Path>>(file_path: P, password: Option<&str>) -> Result<Vec<u8>> {
+    let password = password.ok_or(ExcelParserError::PasswordRequired)?;
+
+    let mut compound = cfb::open(file_path.as_ref())?;
+    let mut info = Vec::new();
+    compound.open_stream("/EncryptionInfo")?.read_to_end(&mut info)?;
+    let mut package = Vec::new();
+    compound
+        .open_stream("/EncryptedPackage")?
+        .read_to_end(&mut package)?;
+
+    decrypt(&info, &package, password)
+}
+
+/// Decrypt `EncryptedPackage` using the parameters from `EncryptionInfo`
+pub fn decrypt(info: &[u8], package: &[u8], password: &str) -> Result<Vec<u8>> {
+    if info.len() < 8 || package.len() < 8 {
+        return Err(decryption_error("truncated encryption streams"));
+    }
+
+    let major = u16::from_le_bytes([info[0], info[1]]);
+    let minor = u16::from_le_bytes([info[2], info[3]]);
+    let password: Vec<u8> = password
+        .encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect();
+
+    let secret_key_and_mode = match (major, minor) {
+        (4, 4) => {
+            let params = AgileParams::parse(&info[8..])?;
+            let key = params.secret_key(&password)?;
+            Mode::Agile(params, key)
+        }
+        (2..=4, 2) => {
+            let params = StandardParams::parse(&info[8..])?;
+            let key = params.secret_key(&password)?;
+            Mode::Standard(key)
+        }
+        _ => {
+            return Err(decryption_error(&format!(
+                "unsupported encryption version {}.{}",
+                major, minor
+            )))
+        }
+    };
+
+    let size = u64::from_le_bytes(package[..8].try_into().unwrap_or_default()) as usize;
+    let encrypted = &package[8..];
+
+    let mut decrypted = match secret_key_and_mode {
+        Mode::Agile(params, key) => {
+            let cipher = AesKey::new(&key)?;
+            let mut output = Vec::with_capacity(encrypted.len());
+            for (index, segment) in encrypted.chunks(AGILE_SEGMENT_LENGTH).enumerate() {
+                let iv = fit(
+                    &params.hash(&[&params.key_salt, &(index as u32).to_le_bytes()]),
+                    params.block_size,
+                    0x36,
+                );
+                let mut block = segment.to_vec();
+                block.resize(segment.len().div_ceil(16) * 16, 0);
+                cipher.decrypt_cbc(&iv, &mut block);
+                output.extend_from_slice(&block);
+            }
+            output
+        }
+        Mode::Standard(key) => {
+            let cipher = AesKey::new(&key)?;
+            let mut block = encrypted.to_vec();
+            block.truncate(encrypted.len() / 16 * 16);
+            cipher.decrypt_ecb(&mut block);
+            block
+        }
+    };
+
+    if size > decrypted.len() {
+        return Err(decryption_error("decrypted package is shorter than declared"));
+    }
+    decrypted.truncate(size);
+    Ok(decrypted)
+}
+
+enum Mode {
+    Agile(AgileParams, Vec<u8>),
+    Standard(Vec<u8>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    fn from_name(name: &str) -> Result<Self> {
+        match name.to_ascii_uppercase().replace('-', "").as_str() {
+            "SHA1" => Ok(Self::Sha1),
+            "SHA256" => Ok(Self::Sha256),
+            "SHA384" => Ok(Self::Sha384),
+            "SHA512" => Ok(Self::Sha512),
+            other => Err(decryption_error(&format!("unsupported hash algorithm {}", other))),
+        }
+    }
+
+    fn digest(self, parts: &[&[u8]]) -> Vec<u8> {
+        fn run<D: Digest>(parts: &[&[u8]]) -> Vec<u8> {
+            let mut hasher = D::new();
+            for part in parts {
+                hasher.update(part);
+            }
+            hasher.finalize().to_vec()
+        }
+
+        match self {
+            Self::Sha1 => run::<Sha1>(parts),
+            Self::Sha256 => run::<Sha256>(parts),
+            Self::Sha384 => run::<Sha384>(parts),
+            Self::Sha512 => run::<Sha512>(parts),
+        }
+    }
+}
+
+/// Parameters of Agile encryption, read from the XML descriptor
+#[derive(Debug)]
+struct AgileParams {
+    key_salt: Vec<u8>,
+    key_hash: HashAlgorithm,
+    block_size: usize,
+    password_salt: Vec<u8>,
+    password_hash: HashAlgorithm,
+    spin_count: u32,
+    key_bits: usize,
+    encrypted_verifier_input: Vec<u8>,
+    encrypted_verifier_value: Vec<u8>,
+    encrypted_key_value: Vec<u8>,
+}
+
+impl AgileParams {
+    fn parse(xml: &[u8]) -> Result<Self> {
+        let xml = String::from_utf8_lossy(xml);
+        let mut reader = Reader::from_str(&xml);
+        let mut buf = Vec::new();
+        let mut key_data = None;
+        let mut encrypted_key = None;
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(ref e) | Event::Empty(ref e) => match e.local_name().as_ref() {
+                    b"keyData" => key_data = Some(e.to_owned()),
+                    // Only the password key encryptor is supported (not certificates)
+                    b"encryptedKey" if encrypted_key.is_none() && attr(e, "spinCount").is_some() => {
+                        encrypted_key = Some(e.to_owned())
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        let key_data = key_data.ok_or_else(|| decryption_error("missing keyData"))?;
+        let encrypted_key =
+            encrypted_key.ok_or_else(|| decryption_error("missing password key encryptor"))?;
+
+        let required = |element: &quick_xml::events::BytesStart, name: &str| {
+            attr(element, name)
+                .ok_or_else(|| decryption_error(&format!("missing attribute {}", name)))
+        };
+        let base64 = |element: &quick_xml::events::BytesStart, name: &str| -> Result<Vec<u8>> {
+            BASE64
+                .decode(required(element, name)?)
+                .map_err(|e| decryption_error(&format!("invalid {}: {}", name, e)))
+        };
+        let number = |element: &quick_xml::events::BytesStart, name: &str| -> Result<usize> {
+            required(element, name)?
+                .parse()
+                .map_err(|_| decryption_error(&format!("invalid {}", name)))
+        };
+
+        if required(&key_data, "cipherAlgorithm")? != "AES"
+            || required(&encrypted_key, "cipherAlgorithm")? != "AES"
+        {
+            return Err(decryption_error("only AES encrypted workbooks are supported"));
+        }
+
+        Ok(Self {
+            key_salt: base64(&key_data, "saltValue")?,
+            key_hash: HashAlgorithm::from_name(&required(&key_data, "hashAlgorithm")?)?,
+            block_size: number(&key_data, "blockSize")?,
+            password_salt: base64(&encrypted_key, "saltValue")?,
+            password_hash: HashAlgorithm::from_name(&required(&encrypted_key, "hashAlgorithm")?)?,
+            spin_count: number(&encrypted_key, "spinCount")? as u32,
+            key_bits: number(&encrypted_key, "keyBits")?,
+            encrypted_verifier_input: base64(&encrypted_key, "encryptedVerifierHashInput")?,
+            encrypted_verifier_value: base64(&encrypted_key, "encryptedVerifierHashValue")?,
+            encrypted_key_value: base64(&encrypted_key, "encryptedKeyValue")?,
+        })
+    }
+
+    fn hash(&self, parts: &[&[u8]]) -> Vec<u8> {
+        self.key_hash.digest(parts)
+    }
+
+    /// Derive the package key from the password, verifying the password on the way
+    fn secret_key(&self, password: &[u8]) -> Result<Vec<u8>> {
+        let hash = self.password_hash;
+        let mut h = hash.digest(&[&self.password_salt, password]);
+        for i in 0..self.spin_count {
+            h = hash.digest(&[&i.to_le_bytes(), &h]);
+        }
+
+        let key_len = self.key_bits / 8;
+        let derive = |block_key: &[u8]| fit(&hash.digest(&[&h, block_key]), key_len, 0x36);
+        let decrypt = |block_key: &[u8], data: &[u8]| -> Result<Vec<u8>> {
+            let mut data = data.to_vec();
+            data.truncate(data.len() / 16 * 16);
+            AesKey::new(&derive(block_key))?.decrypt_cbc(&self.password_salt, &mut data);
+            Ok(data)
+        };
+
+        let verifier_input = decrypt(&BLOCK_KEY_VERIFIER_INPUT, &self.encrypted_verifier_input)?;
+        let verifier_input = &verifier_input[..self.password_salt.len().min(verifier_input.len())];
+        let expected = hash.digest(&[verifier_input]);
+        let actual = decrypt(&BLOCK_KEY_VERIFIER_VALUE, &self.encrypted_verifier_value)?;
+        if actual.len() < expected.len() || actual[..expected.len()] != expected[..] {
+            return Err(ExcelParserError::WrongPassword);
+        }
+
+        let mut key = decrypt(&BLOCK_KEY_SECRET_KEY, &self.encrypted_key_value)?;
+        key.truncate(key_len);
+        Ok(key)
+    }
+}
+
+/// Parameters of Standard encryption, read from the binary header and verifier
+#[derive(Debug)]
+struct StandardParams {
+    key_bits: usize,
+    salt: Vec<u8>,
+    encrypted_verifier: Vec<u8>,
+    verifier_hash_size: usize,
+    encrypted_verifier_hash: Vec<u8>,
+}
+
+impl StandardParams {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let u32_at = |offset: usize| -> Result<u32> {
+            data.get(offset..offset + 4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .ok_or_else(|| decryption_error("truncated EncryptionInfo"))
+        };
+        let bytes_at = |offset: usize, len: usize| -> Result<Vec<u8>> {
+            data.get(offset..offset + len)
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| decryption_error("truncated EncryptionInfo"))
+        };
+
+        // EncryptionInfo: flags (already skipped), header size, header, verifier
+        let header_size = u32_at(0)? as usize;
+        let header = 4;
+        let alg_id = u32_at(header + 8)?;
+        if !matches!(alg_id, 0x660E..=0x6610) {
+            return Err(decryption_error("only AES encrypted workbooks are supported"));
+        }
+        let key_bits = u32_at(header + 16)? as usize;
+
+        let verifier = header + header_size;
+        let salt_size = u32_at(verifier)? as usize;
+        let salt = bytes_at(verifier + 4, salt_size)?;
+        let encrypted_verifier = bytes_at(verifier + 4 + salt_size, 16)?;
+        let verifier_hash_size = u32_at(verifier + 20 + salt_size)? as usize;
+        let encrypted_verifier_hash = bytes_at(verifier + 24 + salt_size, 32)?;
+
+        Ok(Self {
+            key_bits,
+            salt,
+            encrypted_verifier,
+            verifier_hash_size,
+            encrypted_verifier_hash,
+        })
+    }
+
+    /// Derive the package key from the password, verifying the password on the way
+    fn secret_key(&self, password: &[u8]) -> Result<Vec<u8>> {
+        let sha1 = HashAlgorithm::Sha1;
+        let mut h = sha1.digest(&[&self.salt, password]);
+        for i in 0..STANDARD_SPIN_COUNT {
+            h = sha1.digest(&[&i.to_le_bytes(), &h]);
+        }
+        let h = sha1.digest(&[&h, &0u32.to_le_bytes()]);
+
+        let pad = |byte: u8| {
+            let mut buf = [byte; 64];
+            for (b, x) in buf.iter_mut().zip(&h) {
+                *b ^= x;
+            }
+            sha1.digest(&[&buf])
+        };
+        let mut key = pad(0x36);
+        key.extend(pad(0x5c));
+        key.truncate(self.key_bits / 8);
+
+        let cipher = AesKey::new(&key)?;
+        let mut verifier = self.encrypted_verifier.clone();
+        cipher.decrypt_ecb(&mut verifier);
+        let mut verifier_hash = self.encrypted_verifier_hash.clone();
+        cipher.decrypt_ecb(&mut verifier_hash);
+
+        let expected = sha1.digest(&[&verifier]);
+        let size = self.verifier_hash_size.min(expected.len());
+        if verifier_hash.len() < size || verifier_hash[..size] != expected[..size] {
+            return Err(ExcelParserError::WrongPassword);
+        }
+
+        Ok(key)
+    }
+}
+
+enum AesKey {
+    Aes128(Aes128),
+    Aes192(Aes192),
+    Aes256(Aes256),
+}
+
+impl AesKey {
+    fn new(key: &[u8]) -> Result<Self> {
+        let invalid = |_| decryption_error("invalid AES key length");
+        match key.len() {
+            16 => Aes128::new_from_slice(key).map(Self::Aes128).map_err(invalid),
+            24 => Aes192::new_from_slice(key).map(Self::Aes192).map_err(invalid),
+            32 => Aes256::new_from_slice(key).map(Self::Aes256).map_err(invalid),
+            _ => Err(decryption_error("invalid AES key length")),
+        }
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            Self::Aes128(cipher) => cipher.decrypt_block(block),
+            Self::Aes192(cipher) => cipher.decrypt_block(block),
+            Self::Aes256(cipher) => cipher.decrypt_block(block),
+        }
+    }
+
+    fn decrypt_ecb(&self, data: &mut [u8]) {
+        for block in data.chunks_exact_mut(16) {
+            self.decrypt_block(block);
+        }
+    }
+
+    fn decrypt_cbc(&self, iv: &[u8], data: &mut [u8]) {
+        let mut previous = fit(iv, 16, 0);
+        for block in data.chunks_exact_mut(16) {
+            let ciphertext = block.to_vec();
+            self.decrypt_block(block);
+            for (b, p) in block.iter_mut().zip(&previous) {
+                *b ^= p;
+            }
+            previous = ciphertext;
+        }
+    }
+}
+
+/// Truncate or pad a derived value to the required length
+fn fit(value: &[u8], len: usize, pad: u8) -> Vec<u8> {
+    let mut value = value.to_vec();
+    value.resize(len, pad);
+    value
+}
+
+fn decryption_error(msg: &str) -> ExcelParserError {
+    ExcelParserError::Decryption(msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes::cipher::BlockEncrypt;
+
+    fn encrypt_cbc(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+        let cipher = Aes256::new_from_slice(key).unwrap();
+        let mut previous = fit(iv, 16, 0);
+        let mut output = Vec::new();
+        for chunk in data.chunks(16) {
+            let mut block = fit(chunk, 16, 0);
+            for (b, p) in block.iter_mut().zip(&previous) {
+                *b ^= p;
+            }
+            cipher.encrypt_block(GenericArray::from_mut_slice(&mut block));
+            previous = block.clone();
+            output.extend_from_slice(&block);
+        }
+        output
+    }
+
+    /// Build EncryptionInfo/EncryptedPackage streams the way Excel writes Agile encryption
+    fn agile_fixture(password: &str, content: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let hash = HashAlgorithm::Sha512;
+        let key_salt = [7u8; 16];
+        let password_salt = [9u8; 16];
+        let secret_key = [42u8; 32];
+        let verifier = [3u8; 16];
+        let spin_count = 1000u32;
+
+        let password: Vec<u8> = password.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        let mut h = hash.digest(&[&password_salt, &password]);
+        for i in 0..spin_count {
+            h = hash.digest(&[&i.to_le_bytes(), &h]);
+        }
+        let derive = |block_key: &[u8]| fit(&hash.digest(&[&h, block_key]), 32, 0x36);
+
+        let verifier_input = encrypt_cbc(&derive(&BLOCK_KEY_VERIFIER_INPUT), &password_salt, &verifier);
+        let verifier_value = encrypt_cbc(
+            &derive(&BLOCK_KEY_VERIFIER_VALUE),
+            &password_salt,
+            &hash.digest(&[&verifier]),
+        );
+        let key_value = encrypt_cbc(&derive(&BLOCK_KEY_SECRET_KEY), &password_salt, &secret_key);
+
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<encryption xmlns="http://schemas.microsoft.com/office/2006/encryption" xmlns:p="http://schemas.microsoft.com/office/2006/keyEncryptor/password">
+<keyData saltSize="16" blockSize="16" keyBits="256" hashSize="64" cipherAlgorithm="AES" cipherChaining="ChainingModeCBC" hashAlgorithm="SHA512" saltValue="{}"/>
+<keyEncryptors><keyEncryptor uri="http://schemas.microsoft.com/office/2006/keyEncryptor/password">
+<p:encryptedKey spinCount="{}" saltSize="16" blockSize="16" keyBits="256" hashSize="64" cipherAlgorithm="AES" cipherChaining="ChainingModeCBC" hashAlgorithm="SHA512" saltValue="{}" encryptedVerifierHashInput="{}" encryptedVerifierHashValue="{}" encryptedKeyValue="{}"/>
+</keyEncryptor></keyEncryptors></encryption>"#,
+            BASE64.encode(key_salt),
+            spin_count,
+            BASE64.encode(password_salt),
+            BASE64.encode(verifier_input),
+            BASE64.encode(verifier_value),
+            BASE64.encode(key_value),
+        );
+
+        let mut info = vec![4, 0, 4, 0, 0x40, 0, 0, 0];
+        info.extend_from_slice(xml.as_bytes());
+
+        let mut package = (content.len() as u64).to_le_bytes().to_vec();
+        for (index, segment) in content.chunks(AGILE_SEGMENT_LENGTH).enumerate() {
+            let iv = fit(&hash.digest(&[&key_salt, &(index as u32).to_le_bytes()]), 16, 0x36);
+            package.extend(encrypt_cbc(&secret_key, &iv, segment));
+        }
+
+        (info, package)
+    }
+
+    #[test]
+    fn test_agile_round_trip() {
+        let content: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+        let (info, package) = agile_fixture("s3cret", &content);
+
+        let decrypted = decrypt(&info, &package, "s3cret").unwrap();
+        assert_eq!(decrypted, content);
+    }
+
+    #[test]
+    fn test_agile_wrong_password() {
+        let (info, package) = agile_fixture("s3cret", b"PK\x03\x04 payload");
+
+        assert!(matches!(
+            decrypt(&info, &package, "guess"),
+            Err(ExcelParserError::WrongPassword)
+        ));
+    }
+
+    #[test]
+    fn test_standard_round_trip() {
+        let sha1 = HashAlgorithm::Sha1;
+        let salt = [5u8; 16];
+        let verifier = [11u8; 16];
+        let content = b"PK\x03\x04 standard encrypted package".to_vec();
+
+        // Derive the AES-128 key exactly as StandardParams::secret_key does
+        let password: Vec<u8> = "pw".encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+        let mut h = sha1.digest(&[&salt, &password]);
+        for i in 0..STANDARD_SPIN_COUNT {
+            h = sha1.digest(&[&i.to_le_bytes(), &h]);
+        }
+        let h = sha1.digest(&[&h, &0u32.to_le_bytes()]);
+        let mut buf = [0x36u8; 64];
+        for (b, x) in buf.iter_mut().zip(&h) {
+            *b ^= x;
+        }
+        let key = sha1.digest(&[&buf])[..16].to_vec();
+
+        let cipher = Aes128::new_from_slice(&key).unwrap();
+        let encrypt_ecb = |data: &[u8]| {
+            let mut data = fit(data, data.len().div_ceil(16) * 16, 0);
+            for block in data.chunks_exact_mut(16) {
+                cipher.encrypt_block(GenericArray::from_mut_slice(block));
+            }
+            data
+        };
+
+        let mut header = Vec::new();
+        for value in [0x24u32, 0, 0x660E, 0x8004, 128, 0x18, 0, 0] {
+            header.extend_from_slice(&value.to_le_bytes());
+        }
+        let mut info = vec![4, 0, 2, 0, 0x24, 0, 0, 0];
+        info.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        info.extend_from_slice(&header);
+        info.extend_from_slice(&16u32.to_le_bytes());
+        info.extend_from_slice(&salt);
+        info.extend(encrypt_ecb(&verifier));
+        info.extend_from_slice(&20u32.to_le_bytes());
+        info.extend(encrypt_ecb(&sha1.digest(&[&verifier])));
+
+        let mut package = (content.len() as u64).to_le_bytes().to_vec();
+        package.extend(encrypt_ecb(&content));
+
+        assert_eq!(decrypt(&info, &package, "pw").unwrap(), content);
+        assert!(matches!(
+            decrypt(&info, &package, "nope"),
+            Err(ExcelParserError::WrongPassword)
+        ));
+    }
+
+    #[test]
+    fn test_parse_encrypted_workbook() {
+        use crate::parser::tests::{cell, write_xlsx};
+        use crate::parser::ExcelParser;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.xlsx");
+        let sheet_data = format!(r#"<row r="1">{}</row>"#, cell("A1", "Confidential"));
+        write_xlsx(&plain, &[("Budget", &sheet_data, "")], &[]);
+        let (info, package) = agile_fixture("s3cret", &std::fs::read(&plain).unwrap());
+
+        let encrypted = dir.path().join("locked.xlsx");
+        let mut compound = cfb::create(&encrypted).unwrap();
+        compound.create_stream("/EncryptionInfo").unwrap().write_all(&info).unwrap();
+        compound.create_stream("/EncryptedPackage").unwrap().write_all(&package).unwrap();
+        compound.flush().unwrap();
+        drop(compound);
+
+        assert!(is_encrypted(&encrypted).unwrap());
+        assert!(matches!(
+            ExcelParser::new().parse(&encrypted),
+            Err(ExcelParserError::PasswordRequired)
+        ));
+        assert!(matches!(
+            ExcelParser::new().with_password("wrong").parse(&encrypted),
+            Err(ExcelParserError::WrongPassword)
+        ));
+
+        let data = ExcelParser::new().with_password("s3cret").parse(&encrypted).unwrap();
+        assert_eq!(data.sheets[0].name, "Budget");
+        assert_eq!(data.sheets[0].data, vec![vec!["Confidential"]]);
+    }
+
+    #[test]
+    fn test_plain_file_is_not_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.xlsx");
+        std::fs::write(&path, b"PK\x03\x04not really a zip").unwrap();
+        assert!(!is_encrypted(&path).unwrap());
+    }
+}
@@ -0,0 +1,445 @@
+use super::package::{attr, cell_ref, XlsxPackage};
+use crate::error::Result;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Pictures and charts embedded in a workbook
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkbookMedia {
+    pub images: Vec<ImageInfo>,
+    pub charts: Vec<ChartInfo>,
+}
+
+/// A picture from `xl/media`, listed once per placement on a sheet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageInfo {
+    /// Part name inside the package, e.g. "xl/media/image1.png"
+    pub part: String,
+    /// File name used when extracting
+    pub file_name: String,
+    /// Lowercase file extension ("png", "jpeg", "emf", ...)
+    pub format: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Sheet showing the picture; `None` when it is not placed on any sheet
+    pub sheet: Option<String>,
+    /// Top-left anchoring cell ("B3")
+    pub anchor: Option<String>,
+    /// Shape name as shown in Excel's selection pane
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Alt text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A chart from `xl/charts/*.xml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChartInfo {
+    /// Part name inside the package, e.g. "xl/charts/chart1.xml"
+    pub part: String,
+    pub sheet: Option<String>,
+    pub anchor: Option<String>,
+    /// Type of the first plot ("column", "line", "pie", ...)
+    pub chart_type: Option<String>,
+    pub title: Option<String>,
+    pub series: Vec<ChartSeries>,
+}
+
+/// One data series of a chart, with the ranges it references
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChartSeries {
+    pub name: Option<String>,
+    /// Plot type of this series; differs from the chart type in combo charts
+    pub chart_type: String,
+    /// Reference of the series name, e.g. "Sales!$B$1"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_ref: Option<String>,
+    /// Category (or scatter X) range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories_ref: Option<String>,
+    /// Value (or scatter Y) range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values_ref: Option<String>,
+}
+
+/// Something placed on a drawing, with the zero-based cell it is anchored to
+#[derive(Debug, Clone, PartialEq)]
+enum DrawingObject {
+    Picture {
+        rel_id: String,
+        anchor: Option<(u32, u32)>,
+        name: Option<String>,
+        description: Option<String>,
+    },
+    Chart {
+        rel_id: String,
+        anchor: Option<(u32, u32)>,
+    },
+}
+
+/// List all images and charts, following sheet -> drawing -> media/chart relationships
+pub fn read_media(package: &mut XlsxPackage) -> Result<WorkbookMedia> {
+    let mut media = WorkbookMedia::default();
+    let mut placed_images = HashSet::new();
+    let mut placed_charts = HashSet::new();
+    let sheets = package.sheets().to_vec();
+
+    for (sheet_name, sheet_part) in sheets {
+        let sheet_rels = package.relationships(&sheet_part)?;
+        for drawing in sheet_rels.iter().filter(|rel| rel.is_type("drawing")) {
+            let content = match package.read_part(&drawing.target)? {
+                Some(content) => content,
+                None => continue,
+            };
+            let drawing_rels = package.relationships(&drawing.target)?;
+
+            for object in parse_drawing_xml(&content)? {
+                match object {
+                    DrawingObject::Picture {
+                        rel_id,
+                        anchor,
+                        name,
+                        description,
+                    } => {
+                        let Some(rel) = drawing_rels.iter().find(|rel| rel.id == rel_id && !rel.external)
+                        else {
+                            continue;
+                        };
+                        let Some(mut image) = image_info(package, &rel.target) else {
+                            continue;
+                        };
+                        image.sheet = Some(sheet_name.clone());
+                        image.anchor = anchor.map(|(row, column)| cell_ref(row, column));
+                        image.name = name;
+                        image.description = description;
+                        placed_images.insert(rel.target.clone());
+                        media.images.push(image);
+                    }
+                    DrawingObject::Chart { rel_id, anchor } => {
+                        let Some(rel) = drawing_rels.iter().find(|rel| rel.id == rel_id) else {
+                            continue;
+                        };
+                        let Some(content) = package.read_part(&rel.target)? else {
+                            continue;
+                        };
+                        let mut chart = parse_chart_xml(&content)?;
+                        chart.part = rel.target.clone();
+                        chart.sheet = Some(sheet_name.clone());
+                        chart.anchor = anchor.map(|(row, column)| cell_ref(row, column));
+                        placed_charts.insert(rel.target.clone());
+                        media.charts.push(chart);
+                    }
+                }
+            }
+        }
+    }
+
+    // Parts that exist in the package but are not placed on any sheet
+    for part in package.part_names() {
+        if part.starts_with("xl/media/") && !placed_images.contains(&part) {
+            if let Some(image) = image_info(package, &part) {
+                media.images.push(image);
+            }
+        } else if is_chart_part(&part) && !placed_charts.contains(&part) {
+            if let Some(content) = package.read_part(&part)? {
+                let mut chart = parse_chart_xml(&content)?;
+                chart.part = part;
+                media.charts.push(chart);
+            }
+        }
+    }
+
+    Ok(media)
+}
+
+/// Write every listed image to `output_dir` (each part once), returning the written paths
+pub fn extract_images(
+    package: &mut XlsxPackage,
+    images: &[ImageInfo],
+    output_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(output_dir)?;
+
+    let mut written = Vec::new();
+    let mut seen = HashSet::new();
+    for image in images {
+        if !seen.insert(image.part.as_str()) {
+            continue;
+        }
+        if let Some(bytes) = package.read_part_bytes(&image.part)? {
+            let path = output_dir.join(&image.file_name);
+            std::fs::write(&path, bytes)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+fn image_info(package: &mut XlsxPackage, part: &str) -> Option<ImageInfo> {
+    let size = package.part_size(part)?;
+    let file_name = part.rsplit('/').next().unwrap_or(part).to_string();
+    let format = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+
+    Some(ImageInfo {
+        part: part.to_string(),
+        file_name,
+        format,
+        size,
+        sheet: None,
+        anchor: None,
+        name: None,
+        description: None,
+    })
+}
+
+fn is_chart_part(part: &str) -> bool {
+    part.strip_prefix("xl/charts/")
+        .is_some_and(|name| name.starts_with("chart") && name.ends_with(".xml") && !name.contains('/'))
+}
+
+/// Collect pictures and charts from a drawing part (`xl/drawings/drawingN.xml`)
+fn parse_drawing_xml(content: &str) -> Result<Vec<DrawingObject>> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut objects = Vec::new();
+
+    // Anchor of the enclosing xdr:*Anchor; group shapes share their anchor
+    let mut anchor: Option<(u32, u32)> = None;
+    let mut in_from = false;
+    let mut current: Option<&'static str> = None;
+    let mut from = (0u32, 0u32);
+    let mut picture_name: Option<(Option<String>, Option<String>)> = None;
+    let mut in_picture = false;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => match e.local_name().as_ref() {
+                b"twoCellAnchor" | b"oneCellAnchor" | b"absoluteAnchor" => {
+                    anchor = None;
+                    from = (0, 0);
+                }
+                b"from" => in_from = true,
+                b"col" if in_from => current = Some("col"),
+                b"row" if in_from => current = Some("row"),
+                b"pic" => {
+                    in_picture = true;
+                    picture_name = None;
+                }
+                _ => {}
+            },
+            Event::Empty(ref e) => match e.local_name().as_ref() {
+                b"cNvPr" if in_picture => picture_name = Some(shape_name(e)),
+                b"blip" if in_picture => {
+                    if let Some(rel_id) = attr(e, "embed") {
+                        let (name, description) = picture_name.take().unwrap_or_default();
+                        objects.push(DrawingObject::Picture {
+                            rel_id,
+                            anchor,
+                            name,
+                            description,
+                        });
+                    }
+                }
+                b"chart" => {
+                    if let Some(rel_id) = attr(e, "id") {
+                        objects.push(DrawingObject::Chart { rel_id, anchor });
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(e) => {
+                if let Some(field) = current {
+                    let value: u32 = e.unescape()?.trim().parse().unwrap_or(0);
+                    match field {
+                        "col" => from.1 = value,
+                        _ => from.0 = value,
+                    }
+                }
+            }
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"from" => {
+                    in_from = false;
+                    anchor = Some(from);
+                }
+                b"col" | b"row" => current = None,
+                b"pic" => in_picture = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(objects)
+}
+
+fn shape_name(element: &BytesStart) -> (Option<String>, Option<String>) {
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    (non_empty(attr(element, "name")), non_empty(attr(element, "descr")))
+}
+
+/// Read chart type, title and series references from a chart part
+fn parse_chart_xml(content: &str) -> Result<ChartInfo> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut chart = ChartInfo::default();
+    let mut title = String::new();
+
+    // Local names of the open elements, starting below c:chartSpace
+    let mut path: Vec<String> = Vec::new();
+    let mut plot_type: Option<String> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                if in_plot_area(&path) && name.ends_with("Chart") {
+                    let kind = name.trim_end_matches("Chart").to_string();
+                    // Bar charts are columns unless barDir says otherwise
+                    plot_type = Some(if kind == "bar" { "column".to_string() } else { kind });
+                } else if name == "ser" && in_plot(&path) {
+                    chart.series.push(ChartSeries {
+                        chart_type: plot_type.clone().unwrap_or_default(),
+                        ..Default::default()
+                    });
+                }
+                path.push(name);
+            }
+            Event::Empty(ref e)
+                if e.local_name().as_ref() == b"barDir"
+                    && in_plot(&path)
+                    && attr(e, "val").as_deref() == Some("bar")
+                    && plot_type.as_deref() == Some("column") =>
+            {
+                plot_type = Some("bar".to_string());
+            }
+            Event::End(_) => {
+                let closed = path.pop();
+                if in_plot_area(&path) && closed.as_deref().is_some_and(|n| n.ends_with("Chart")) {
+                    if chart.chart_type.is_none() {
+                        chart.chart_type = plot_type.clone();
+                    }
+                    plot_type = None;
+                }
+            }
+            Event::Text(e) => {
+                let text = e.unescape()?;
+                let names: Vec<&str> = path.iter().map(String::as_str).collect();
+                match names.as_slice() {
+                    ["chartSpace", "chart", "title", .., "t"] | ["chartSpace", "chart", "title", .., "v"] => {
+                        title.push_str(&text);
+                    }
+                    ["chartSpace", "chart", "plotArea", _, "ser", rest @ ..] => {
+                        if let Some(series) = chart.series.last_mut() {
+                            apply_series_text(series, rest, text.trim());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    if !title.trim().is_empty() {
+        chart.title = Some(title.trim().to_string());
+    }
+    Ok(chart)
+}
+
+/// Whether `path` is c:chartSpace/c:chart/c:plotArea
+fn in_plot_area(path: &[String]) -> bool {
+    path.len() == 3 && path[2] == "plotArea"
+}
+
+/// Whether `path` is a plot (c:barChart, c:lineChart, ...) inside the plot area
+fn in_plot(path: &[String]) -> bool {
+    path.len() == 4 && in_plot_area(&path[..3])
+}
+
+/// Route text found below c:ser to the matching series field
+fn apply_series_text(series: &mut ChartSeries, path: &[&str], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match path {
+        ["tx", "strRef", "f"] => series.name_ref = Some(text.to_string()),
+        ["tx", "v"] | ["tx", "strRef", "strCache", "pt", "v"] => {
+            series.name.get_or_insert_with(|| text.to_string());
+        }
+        ["cat" | "xVal", _, "f"] => series.categories_ref = Some(text.to_string()),
+        ["val" | "yVal", _, "f"] => series.values_ref = Some(text.to_string()),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chart_xml() {
+        let xml = r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><c:chart>
+            <c:title><c:tx><c:rich><a:p><a:r><a:t>Monthly </a:t></a:r><a:r><a:t>sales</a:t></a:r></a:p></c:rich></c:tx></c:title>
+            <c:plotArea>
+              <c:barChart><c:barDir val="bar"/>
+                <c:ser><c:idx val="0"/><c:tx><c:strRef><c:f>Sales!$B$1</c:f><c:strCache><c:pt idx="0"><c:v>Tea</c:v></c:pt></c:strCache></c:strRef></c:tx>
+                  <c:cat><c:strRef><c:f>Sales!$A$2:$A$13</c:f></c:strRef></c:cat><c:val><c:numRef><c:f>Sales!$B$2:$B$13</c:f></c:numRef></c:val></c:ser>
+              </c:barChart>
+              <c:lineChart><c:ser><c:tx><c:v>Trend</c:v></c:tx><c:val><c:numRef><c:f>Sales!$C$2:$C$13</c:f></c:numRef></c:val></c:ser></c:lineChart>
+              <c:valAx><c:title><c:tx><c:rich><a:p><a:r><a:t>Units</a:t></a:r></a:p></c:rich></c:tx></c:title></c:valAx>
+            </c:plotArea></c:chart></c:chartSpace>"#;
+
+        let chart = parse_chart_xml(xml).unwrap();
+        assert_eq!(chart.title.as_deref(), Some("Monthly sales"));
+        assert_eq!(chart.chart_type.as_deref(), Some("bar"));
+        assert_eq!(chart.series.len(), 2);
+
+        let tea = &chart.series[0];
+        assert_eq!(tea.name.as_deref(), Some("Tea"));
+        assert_eq!(tea.name_ref.as_deref(), Some("Sales!$B$1"));
+        assert_eq!(tea.categories_ref.as_deref(), Some("Sales!$A$2:$A$13"));
+        assert_eq!(tea.values_ref.as_deref(), Some("Sales!$B$2:$B$13"));
+
+        let trend = &chart.series[1];
+        assert_eq!(trend.chart_type, "line");
+        assert_eq!(trend.name.as_deref(), Some("Trend"));
+    }
+
+    #[test]
+    fn test_parse_drawing_xml() {
+        let xml = r#"<xdr:wsDr xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart">
+            <xdr:twoCellAnchor><xdr:from><xdr:col>1</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>2</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:from><xdr:to><xdr:col>4</xdr:col><xdr:row>9</xdr:row></xdr:to>
+              <xdr:pic><xdr:nvPicPr><xdr:cNvPr id="2" name="Picture 1" descr="Company logo"/><xdr:cNvPicPr/></xdr:nvPicPr><xdr:blipFill><a:blip r:embed="rId1"/></xdr:blipFill></xdr:pic><xdr:clientData/></xdr:twoCellAnchor>
+            <xdr:oneCellAnchor><xdr:from><xdr:col>6</xdr:col><xdr:row>0</xdr:row></xdr:from><xdr:ext cx="1" cy="1"/>
+              <xdr:graphicFrame><a:graphic><a:graphicData><c:chart r:id="rId2"/></a:graphicData></a:graphic></xdr:graphicFrame></xdr:oneCellAnchor>
+            </xdr:wsDr>"#;
+
+        let objects = parse_drawing_xml(xml).unwrap();
+        assert_eq!(
+            objects,
+            vec![
+                DrawingObject::Picture {
+                    rel_id: "rId1".to_string(),
+                    anchor: Some((2, 1)),
+                    name: Some("Picture 1".to_string()),
+                    description: Some("Company logo".to_string()),
+                },
+                DrawingObject::Chart {
+                    rel_id: "rId2".to_string(),
+                    anchor: Some((0, 6)),
+                },
+            ]
+        );
+    }
+}
@@ -1,23 +1,89 @@
 use crate::error::{ExcelParserError, Result};
-use calamine::{open_workbook_auto, Data, Reader, Sheets};
+use calamine::{Data, Reader, Sheets};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
-pub struct ExcelParser;
+pub mod annotations;
+pub mod crypto;
+pub mod media;
+pub mod package;
+pub mod source;
+pub mod tables;
+
+pub use annotations::{CellAnnotation, CellComment, DataValidation, Hyperlink};
+pub use media::{ChartInfo, ChartSeries, ImageInfo, WorkbookMedia};
+pub use tables::{ExcelTable, TableInfo};
+use annotations::{read_sheet_extras, SheetExtras};
+use package::{cell_ref, parse_cell_ref, parse_range_ref, XlsxPackage};
+use source::{WorkbookReader, WorkbookSource};
+
+pub struct ExcelParser {
+    password: Option<String>,
+}
 
 #[derive(Debug, Clone)]
 pub struct ExcelData {
     pub sheets: Vec<Sheet>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Sheet {
     pub name: String,
     pub data: Vec<Vec<String>>,
+    /// Comments, hyperlinks and validation rules attached to cells (xlsx/xlsm only)
+    pub annotations: Vec<CellAnnotation>,
+    /// Data validation rules declared on the sheet
+    pub validations: Vec<DataValidation>,
+}
+
+impl Sheet {
+    pub fn new(name: impl Into<String>, data: Vec<Vec<String>>) -> Self {
+        Self {
+            name: name.into(),
+            data,
+            ..Default::default()
+        }
+    }
+
+    /// Annotations keyed by their position in `data`; build once when looking up many cells
+    pub fn annotation_index(&self) -> HashMap<(usize, usize), &CellAnnotation> {
+        let mut index = HashMap::with_capacity(self.annotations.len());
+        for annotation in &self.annotations {
+            if let (Some(row), Some(column)) = (annotation.row, annotation.column) {
+                index.entry((row, column)).or_insert(annotation);
+            }
+        }
+        index
+    }
+
+    /// Get the annotation of a cell by its position in `data`
+    pub fn annotation(&self, row: usize, column: usize) -> Option<&CellAnnotation> {
+        self.annotations
+            .iter()
+            .find(|a| a.row == Some(row) && a.column == Some(column))
+    }
+
+    /// Get the data validation rule that applies to a cell by its position in `data`
+    pub fn validation(&self, row: usize, column: usize) -> Option<&DataValidation> {
+        self.annotation(row, column)
+            .and_then(|a| a.validation)
+            .and_then(|index| self.validations.get(index))
+    }
 }
 
 impl ExcelParser {
     pub fn new() -> Self {
-        Self
+        Self { password: None }
+    }
+
+    /// Password used to decrypt password protected workbooks
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    fn open_source(&self, file_path: &Path) -> Result<WorkbookSource> {
+        WorkbookSource::open(file_path, self.password.as_deref())
     }
 
     /// Parse Excel file and return structured data
@@ -62,13 +128,16 @@ impl ExcelParser {
     }
 
     fn parse_xlsx<P: AsRef<Path>>(&self, file_path: P) -> Result<ExcelData> {
-        let file_path = file_path.as_ref();
-        let mut workbook: Sheets<_> = open_workbook_auto(file_path)?;
+        let source = self.open_source(file_path.as_ref())?;
+        let mut workbook = source.workbook()?;
+        let mut package = XlsxPackage::open(&source)?;
         let sheet_names = workbook.sheet_names().to_vec();
         let mut sheets = Vec::new();
 
         for sheet_name in sheet_names {
-            if let Some(sheet_data) = self.extract_sheet_data(&mut workbook, &sheet_name)? {
+            if let Some(sheet_data) =
+                self.extract_sheet_data(&mut workbook, package.as_mut(), &sheet_name)?
+            {
                 sheets.push(sheet_data);
             }
         }
@@ -87,10 +156,11 @@ impl ExcelParser {
     }
 
     fn parse_xlsx_sheet<P: AsRef<Path>>(&self, file_path: P, sheet_name: &str) -> Result<Sheet> {
-        let file_path = file_path.as_ref();
-        let mut workbook: Sheets<_> = open_workbook_auto(file_path)?;
+        let source = self.open_source(file_path.as_ref())?;
+        let mut workbook = source.workbook()?;
+        let mut package = XlsxPackage::open(&source)?;
 
-        self.extract_sheet_data(&mut workbook, sheet_name)?
+        self.extract_sheet_data(&mut workbook, package.as_mut(), sheet_name)?
             .ok_or_else(|| ExcelParserError::SheetNotFound(sheet_name.to_string()))
     }
 
@@ -99,9 +169,10 @@ impl ExcelParser {
         self.parse_xlsx_sheet(file_path, sheet_name)
     }
 
-    fn extract_sheet_data<R: std::io::Read + std::io::Seek>(
+    fn extract_sheet_data(
         &self,
-        workbook: &mut Sheets<R>,
+        workbook: &mut Sheets<WorkbookReader>,
+        package: Option<&mut XlsxPackage>,
         sheet_name: &str,
     ) -> Result<Option<Sheet>> {
         let range = workbook.worksheet_range(sheet_name)?;
@@ -110,34 +181,17 @@ impl ExcelParser {
             return Ok(None);
         }
 
+        let origin = range.start().unwrap_or((0, 0));
         let mut data = Vec::new();
+        // Absolute worksheet row -> index into `data`, since empty rows are dropped
+        let mut row_map = HashMap::new();
 
-        for row in range.rows() {
-            let mut row_data = Vec::new();
-            for cell in row {
-                let cell_value = match cell {
-                    Data::Empty => String::new(),
-                    Data::String(s) => s.clone(),
-                    Data::Float(f) => {
-                        // Check if it's a whole number
-                        if f.fract() == 0.0 {
-                            format!("{}", f.round() as i64)
-                        } else {
-                            format!("{}", f)
-                        }
-                    }
-                    Data::Int(i) => i.to_string(),
-                    Data::Bool(b) => b.to_string(),
-                    Data::DateTime(dt) => dt.to_string(),
-                    Data::Error(e) => format!("#ERROR: {:?}", e),
-                    Data::DateTimeIso(dt) => dt.to_string(),
-                    Data::DurationIso(d) => d.to_string(),
-                };
-                row_data.push(cell_value);
-            }
+        for (row_offset, row) in range.rows().enumerate() {
+            let row_data: Vec<String> = row.iter().map(cell_to_string).collect();
 
             // Only add non-empty rows or rows with at least one non-empty cell
             if !row_data.iter().all(|cell| cell.is_empty()) {
+                row_map.insert(origin.0 + row_offset as u32, data.len());
                 data.push(row_data);
             }
         }
@@ -146,20 +200,297 @@ impl ExcelParser {
             return Ok(None);
         }
 
-        Ok(Some(Sheet {
-            name: sheet_name.to_string(),
-            data,
-        }))
+        let mut sheet = Sheet::new(sheet_name, data);
+
+        if let Some(package) = package {
+            let extras = read_sheet_extras(package, sheet_name)?;
+            let grid = DataGrid {
+                origin,
+                width: range.width() as u32,
+                row_map,
+            };
+            self.attach_annotations(workbook, &mut sheet, extras, &grid);
+        }
+
+        Ok(Some(sheet))
+    }
+
+    fn attach_annotations(
+        &self,
+        workbook: &mut Sheets<WorkbookReader>,
+        sheet: &mut Sheet,
+        extras: SheetExtras,
+        grid: &DataGrid,
+    ) {
+        let mut annotations: BTreeMap<(u32, u32), CellAnnotation> = BTreeMap::new();
+
+        for (reference, comment) in extras.comments {
+            if let Some((row, column)) = parse_cell_ref(&reference) {
+                grid.entry(&mut annotations, row, column).comments.push(comment);
+            }
+        }
+
+        for (reference, hyperlink) in extras.hyperlinks {
+            let Some((start, end)) = parse_range_ref(&reference) else {
+                continue;
+            };
+            // A link range can span whole columns; only expand it over cells we actually output
+            grid.entry(&mut annotations, start.0, start.1).hyperlink = Some(hyperlink.clone());
+            for (row, column) in grid.cells_within(start, end) {
+                grid.entry(&mut annotations, row, column).hyperlink = Some(hyperlink.clone());
+            }
+        }
+
+        let mut validations = extras.validations;
+        for (index, validation) in validations.iter_mut().enumerate() {
+            if validation.validation_type == "list" && validation.list_values.is_empty() {
+                if let Some(formula) = validation.formula1.clone() {
+                    validation.list_values = self.resolve_list_values(workbook, &sheet.name, &formula);
+                }
+            }
+
+            for range in &validation.ranges {
+                let Some((start, end)) = parse_range_ref(range) else {
+                    continue;
+                };
+                for (row, column) in grid.cells_within(start, end) {
+                    grid.entry(&mut annotations, row, column).validation.get_or_insert(index);
+                }
+            }
+        }
+
+        sheet.annotations = annotations.into_values().collect();
+        sheet.validations = validations;
+    }
+
+    /// Resolve a list validation source ("$A$1:$A$5", "Lists!A1:A5" or a defined name) to values
+    fn resolve_list_values(
+        &self,
+        workbook: &mut Sheets<WorkbookReader>,
+        sheet_name: &str,
+        formula: &str,
+    ) -> Vec<String> {
+        let mut formula = formula.trim().trim_start_matches('=').to_string();
+        if let Some((_, target)) = workbook
+            .defined_names()
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&formula))
+        {
+            formula = target.trim_start_matches('=').to_string();
+        }
+
+        let (target_sheet, reference) = match formula.rsplit_once('!') {
+            Some((sheet, reference)) => (
+                sheet.trim_matches('\'').replace("''", "'"),
+                reference.to_string(),
+            ),
+            None => (sheet_name.to_string(), formula),
+        };
+
+        let Some((start, end)) = parse_range_ref(&reference) else {
+            return Vec::new();
+        };
+        let Ok(range) = workbook.worksheet_range(&target_sheet) else {
+            return Vec::new();
+        };
+        // Whole-column sources ("$A:$A") stop at the used range
+        let Some(used_end) = range.end() else {
+            return Vec::new();
+        };
+        let end = (end.0.min(used_end.0), end.1.min(used_end.1));
+
+        let mut values = Vec::new();
+        for row in start.0..=end.0 {
+            for column in start.1..=end.1 {
+                if let Some(value) = range.get_value((row, column)) {
+                    let value = cell_to_string(value);
+                    if !value.is_empty() {
+                        values.push(value);
+                    }
+                }
+            }
+        }
+        values
+    }
+
+    /// List the Excel tables (ListObjects) defined in the workbook
+    pub fn list_tables<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<TableInfo>> {
+        let file_path = file_path.as_ref();
+        if !file_path.exists() {
+            return Err(ExcelParserError::FileNotFound(
+                file_path.display().to_string(),
+            ));
+        }
+
+        Self::tables_in(&self.open_source(file_path)?)
+    }
+
+    fn tables_in(source: &WorkbookSource) -> Result<Vec<TableInfo>> {
+        match XlsxPackage::open(source)? {
+            Some(mut package) => tables::read_tables(&mut package),
+            None => Err(ExcelParserError::UnsupportedFormat(
+                "Excel tables are only available in .xlsx/.xlsm files".to_string(),
+            )),
+        }
+    }
+
+    /// List the pictures and charts embedded in the workbook
+    pub fn list_media<P: AsRef<Path>>(&self, file_path: P) -> Result<WorkbookMedia> {
+        let mut package = self.open_package(file_path.as_ref())?;
+        media::read_media(&mut package)
+    }
+
+    /// Write the embedded pictures to `output_dir`, returning the media listing and written files
+    pub fn extract_media<P: AsRef<Path>, D: AsRef<Path>>(
+        &self,
+        file_path: P,
+        output_dir: D,
+    ) -> Result<(WorkbookMedia, Vec<std::path::PathBuf>)> {
+        let mut package = self.open_package(file_path.as_ref())?;
+        let workbook_media = media::read_media(&mut package)?;
+        let written = media::extract_images(&mut package, &workbook_media.images, output_dir.as_ref())?;
+        Ok((workbook_media, written))
+    }
+
+    fn open_package(&self, file_path: &Path) -> Result<XlsxPackage> {
+        if !file_path.exists() {
+            return Err(ExcelParserError::FileNotFound(
+                file_path.display().to_string(),
+            ));
+        }
+
+        XlsxPackage::open(&self.open_source(file_path)?)?.ok_or_else(|| {
+            ExcelParserError::UnsupportedFormat(
+                "Images and charts are only available in .xlsx/.xlsm files".to_string(),
+            )
+        })
+    }
+
+    /// Parse exactly the range of a named Excel table, e.g. "tblSales"
+    pub fn parse_table<P: AsRef<Path>>(&self, file_path: P, table_name: &str) -> Result<ExcelTable> {
+        let file_path = file_path.as_ref();
+        if !file_path.exists() {
+            return Err(ExcelParserError::FileNotFound(
+                file_path.display().to_string(),
+            ));
+        }
+
+        // Open (and decrypt) the workbook once for both the table definition and its cells
+        let source = self.open_source(file_path)?;
+        let info = Self::tables_in(&source)?
+            .into_iter()
+            .find(|table| table.matches(table_name))
+            .ok_or_else(|| ExcelParserError::TableNotFound(table_name.to_string()))?;
+
+        let ((first_row, first_column), (last_row, last_column)) = parse_range_ref(&info.range)
+            .ok_or_else(|| ExcelParserError::InvalidRange(info.range.clone()))?;
+
+        let mut workbook = source.workbook()?;
+        let range = workbook.worksheet_range(&info.sheet)?;
+
+        let mut rows: Vec<Vec<String>> = (first_row..=last_row)
+            .map(|row| {
+                (first_column..=last_column)
+                    .map(|column| {
+                        range
+                            .get_value((row, column))
+                            .map(cell_to_string)
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if info.has_header_row && !rows.is_empty() {
+            rows.remove(0);
+        }
+        let totals = if info.has_totals_row { rows.pop() } else { None };
+
+        Ok(ExcelTable { info, rows, totals })
     }
 
     /// Get sheet names from Excel file
     pub fn get_sheet_names<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<String>> {
-        let file_path = file_path.as_ref();
-        let workbook: Sheets<_> = open_workbook_auto(file_path)?;
+        let workbook = self.open_source(file_path.as_ref())?.workbook()?;
         Ok(workbook.sheet_names().to_vec())
     }
 }
 
+/// Maps absolute worksheet coordinates onto positions in `Sheet::data`
+struct DataGrid {
+    origin: (u32, u32),
+    width: u32,
+    row_map: HashMap<u32, usize>,
+}
+
+impl DataGrid {
+    fn position(&self, row: u32, column: u32) -> (Option<usize>, Option<usize>) {
+        let data_row = self.row_map.get(&row).copied();
+        let data_column = (column >= self.origin.1 && column < self.origin.1 + self.width)
+            .then(|| (column - self.origin.1) as usize);
+        (data_row, data_column)
+    }
+
+    fn entry<'a>(
+        &self,
+        annotations: &'a mut BTreeMap<(u32, u32), CellAnnotation>,
+        row: u32,
+        column: u32,
+    ) -> &'a mut CellAnnotation {
+        annotations.entry((row, column)).or_insert_with(|| {
+            let (data_row, data_column) = self.position(row, column);
+            CellAnnotation {
+                reference: cell_ref(row, column),
+                row: data_row,
+                column: data_column,
+                ..Default::default()
+            }
+        })
+    }
+
+    /// Cells of the output grid that fall inside the given absolute range
+    fn cells_within(&self, start: (u32, u32), end: (u32, u32)) -> Vec<(u32, u32)> {
+        let first_column = start.1.max(self.origin.1);
+        let last_column = end.1.min(self.origin.1 + self.width.saturating_sub(1));
+        let mut rows: Vec<u32> = self
+            .row_map
+            .keys()
+            .copied()
+            .filter(|row| (start.0..=end.0).contains(row))
+            .collect();
+        rows.sort_unstable();
+
+        let mut cells = Vec::new();
+        for row in rows {
+            for column in first_column..=last_column {
+                cells.push((row, column));
+            }
+        }
+        cells
+    }
+}
+
+fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(s) => s.clone(),
+        Data::Float(f) => {
+            // Check if it's a whole number
+            if f.fract() == 0.0 {
+                format!("{}", f.round() as i64)
+            } else {
+                format!("{}", f)
+            }
+        }
+        Data::Int(i) => i.to_string(),
+        Data::Bool(b) => b.to_string(),
+        Data::DateTime(dt) => dt.to_string(),
+        Data::Error(e) => format!("#ERROR: {:?}", e),
+        Data::DateTimeIso(dt) => dt.to_string(),
+        Data::DurationIso(d) => d.to_string(),
+    }
+}
+
 impl Default for ExcelParser {
     fn default() -> Self {
         Self::new()
@@ -197,4 +528,233 @@ mod tests {
         let result = parser.parse("nonexistent.xlsx");
         assert!(matches!(result, Err(ExcelParserError::FileNotFound(_))));
     }
+
+    /// Build a minimal xlsx package; `sheets` holds (name, sheetData xml, extra worksheet xml)
+    pub(crate) fn write_xlsx(
+        path: &Path,
+        sheets: &[(&str, &str, &str)],
+        extra_parts: &[(&str, &str)],
+    ) {
+        use std::io::Write;
+        use zip::write::FileOptions;
+
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        let options = FileOptions::default();
+
+        let mut workbook = String::from(
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets>"#,
+        );
+        let mut rels = String::from(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        );
+        for (i, (name, _, _)) in sheets.iter().enumerate() {
+            workbook.push_str(&format!(
+                r#"<sheet name="{}" sheetId="{}" r:id="rId{}"/>"#,
+                name,
+                i + 1,
+                i + 1
+            ));
+            rels.push_str(&format!(
+                r#"<Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{}.xml"/>"#,
+                i + 1,
+                i + 1
+            ));
+        }
+        workbook.push_str("</sheets></workbook>");
+        rels.push_str("</Relationships>");
+
+        zip.start_file("xl/workbook.xml", options).unwrap();
+        zip.write_all(workbook.as_bytes()).unwrap();
+        zip.start_file("xl/_rels/workbook.xml.rels", options).unwrap();
+        zip.write_all(rels.as_bytes()).unwrap();
+
+        for (i, (_, sheet_data, extra)) in sheets.iter().enumerate() {
+            zip.start_file(format!("xl/worksheets/sheet{}.xml", i + 1), options)
+                .unwrap();
+            zip.write_all(
+                format!(
+                    r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheetData>{}</sheetData>{}</worksheet>"#,
+                    sheet_data, extra
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        }
+
+        for (name, content) in extra_parts {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    /// Inline-string cell xml
+    pub(crate) fn cell(reference: &str, value: &str) -> String {
+        format!(
+            r#"<c r="{}" t="inlineStr"><is><t>{}</t></is></c>"#,
+            reference, value
+        )
+    }
+
+    #[test]
+    fn test_parse_annotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("annotated.xlsx");
+
+        let sheet_data = format!(
+            r#"<row r="1">{}{}</row><row r="3">{}{}</row>"#,
+            cell("A1", "Name"),
+            cell("B1", "Status"),
+            cell("A3", "John"),
+            cell("B3", "Open"),
+        );
+        let extra = r#"<dataValidations count="2"><dataValidation type="list" sqref="B2:B100"><formula1>Lists!$A$1:$A$2</formula1></dataValidation><dataValidation type="list" sqref="A:A"><formula1>Lists!$A:$A</formula1></dataValidation></dataValidations><hyperlinks><hyperlink ref="A3" r:id="rId2"/></hyperlinks>"#;
+        let lists = format!(r#"<row r="1">{}</row><row r="2">{}</row>"#, cell("A1", "Open"), cell("A2", "Closed"));
+
+        write_xlsx(
+            &path,
+            &[("Data", &sheet_data, extra), ("Lists", &lists, "")],
+            &[
+                (
+                    "xl/worksheets/_rels/sheet1.xml.rels",
+                    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments" Target="../comments1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com/john" TargetMode="External"/></Relationships>"#,
+                ),
+                (
+                    "xl/comments1.xml",
+                    r#"<comments><authors><author>Alice</author></authors><commentList><comment ref="A3" authorId="0"><text><t>Alice: Team lead</t></text></comment></commentList></comments>"#,
+                ),
+            ],
+        );
+
+        let sheet = ExcelParser::new().parse_sheet(&path, "Data").unwrap();
+        assert_eq!(sheet.data.len(), 2);
+
+        let john = sheet.annotation(1, 0).unwrap();
+        assert_eq!(john.reference, "A3");
+        assert_eq!(john.comments[0].author.as_deref(), Some("Alice"));
+        assert_eq!(john.comments[0].text, "Team lead");
+        assert_eq!(
+            john.hyperlink.as_ref().unwrap().target.as_deref(),
+            Some("https://example.com/john")
+        );
+
+        let status = sheet.validation(1, 1).unwrap();
+        assert_eq!(status.list_values, vec!["Open", "Closed"]);
+        assert!(sheet.validation(0, 1).is_none());
+
+        // Whole-column rule and source
+        let names = sheet.validation(1, 0).unwrap();
+        assert_eq!(names.list_values, vec!["Open", "Closed"]);
+        assert!(sheet.validation(0, 0).is_some());
+
+        let index = sheet.annotation_index();
+        assert_eq!(index.get(&(1, 0)).map(|a| a.reference.as_str()), Some("A3"));
+        assert!(!index.contains_key(&(0, 1)));
+    }
+
+    #[test]
+    fn test_parse_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tables.xlsx");
+
+        // A title above the table and a note beside it must not leak into the table
+        let sheet_data = format!(
+            r#"<row r="1">{}</row><row r="2">{}{}{}</row><row r="3">{}{}<c r="D3"><v>10</v></c></row><row r="4">{}{}<c r="D4"><v>5</v></c></row><row r="5">{}<c r="D5"><v>15</v></c></row>"#,
+            cell("A1", "Quarterly report"),
+            cell("B2", "Region header"),
+            cell("C2", "Product"),
+            cell("D2", "Amount"),
+            cell("B3", "North"),
+            cell("C3", "Tea"),
+            cell("B4", "South"),
+            cell("C4", "Coffee"),
+            cell("B5", "Total"),
+        );
+
+        write_xlsx(
+            &path,
+            &[("Sales", &sheet_data, r#"<tableParts count="1"><tablePart r:id="rId1"/></tableParts>"#)],
+            &[
+                (
+                    "xl/worksheets/_rels/sheet1.xml.rels",
+                    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/table" Target="../tables/table1.xml"/></Relationships>"#,
+                ),
+                (
+                    "xl/tables/table1.xml",
+                    r#"<table xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" id="1" name="tblSales" displayName="tblSales" ref="B2:D5" totalsRowCount="1"><tableColumns count="3"><tableColumn id="1" name="Region"/><tableColumn id="2" name="Product"/><tableColumn id="3" name="Amount"/></tableColumns></table>"#,
+                ),
+            ],
+        );
+
+        let parser = ExcelParser::new();
+        let tables = parser.list_tables(&path).unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].sheet, "Sales");
+
+        let table = parser.parse_table(&path, "tblSales").unwrap();
+        assert_eq!(table.info.columns, vec!["Region", "Product", "Amount"]);
+        assert_eq!(table.rows, vec![vec!["North", "Tea", "10"], vec!["South", "Coffee", "5"]]);
+        assert_eq!(table.totals, Some(vec!["Total".to_string(), String::new(), "15".to_string()]));
+
+        let sheet = table.to_sheet();
+        assert_eq!(sheet.name, "tblSales");
+        assert_eq!(sheet.data[0], vec!["Region", "Product", "Amount"]);
+        assert_eq!(sheet.data.len(), 4);
+
+        assert!(matches!(
+            parser.parse_table(&path, "tblMissing"),
+            Err(ExcelParserError::TableNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_list_and_extract_media() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("media.xlsx");
+
+        let sheet_data = format!(r#"<row r="1">{}</row>"#, cell("A1", "Report"));
+        write_xlsx(
+            &path,
+            &[("Report", &sheet_data, r#"<drawing r:id="rId1"/>"#)],
+            &[
+                (
+                    "xl/worksheets/_rels/sheet1.xml.rels",
+                    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing" Target="../drawings/drawing1.xml"/></Relationships>"#,
+                ),
+                (
+                    "xl/drawings/drawing1.xml",
+                    r#"<xdr:wsDr xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart"><xdr:oneCellAnchor><xdr:from><xdr:col>1</xdr:col><xdr:row>2</xdr:row></xdr:from><xdr:pic><xdr:nvPicPr><xdr:cNvPr id="2" name="Logo" descr="Company logo"/></xdr:nvPicPr><xdr:blipFill><a:blip r:embed="rId1"/></xdr:blipFill></xdr:pic></xdr:oneCellAnchor><xdr:twoCellAnchor><xdr:from><xdr:col>4</xdr:col><xdr:row>0</xdr:row></xdr:from><xdr:graphicFrame><a:graphic><a:graphicData><c:chart r:id="rId2"/></a:graphicData></a:graphic></xdr:graphicFrame></xdr:twoCellAnchor></xdr:wsDr>"#,
+                ),
+                (
+                    "xl/drawings/_rels/drawing1.xml.rels",
+                    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.png"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart" Target="../charts/chart1.xml"/></Relationships>"#,
+                ),
+                (
+                    "xl/charts/chart1.xml",
+                    r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart><c:plotArea><c:pieChart><c:ser><c:tx><c:v>Share</c:v></c:tx><c:val><c:numRef><c:f>Report!$B$2:$B$4</c:f></c:numRef></c:val></c:ser></c:pieChart></c:plotArea></c:chart></c:chartSpace>"#,
+                ),
+                ("xl/media/image1.png", "not really a png"),
+                ("xl/media/image2.jpeg", "unplaced"),
+            ],
+        );
+
+        let parser = ExcelParser::new();
+        let media = parser.list_media(&path).unwrap();
+        assert_eq!(media.images.len(), 2);
+        assert_eq!(media.images[0].sheet.as_deref(), Some("Report"));
+        assert_eq!(media.images[0].anchor.as_deref(), Some("B3"));
+        assert_eq!(media.images[0].description.as_deref(), Some("Company logo"));
+        assert_eq!(media.images[1].part, "xl/media/image2.jpeg");
+        assert!(media.images[1].sheet.is_none());
+
+        assert_eq!(media.charts.len(), 1);
+        assert_eq!(media.charts[0].anchor.as_deref(), Some("E1"));
+        assert_eq!(media.charts[0].chart_type.as_deref(), Some("pie"));
+        assert_eq!(media.charts[0].series[0].values_ref.as_deref(), Some("Report!$B$2:$B$4"));
+
+        let out_dir = dir.path().join("images");
+        let (_, written) = parser.extract_media(&path, &out_dir).unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(std::fs::read_to_string(out_dir.join("image1.png")).unwrap(), "not really a png");
+    }
 }
@@ -0,0 +1,334 @@
+use super::source::{WorkbookReader, WorkbookSource};
+use crate::error::Result;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::io::Read;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+/// Direct access to the XML parts of an xlsx/xlsm package.
+///
+/// calamine only exposes cell values, so everything else (comments,
+/// hyperlinks, validations, ...) is read from the package here.
+pub struct XlsxPackage {
+    archive: ZipArchive<WorkbookReader>,
+    sheets: Vec<(String, String)>,
+}
+
+/// A single entry of a `_rels/*.rels` part
+#[derive(Debug, Clone)]
+pub struct Relationship {
+    pub id: String,
+    pub rel_type: String,
+    /// Absolute part name for internal targets, the raw URL for external ones
+    pub target: String,
+    pub external: bool,
+}
+
+impl Relationship {
+    /// Check the relationship type by its last path segment (e.g. "comments")
+    pub fn is_type(&self, kind: &str) -> bool {
+        self.rel_type.rsplit('/').next() == Some(kind)
+    }
+}
+
+impl XlsxPackage {
+    /// Open the package, returning `None` for formats that are not zip based (.xls, .xlsb)
+    pub fn open(source: &WorkbookSource) -> Result<Option<Self>> {
+        if !source.is_xml_package() {
+            return Ok(None);
+        }
+
+        let archive = match ZipArchive::new(source.reader()?) {
+            Ok(archive) => archive,
+            // Not a zip container; calamine reports the real error
+            Err(ZipError::InvalidArchive(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut package = Self {
+            archive,
+            sheets: Vec::new(),
+        };
+        package.sheets = package.load_sheet_parts()?;
+        Ok(Some(package))
+    }
+
+    /// Read a part as UTF-8 text, `None` if the part does not exist
+    pub fn read_part(&mut self, part_name: &str) -> Result<Option<String>> {
+        let bytes = match self.read_part_bytes(part_name)? {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+
+    /// Read a part as raw bytes, `None` if the part does not exist
+    pub fn read_part_bytes(&mut self, part_name: &str) -> Result<Option<Vec<u8>>> {
+        let part_name = part_name.trim_start_matches('/');
+        let mut file = match self.archive.by_name(part_name) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        Ok(Some(content))
+    }
+
+    /// Uncompressed size of a part, `None` if the part does not exist
+    pub fn part_size(&mut self, part_name: &str) -> Option<u64> {
+        self.archive
+            .by_name(part_name.trim_start_matches('/'))
+            .ok()
+            .map(|file| file.size())
+    }
+
+    /// Names of all parts in the package
+    pub fn part_names(&self) -> Vec<String> {
+        self.archive.file_names().map(|name| name.to_string()).collect()
+    }
+
+    /// Worksheet names paired with their part names, in workbook order
+    pub fn sheets(&self) -> &[(String, String)] {
+        &self.sheets
+    }
+
+    /// Part name of the worksheet with the given name
+    pub fn sheet_part(&self, sheet_name: &str) -> Option<&str> {
+        self.sheets
+            .iter()
+            .find(|(name, _)| name == sheet_name)
+            .map(|(_, part)| part.as_str())
+    }
+
+    /// Relationships declared by a part (read from its `_rels/<name>.rels` sibling)
+    pub fn relationships(&mut self, part_name: &str) -> Result<Vec<Relationship>> {
+        let part_name = part_name.trim_start_matches('/');
+        let (dir, file_name) = match part_name.rfind('/') {
+            Some(pos) => (&part_name[..pos], &part_name[pos + 1..]),
+            None => ("", part_name),
+        };
+        let rels_name = if dir.is_empty() {
+            format!("_rels/{}.rels", file_name)
+        } else {
+            format!("{}/_rels/{}.rels", dir, file_name)
+        };
+
+        let content = match self.read_part(&rels_name)? {
+            Some(content) => content,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut relationships = Vec::new();
+        let mut reader = Reader::from_str(&content);
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(ref e) | Event::Empty(ref e)
+                    if e.local_name().as_ref() == b"Relationship" =>
+                {
+                    let external = attr(e, "TargetMode").as_deref() == Some("External");
+                    let raw_target = attr(e, "Target").unwrap_or_default();
+                    let target = if external {
+                        raw_target
+                    } else {
+                        resolve_target(part_name, &raw_target)
+                    };
+                    relationships.push(Relationship {
+                        id: attr(e, "Id").unwrap_or_default(),
+                        rel_type: attr(e, "Type").unwrap_or_default(),
+                        target,
+                        external,
+                    });
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(relationships)
+    }
+
+    fn load_sheet_parts(&mut self) -> Result<Vec<(String, String)>> {
+        let workbook = match self.read_part("xl/workbook.xml")? {
+            Some(content) => content,
+            None => return Ok(Vec::new()),
+        };
+        let relationships = self.relationships("xl/workbook.xml")?;
+
+        let mut sheets = Vec::new();
+        let mut reader = Reader::from_str(&workbook);
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(ref e) | Event::Empty(ref e) if e.local_name().as_ref() == b"sheet" => {
+                    let name = attr(e, "name").unwrap_or_default();
+                    let rel_id = attr(e, "id").unwrap_or_default();
+                    if let Some(rel) = relationships.iter().find(|rel| rel.id == rel_id) {
+                        sheets.push((name, rel.target.clone()));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(sheets)
+    }
+}
+
+/// Get an attribute value by its local name (namespace prefix ignored)
+pub fn attr(element: &BytesStart, name: &str) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name.as_bytes())
+        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+/// Resolve a relationship target relative to the part that declares it
+pub fn resolve_target(base_part: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+
+    let mut segments: Vec<&str> = base_part.split('/').collect();
+    segments.pop();
+    for segment in target.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                segments.pop();
+            }
+            other => segments.push(other),
+        }
+    }
+    segments.join("/")
+}
+
+/// Zero-based index of the last worksheet row (1,048,576 rows)
+pub const MAX_ROW: u32 = 1_048_575;
+/// Zero-based index of the last worksheet column (XFD)
+pub const MAX_COLUMN: u32 = 16_383;
+
+/// Parse column letters ("B", "XFD") into a zero-based index
+fn parse_column(letters: &str) -> Option<u32> {
+    if letters.is_empty() || letters.len() > 3 || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let column = letters
+        .bytes()
+        .fold(0, |column, c| column * 26 + u32::from(c.to_ascii_uppercase() - b'A' + 1));
+    (column - 1 <= MAX_COLUMN).then(|| column - 1)
+}
+
+/// Parse a one-based row number into a zero-based index
+fn parse_row(digits: &str) -> Option<u32> {
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let row: u32 = digits.parse().ok()?;
+    (1..=MAX_ROW + 1).contains(&row).then(|| row - 1)
+}
+
+/// Parse an A1-style reference ("B3", "$B$3") into zero-based (row, column)
+pub fn parse_cell_ref(reference: &str) -> Option<(u32, u32)> {
+    let reference = reference.replace('$', "");
+    let split = reference.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = reference.split_at(split);
+    Some((parse_row(digits)?, parse_column(letters)?))
+}
+
+/// Parse an A1-style range ("A1:C5", whole columns "B:D", whole rows "3:5" or a single cell)
+/// into inclusive zero-based corners
+pub fn parse_range_ref(reference: &str) -> Option<((u32, u32), (u32, u32))> {
+    let Some((start, end)) = reference.split_once(':') else {
+        let cell = parse_cell_ref(reference)?;
+        return Some((cell, cell));
+    };
+    let (start, end) = match (parse_cell_ref(start), parse_cell_ref(end)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => {
+            let (start, end) = (start.replace('$', ""), end.replace('$', ""));
+            match (parse_column(&start), parse_column(&end)) {
+                (Some(first), Some(last)) => ((0, first), (MAX_ROW, last)),
+                _ => ((parse_row(&start)?, 0), (parse_row(&end)?, MAX_COLUMN)),
+            }
+        }
+    };
+    Some((
+        (start.0.min(end.0), start.1.min(end.1)),
+        (start.0.max(end.0), start.1.max(end.1)),
+    ))
+}
+
+/// Convert a zero-based column index into its letter name ("A", "AB", ...)
+pub fn column_name(mut column: u32) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (column % 26) as u8);
+        if column < 26 {
+            break;
+        }
+        column = column / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Format zero-based (row, column) as an A1-style reference
+pub fn cell_ref(row: u32, column: u32) -> String {
+    format!("{}{}", column_name(column), row + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_references() {
+        assert_eq!(parse_cell_ref("A1"), Some((0, 0)));
+        assert_eq!(parse_cell_ref("$AB$12"), Some((11, 27)));
+        assert_eq!(parse_cell_ref("A0"), None);
+        assert_eq!(parse_cell_ref("12"), None);
+        assert_eq!(parse_cell_ref("XFD1048576"), Some((MAX_ROW, MAX_COLUMN)));
+        assert_eq!(parse_cell_ref("XFE1"), None);
+        assert_eq!(parse_cell_ref("ABCDEFGH1"), None);
+        assert_eq!(parse_cell_ref("A99999999999"), None);
+        assert_eq!(cell_ref(11, 27), "AB12");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+    }
+
+    #[test]
+    fn test_range_references() {
+        assert_eq!(parse_range_ref("C5:A1"), Some(((0, 0), (4, 2))));
+        assert_eq!(parse_range_ref("B2"), Some(((1, 1), (1, 1))));
+        assert_eq!(parse_range_ref("$B:$B"), Some(((0, 1), (MAX_ROW, 1))));
+        assert_eq!(parse_range_ref("D:B"), Some(((0, 1), (MAX_ROW, 3))));
+        assert_eq!(parse_range_ref("3:5"), Some(((2, 0), (4, MAX_COLUMN))));
+        assert_eq!(parse_range_ref("B:3"), None);
+        assert_eq!(parse_range_ref("A1:B"), None);
+    }
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(
+            resolve_target("xl/workbook.xml", "worksheets/sheet1.xml"),
+            "xl/worksheets/sheet1.xml"
+        );
+        assert_eq!(
+            resolve_target("xl/worksheets/sheet1.xml", "../comments1.xml"),
+            "xl/comments1.xml"
+        );
+        assert_eq!(
+            resolve_target("xl/workbook.xml", "/xl/worksheets/sheet2.xml"),
+            "xl/worksheets/sheet2.xml"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use super::crypto;
+use crate::error::Result;
+use calamine::{open_workbook_from_rs, Sheets, Xls, Xlsb, Xlsx};
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// An opened workbook file, decrypted in memory when it is password protected
+pub struct WorkbookSource {
+    path: PathBuf,
+    extension: String,
+    decrypted: Option<Arc<[u8]>>,
+}
+
+/// Reader over either the workbook file or its decrypted package
+pub enum WorkbookReader {
+    File(BufReader<File>),
+    Memory(Cursor<Arc<[u8]>>),
+}
+
+impl Read for WorkbookReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::File(reader) => reader.read(buf),
+            Self::Memory(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl Seek for WorkbookReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            Self::File(reader) => reader.seek(pos),
+            Self::Memory(reader) => reader.seek(pos),
+        }
+    }
+}
+
+impl WorkbookSource {
+    /// Open a workbook, decrypting OLE-wrapped OOXML packages with the given password
+    pub fn open<P: AsRef<Path>>(file_path: P, password: Option<&str>) -> Result<Self> {
+        let path = file_path.as_ref().to_path_buf();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        let decrypted = if matches!(extension.as_str(), "xlsx" | "xlsm" | "xlsb")
+            && crypto::is_encrypted(&path)?
+        {
+            Some(Arc::from(crypto::decrypt_file(&path, password)?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            extension,
+            decrypted,
+        })
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.decrypted.is_some()
+    }
+
+    /// Whether the workbook is a zip package with XML parts (xlsx/xlsm)
+    pub fn is_xml_package(&self) -> bool {
+        matches!(self.extension.as_str(), "xlsx" | "xlsm")
+    }
+
+    /// Open a fresh reader positioned at the start of the (decrypted) workbook
+    pub fn reader(&self) -> Result<WorkbookReader> {
+        Ok(match &self.decrypted {
+            Some(bytes) => WorkbookReader::Memory(Cursor::new(bytes.clone())),
+            None => WorkbookReader::File(BufReader::new(File::open(&self.path)?)),
+        })
+    }
+
+    /// Open the workbook with calamine
+    pub fn workbook(&self) -> Result<Sheets<WorkbookReader>> {
+        let reader = self.reader()?;
+        Ok(match self.extension.as_str() {
+            "xls" => Sheets::Xls(
+                open_workbook_from_rs::<Xls<_>, _>(reader).map_err(calamine::Error::Xls)?,
+            ),
+            "xlsb" => Sheets::Xlsb(
+                open_workbook_from_rs::<Xlsb<_>, _>(reader).map_err(calamine::Error::Xlsb)?,
+            ),
+            _ => Sheets::Xlsx(
+                open_workbook_from_rs::<Xlsx<_>, _>(reader).map_err(calamine::Error::Xlsx)?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use super::package::{attr, XlsxPackage};
+use super::Sheet;
+use crate::error::Result;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+/// An Excel table (ListObject) declared in `xl/tables/*.xml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableInfo {
+    pub name: String,
+    pub display_name: String,
+    /// Worksheet containing the table
+    pub sheet: String,
+    /// A1-style range covering header, body and totals rows
+    pub range: String,
+    /// Column names as declared by the table, not as read from the cells
+    pub columns: Vec<String>,
+    pub has_header_row: bool,
+    pub has_totals_row: bool,
+}
+
+impl TableInfo {
+    /// Table names are case-insensitive in Excel
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) || self.display_name.eq_ignore_ascii_case(name)
+    }
+}
+
+/// Cell values of a single Excel table
+#[derive(Debug, Clone)]
+pub struct ExcelTable {
+    pub info: TableInfo,
+    /// Body rows, without header and totals rows
+    pub rows: Vec<Vec<String>>,
+    pub totals: Option<Vec<String>>,
+}
+
+impl ExcelTable {
+    /// Convert to a sheet (column names, body, totals) so it can go through any output writer
+    pub fn to_sheet(&self) -> Sheet {
+        let mut data = Vec::with_capacity(self.rows.len() + 2);
+        data.push(self.info.columns.clone());
+        data.extend(self.rows.iter().cloned());
+        if let Some(totals) = &self.totals {
+            data.push(totals.clone());
+        }
+        Sheet::new(self.info.display_name.clone(), data)
+    }
+}
+
+/// Read the definitions of all tables in the workbook, in sheet order
+pub fn read_tables(package: &mut XlsxPackage) -> Result<Vec<TableInfo>> {
+    let mut tables = Vec::new();
+    let sheets = package.sheets().to_vec();
+
+    for (sheet_name, sheet_part) in sheets {
+        let relationships = package.relationships(&sheet_part)?;
+        for rel in relationships.iter().filter(|rel| rel.is_type("table")) {
+            if let Some(content) = package.read_part(&rel.target)? {
+                if let Some(table) = parse_table_xml(&content, &sheet_name)? {
+                    tables.push(table);
+                }
+            }
+        }
+    }
+
+    Ok(tables)
+}
+
+fn parse_table_xml(content: &str, sheet_name: &str) -> Result<Option<TableInfo>> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut table: Option<TableInfo> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) | Event::Empty(ref e) => match e.local_name().as_ref() {
+                b"table" => {
+                    let name = attr(e, "name").unwrap_or_default();
+                    table = Some(TableInfo {
+                        display_name: attr(e, "displayName").unwrap_or_else(|| name.clone()),
+                        name,
+                        sheet: sheet_name.to_string(),
+                        range: attr(e, "ref").unwrap_or_default(),
+                        columns: Vec::new(),
+                        has_header_row: attr(e, "headerRowCount").as_deref() != Some("0"),
+                        has_totals_row: attr(e, "totalsRowCount")
+                            .and_then(|count| count.parse::<u32>().ok())
+                            .is_some_and(|count| count > 0),
+                    });
+                }
+                b"tableColumn" => {
+                    if let Some(table) = table.as_mut() {
+                        table.columns.push(attr(e, "name").unwrap_or_default());
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_table_xml() {
+        let xml = r#"<table xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" id="1" name="Table1" displayName="tblSales" ref="B2:D6" totalsRowCount="1">
+            <autoFilter ref="B2:D5"/>
+            <tableColumns count="3"><tableColumn id="1" name="Region" totalsRowLabel="Total"/><tableColumn id="2" name="Product"/><tableColumn id="3" name="Amount" totalsRowFunction="sum"/></tableColumns>
+            </table>"#;
+
+        let table = parse_table_xml(xml, "Sales").unwrap().unwrap();
+        assert_eq!(table.display_name, "tblSales");
+        assert_eq!(table.sheet, "Sales");
+        assert_eq!(table.range, "B2:D6");
+        assert_eq!(table.columns, vec!["Region", "Product", "Amount"]);
+        assert!(table.has_header_row);
+        assert!(table.has_totals_row);
+        assert!(table.matches("TBLSALES"));
+    }
+}
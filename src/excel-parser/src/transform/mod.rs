@@ -0,0 +1,367 @@
+use crate::error::{ExcelParserError, Result};
+use crate::parser::{ExcelData, Sheet};
+use std::collections::HashMap;
+
+/// Reshaping applied to sheets before any `OutputWriter` runs.
+///
+/// Both transformations treat the first row of a sheet as its header.
+#[derive(Debug, Clone)]
+pub enum Transform {
+    Unpivot(Unpivot),
+    Pivot(Pivot),
+}
+
+/// Wide to long: one output row per (id columns, value column) pair
+#[derive(Debug, Clone)]
+pub struct Unpivot {
+    pub id_columns: Vec<String>,
+    /// Columns to melt; all non-id columns when empty
+    pub value_columns: Vec<String>,
+    pub var_name: String,
+    pub value_name: String,
+}
+
+/// Long to wide: the inverse of `Unpivot`
+#[derive(Debug, Clone)]
+pub struct Pivot {
+    /// Columns identifying an output row; all columns except the two below when empty
+    pub id_columns: Vec<String>,
+    /// Column whose values become the new column headers
+    pub var_name: String,
+    /// Column whose values fill the new columns
+    pub value_name: String,
+}
+
+pub const DEFAULT_VAR_NAME: &str = "variable";
+pub const DEFAULT_VALUE_NAME: &str = "value";
+
+impl Transform {
+    /// Parse a CLI spec such as `id_cols=Region,Product;value_cols=Jan,Feb`
+    pub fn unpivot_from_spec(spec: &str, var_name: &str, value_name: &str) -> Result<Self> {
+        let mut unpivot = Unpivot {
+            id_columns: Vec::new(),
+            value_columns: Vec::new(),
+            var_name: var_name.to_string(),
+            value_name: value_name.to_string(),
+        };
+        for (key, columns) in parse_spec(spec)? {
+            match key.as_str() {
+                "id_cols" => unpivot.id_columns = columns,
+                "value_cols" => unpivot.value_columns = columns,
+                _ => return Err(transform_error(format!("Unknown unpivot option: {}", key))),
+            }
+        }
+        Ok(Self::Unpivot(unpivot))
+    }
+
+    /// Parse a CLI spec such as `id_cols=Region,Product`
+    pub fn pivot_from_spec(spec: &str, var_name: &str, value_name: &str) -> Result<Self> {
+        let mut pivot = Pivot {
+            id_columns: Vec::new(),
+            var_name: var_name.to_string(),
+            value_name: value_name.to_string(),
+        };
+        for (key, columns) in parse_spec(spec)? {
+            match key.as_str() {
+                "id_cols" => pivot.id_columns = columns,
+                _ => return Err(transform_error(format!("Unknown pivot option: {}", key))),
+            }
+        }
+        Ok(Self::Pivot(pivot))
+    }
+
+    /// Apply the transformation to every sheet that has the columns it names.
+    ///
+    /// Sheets missing any of them are kept unchanged and their names returned, so
+    /// that a spec written for one sheet does not fail a run over all sheets. It is
+    /// an error if no sheet has the columns.
+    pub fn apply_all(&self, data: &ExcelData) -> Result<(ExcelData, Vec<String>)> {
+        let mut sheets = Vec::with_capacity(data.sheets.len());
+        let mut skipped = Vec::new();
+        for sheet in &data.sheets {
+            if self.applies_to(sheet) {
+                sheets.push(self.apply(sheet)?);
+            } else {
+                skipped.push(sheet.name.clone());
+                sheets.push(sheet.clone());
+            }
+        }
+
+        if !skipped.is_empty() && skipped.len() == data.sheets.len() {
+            let columns: Vec<&str> = self.columns().into_iter().map(String::as_str).collect();
+            return Err(transform_error(format!(
+                "Columns not found in any sheet: {}",
+                columns.join(", ")
+            )));
+        }
+        Ok((ExcelData { sheets }, skipped))
+    }
+
+    /// Whether the header of the sheet has every column the transformation names
+    pub fn applies_to(&self, sheet: &Sheet) -> bool {
+        let Some(header) = sheet.data.first() else {
+            return true;
+        };
+        self.columns()
+            .iter()
+            .all(|name| header.iter().any(|h| h.trim() == name.trim()))
+    }
+
+    /// Columns the transformation looks up by name
+    fn columns(&self) -> Vec<&String> {
+        match self {
+            Transform::Unpivot(unpivot) => unpivot
+                .id_columns
+                .iter()
+                .chain(&unpivot.value_columns)
+                .collect(),
+            Transform::Pivot(pivot) => pivot
+                .id_columns
+                .iter()
+                .chain([&pivot.var_name, &pivot.value_name])
+                .collect(),
+        }
+    }
+
+    /// Apply the transformation to a single sheet.
+    ///
+    /// Cell annotations are dropped since their coordinates no longer apply.
+    pub fn apply(&self, sheet: &Sheet) -> Result<Sheet> {
+        match self {
+            Transform::Unpivot(unpivot) => unpivot.apply(sheet),
+            Transform::Pivot(pivot) => pivot.apply(sheet),
+        }
+    }
+}
+
+impl Unpivot {
+    pub fn apply(&self, sheet: &Sheet) -> Result<Sheet> {
+        let Some((header, rows)) = sheet.data.split_first() else {
+            return Ok(Sheet::new(sheet.name.clone(), Vec::new()));
+        };
+
+        let id_indices = column_indices(header, &self.id_columns, &sheet.name)?;
+        let value_indices = if self.value_columns.is_empty() {
+            (0..header.len()).filter(|i| !id_indices.contains(i)).collect()
+        } else {
+            column_indices(header, &self.value_columns, &sheet.name)?
+        };
+
+        let mut output_header: Vec<String> = id_indices.iter().map(|&i| header[i].clone()).collect();
+        output_header.push(self.var_name.clone());
+        output_header.push(self.value_name.clone());
+
+        let mut data = Vec::with_capacity(rows.len() * value_indices.len() + 1);
+        data.push(output_header);
+
+        for row in rows {
+            let ids: Vec<String> = id_indices.iter().map(|&i| cell(row, i)).collect();
+            for &value_index in &value_indices {
+                let mut record = ids.clone();
+                record.push(header[value_index].clone());
+                record.push(cell(row, value_index));
+                data.push(record);
+            }
+        }
+
+        Ok(Sheet::new(sheet.name.clone(), data))
+    }
+}
+
+impl Pivot {
+    pub fn apply(&self, sheet: &Sheet) -> Result<Sheet> {
+        let Some((header, rows)) = sheet.data.split_first() else {
+            return Ok(Sheet::new(sheet.name.clone(), Vec::new()));
+        };
+
+        let var_index = column_indices(header, std::slice::from_ref(&self.var_name), &sheet.name)?[0];
+        let value_index =
+            column_indices(header, std::slice::from_ref(&self.value_name), &sheet.name)?[0];
+        let id_indices = if self.id_columns.is_empty() {
+            (0..header.len())
+                .filter(|&i| i != var_index && i != value_index)
+                .collect()
+        } else {
+            column_indices(header, &self.id_columns, &sheet.name)?
+        };
+
+        // New columns and output rows both keep their order of first appearance
+        let mut new_columns: Vec<String> = Vec::new();
+        let mut column_positions: HashMap<String, usize> = HashMap::new();
+        let mut keys: Vec<Vec<String>> = Vec::new();
+        let mut key_positions: HashMap<Vec<String>, usize> = HashMap::new();
+        let mut cells: HashMap<(usize, usize), String> = HashMap::new();
+
+        for row in rows {
+            let key: Vec<String> = id_indices.iter().map(|&i| cell(row, i)).collect();
+            let variable = cell(row, var_index);
+
+            let column = *column_positions.entry(variable.clone()).or_insert_with(|| {
+                new_columns.push(variable.clone());
+                new_columns.len() - 1
+            });
+            let row_position = *key_positions.entry(key.clone()).or_insert_with(|| {
+                keys.push(key.clone());
+                keys.len() - 1
+            });
+
+            if cells.insert((row_position, column), cell(row, value_index)).is_some() {
+                return Err(transform_error(format!(
+                    "Duplicate entry for ({}) and {} = {} in sheet {}",
+                    key.join(", "),
+                    self.var_name,
+                    variable,
+                    sheet.name
+                )));
+            }
+        }
+
+        let mut output_header: Vec<String> = id_indices.iter().map(|&i| header[i].clone()).collect();
+        output_header.extend(new_columns.iter().cloned());
+
+        let mut data = Vec::with_capacity(keys.len() + 1);
+        data.push(output_header);
+        for (row_position, key) in keys.into_iter().enumerate() {
+            let mut record = key;
+            for column in 0..new_columns.len() {
+                record.push(cells.remove(&(row_position, column)).unwrap_or_default());
+            }
+            data.push(record);
+        }
+
+        Ok(Sheet::new(sheet.name.clone(), data))
+    }
+}
+
+fn cell(row: &[String], index: usize) -> String {
+    row.get(index).cloned().unwrap_or_default()
+}
+
+fn column_indices(header: &[String], names: &[String], sheet_name: &str) -> Result<Vec<usize>> {
+    names
+        .iter()
+        .map(|name| {
+            header
+                .iter()
+                .position(|h| h.trim() == name.trim())
+                .ok_or_else(|| {
+                    transform_error(format!("Column not found in sheet {}: {}", sheet_name, name))
+                })
+        })
+        .collect()
+}
+
+/// Split `key=a,b;key2=c` into keys with their column lists
+fn parse_spec(spec: &str) -> Result<Vec<(String, Vec<String>)>> {
+    spec.split(';')
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            let (key, columns) = part
+                .split_once('=')
+                .ok_or_else(|| transform_error(format!("Expected key=columns, got: {}", part)))?;
+            let columns = columns
+                .split(',')
+                .map(|column| column.trim().to_string())
+                .filter(|column| !column.is_empty())
+                .collect();
+            Ok((key.trim().to_lowercase(), columns))
+        })
+        .collect()
+}
+
+fn transform_error(msg: String) -> ExcelParserError {
+    ExcelParserError::Transform(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(data: &[&[&str]]) -> Vec<Vec<String>> {
+        data.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    fn wide_sheet() -> Sheet {
+        Sheet::new(
+            "Finance",
+            rows(&[
+                &["Region", "Product", "Jan", "Feb"],
+                &["North", "Tea", "10", "12"],
+                &["South", "Coffee", "7", ""],
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_unpivot() {
+        let transform = Transform::unpivot_from_spec("id_cols=Region,Product", "Month", "Amount").unwrap();
+        let long = transform.apply(&wide_sheet()).unwrap();
+
+        assert_eq!(
+            long.data,
+            rows(&[
+                &["Region", "Product", "Month", "Amount"],
+                &["North", "Tea", "Jan", "10"],
+                &["North", "Tea", "Feb", "12"],
+                &["South", "Coffee", "Jan", "7"],
+                &["South", "Coffee", "Feb", ""],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_unpivot_selected_columns() {
+        let transform =
+            Transform::unpivot_from_spec("id_cols=Region;value_cols=Feb", DEFAULT_VAR_NAME, DEFAULT_VALUE_NAME)
+                .unwrap();
+        let long = transform.apply(&wide_sheet()).unwrap();
+
+        assert_eq!(long.data[0], vec!["Region", "variable", "value"]);
+        assert_eq!(long.data.len(), 3);
+        assert_eq!(long.data[1], vec!["North", "Feb", "12"]);
+    }
+
+    #[test]
+    fn test_pivot_round_trip() {
+        let unpivot = Transform::unpivot_from_spec("id_cols=Region,Product", "Month", "Amount").unwrap();
+        let pivot = Transform::pivot_from_spec("id_cols=Region,Product", "Month", "Amount").unwrap();
+
+        let wide = pivot.apply(&unpivot.apply(&wide_sheet()).unwrap()).unwrap();
+        assert_eq!(wide.data, wide_sheet().data);
+    }
+
+    #[test]
+    fn test_pivot_duplicates_and_missing_columns() {
+        let sheet = Sheet::new(
+            "Long",
+            rows(&[&["Region", "Month", "Amount"], &["North", "Jan", "1"], &["North", "Jan", "2"]]),
+        );
+        let pivot = Transform::pivot_from_spec("", "Month", "Amount").unwrap();
+        assert!(matches!(pivot.apply(&sheet), Err(ExcelParserError::Transform(_))));
+
+        let unpivot = Transform::unpivot_from_spec("id_cols=Country", "Month", "Amount").unwrap();
+        assert!(matches!(unpivot.apply(&wide_sheet()), Err(ExcelParserError::Transform(_))));
+
+        assert!(Transform::unpivot_from_spec("Region", "Month", "Amount").is_err());
+    }
+
+    #[test]
+    fn test_apply_all_skips_sheets_without_columns() {
+        let notes = Sheet::new("Notes", rows(&[&["Note"], &["Draft figures"]]));
+        let data = ExcelData {
+            sheets: vec![notes.clone(), wide_sheet()],
+        };
+
+        let transform = Transform::unpivot_from_spec("id_cols=Region,Product", "Month", "Amount").unwrap();
+        let (reshaped, skipped) = transform.apply_all(&data).unwrap();
+        assert_eq!(skipped, vec!["Notes"]);
+        assert_eq!(reshaped.sheets[0].data, notes.data);
+        assert_eq!(reshaped.sheets[1].data[0], vec!["Region", "Product", "Month", "Amount"]);
+        assert_eq!(reshaped.sheets[1].data.len(), 5);
+
+        let transform = Transform::unpivot_from_spec("id_cols=Country", "Month", "Amount").unwrap();
+        assert!(matches!(transform.apply_all(&data), Err(ExcelParserError::Transform(_))));
+    }
+}
@@ -31,13 +31,13 @@ impl OutputWriter for CsvOutput {
                 .from_writer(writer);
 
             if self.has_headers {
-                csv_writer.write_record(&["Page", "Content"])?;
+                csv_writer.write_record(["Page", "Content"])?;
             }
 
             for page in &data.pages {
                 // Clean up text for CSV (remove newlines, etc.)
                 let cleaned_text = page.text.replace('\n', " ").replace('\r', "");
-                csv_writer.write_record(&[&page.number.to_string(), &cleaned_text])?;
+                csv_writer.write_record([&page.number.to_string(), &cleaned_text])?;
             }
         } else {
             // Output all tables
@@ -56,11 +56,11 @@ impl OutputWriter for CsvOutput {
                 .from_writer(writer);
 
             if self.has_headers {
-                csv_writer.write_record(&["Content"])?;
+                csv_writer.write_record(["Content"])?;
             }
 
             let cleaned_text = page.text.replace('\n', " ").replace('\r', "");
-            csv_writer.write_record(&[&cleaned_text])?;
+            csv_writer.write_record([&cleaned_text])?;
         } else {
             // Output tables from this page
             self.write_tables(&page.tables, writer)?;
@@ -186,7 +186,6 @@ impl CsvOutput {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::PdfMetadata;
 
     #[test]
     fn test_csv_table_output() {
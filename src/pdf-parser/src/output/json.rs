@@ -63,7 +63,6 @@ impl OutputWriter for JsonOutput {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::PdfMetadata;
 
     #[test]
     fn test_json_page_output() {
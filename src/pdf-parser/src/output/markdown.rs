@@ -176,7 +176,6 @@ impl MarkdownOutput {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::PdfMetadata;
 
     #[test]
     fn test_markdown_page_output() {
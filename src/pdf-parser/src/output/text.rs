@@ -57,7 +57,6 @@ impl Default for TextOutput {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::PdfMetadata;
 
     #[test]
     fn test_text_output() {
@@ -11,6 +11,12 @@ impl TextOutput {
     }
 }
 
+impl Default for TextOutput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OutputWriter for TextOutput {
     fn write_ppt_data<W: Write>(&self, data: &PptData, writer: &mut W) -> Result<()> {
         // Write presentation metadata as header
@@ -346,10 +346,8 @@ impl PptParser {
                         current_text.clear();
                     }
                 }
-                Ok(Event::Text(e)) => {
-                    if in_text_element {
-                        current_text.push_str(&e.unescape()?.to_string());
-                    }
+                Ok(Event::Text(e)) if in_text_element => {
+                    current_text.push_str(&e.unescape()?);
                 }
                 Ok(Event::End(ref e)) => {
                     let name = e.name();
@@ -457,6 +455,7 @@ impl PptParser {
 
     fn extract_table_rows(&self, content: &[String]) -> Vec<Vec<String>> {
         let mut rows = Vec::new();
+        let multiple_spaces = Regex::new(r"\s{2,}").unwrap();
         
         for item in content {
             if !self.looks_like_table_row(item) {
@@ -472,8 +471,7 @@ impl PptParser {
                     .collect()
             } else {
                 // Split on multiple spaces
-                let re = Regex::new(r"\s{2,}").unwrap();
-                re.split(item).map(|s| s.trim().to_string()).collect()
+                multiple_spaces.split(item).map(|s| s.trim().to_string()).collect()
             };
             
             if cells.len() > 1 {
@@ -502,7 +500,7 @@ impl PptParser {
                     trimmed[2..].to_string()
                 } else if trimmed.len() > 2 && trimmed.chars().next().unwrap().is_ascii_digit() {
                     // Skip number and dot
-                    trimmed.splitn(2, '.').nth(1).unwrap_or("").trim().to_string()
+                    trimmed.split_once('.').map_or("", |(_, rest)| rest).trim().to_string()
                 } else {
                     trimmed.to_string()
                 }